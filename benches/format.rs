@@ -0,0 +1,47 @@
+//! Compare the digits of [Format::Dec], [Format::Hex], [Format::Bin] and [Format::Octal] with
+//! what [format!] writes
+//!
+//! Run with `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use numf::format::{format_digits, Format, FormatOptions, NumberType};
+
+/// small numbers take the fast path, the last one is too large for it
+const NUMBERS: [NumberType; 4] = [7, 0x1337, u64::MAX as NumberType, u128::MAX];
+
+fn digits(c: &mut Criterion) {
+    for num in NUMBERS {
+        let mut group = c.benchmark_group(format!("digits of {num:#X}"));
+        group.bench_function("format_digits hex", |b| {
+            b.iter(|| format_digits::<16>(black_box(num)))
+        });
+        group.bench_function("format! hex", |b| {
+            b.iter(|| format!("{:X}", black_box(num)))
+        });
+        group.bench_function("format_digits dec", |b| {
+            b.iter(|| format_digits::<10>(black_box(num)))
+        });
+        group.bench_function("format! dec", |b| b.iter(|| format!("{}", black_box(num))));
+        group.bench_function("format_digits bin", |b| {
+            b.iter(|| format_digits::<2>(black_box(num)))
+        });
+        group.bench_function("format! bin", |b| {
+            b.iter(|| format!("{:b}", black_box(num)))
+        });
+        group.finish();
+    }
+}
+
+fn format(c: &mut Criterion) {
+    let options = FormatOptions::default();
+    let mut group = c.benchmark_group("Format::format");
+    for format in [Format::Dec, Format::Hex, Format::Bin, Format::Octal] {
+        group.bench_function(format.to_string(), |b| {
+            b.iter(|| format.format(black_box(0x1337), &options))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, digits, format);
+criterion_main!(benches);
@@ -0,0 +1,171 @@
+//! Reading numbers from CSV and writing them as CSV, for `--csv-in` and `--csv-out`
+//!
+//! This module is only there with the `csv` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use numf::csv_io::Table;
+//! use numf::format::{Format, FormatOptions};
+//!
+//! let table = Table::read(b"name,value\nfoo,0x10\n\"b,ar\",32\n", 2).unwrap();
+//! assert_eq!(table.cells(), vec!["0x10", "32"]);
+//!
+//! let mut options = FormatOptions::default();
+//! options.set_format(Format::Bin);
+//! let mut out = Vec::new();
+//! table.write(&mut out, &options, &[Some(16), Some(32)]).unwrap();
+//! assert_eq!(out, b"name,value\nfoo,10000\n\"b,ar\",100000\n");
+//! ```
+
+use std::io::Write;
+
+use anyhow::anyhow;
+
+use crate::format::{FormatOptions, NumberType};
+
+/// A CSV table with a column of numbers
+///
+/// The first row is the header, it is kept as is.
+#[derive(Clone, Debug)]
+pub struct Table {
+    header: csv::StringRecord,
+    rows: Vec<csv::StringRecord>,
+    /// the column of the numbers, counted from 0
+    column: usize,
+}
+
+impl Table {
+    /// Read a CSV table from `data`, with the numbers in `column`
+    ///
+    /// The columns are counted from 1, like with `cut`. Quoted cells are unquoted, and rows may
+    /// have different lengths.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the data is not valid CSV, or if the header does not have that many columns.
+    pub fn read(data: &[u8], column: usize) -> anyhow::Result<Table> {
+        if column == 0 {
+            return Err(anyhow!("the columns are counted from 1"));
+        }
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .flexible(true)
+            .from_reader(data);
+        let header = reader.headers()?.clone();
+        if header.len() < column {
+            return Err(anyhow!(
+                "there is no column {column}, the header has {} columns",
+                header.len()
+            ));
+        }
+        let rows = reader.records().collect::<Result<Vec<_>, _>>()?;
+        Ok(Table {
+            header,
+            rows,
+            column: column - 1,
+        })
+    }
+
+    /// The cells with the numbers, without the header
+    ///
+    /// Rows that are too short have an empty cell.
+    pub fn cells(&self) -> Vec<&str> {
+        self.rows
+            .iter()
+            .map(|row| row.get(self.column).unwrap_or(""))
+            .collect()
+    }
+
+    /// Write the table as CSV, with the numbers formatted in their column
+    ///
+    /// `numbers` has the number of every row, in the same order as [Self::cells], [None] keeps
+    /// the cell of that row as it is. If more than one format is selected in `options`, the
+    /// column is replaced by one column for every format, named after the format.
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `out` fails.
+    pub fn write(
+        &self,
+        out: &mut impl Write,
+        options: &FormatOptions,
+        numbers: &[Option<NumberType>],
+    ) -> anyhow::Result<()> {
+        let formats = options.formats();
+        // the rows are written with the lengths they were read with
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(out);
+        let header: Vec<String> = match formats.as_slice() {
+            [_] => self.header.iter().map(str::to_string).collect(),
+            _ => self.replace(
+                &self.header,
+                formats.iter().map(|format| format.to_string()).collect(),
+            ),
+        };
+        writer.write_record(&header)?;
+        for (row, num) in self.rows.iter().zip(numbers) {
+            let cells = match num {
+                Some(num) => self.replace(
+                    row,
+                    formats
+                        .iter()
+                        .map(|format| format.format_str(*num, options))
+                        .collect(),
+                ),
+                None => row.iter().map(str::to_string).collect(),
+            };
+            writer.write_record(&cells)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// the cells of `row`, with the cell of the numbers replaced by `cells`
+    fn replace(&self, row: &csv::StringRecord, cells: Vec<String>) -> Vec<String> {
+        let mut row: Vec<String> = row.iter().map(str::to_string).collect();
+        if row.len() <= self.column {
+            row.resize(self.column + 1, String::new());
+        }
+        row.splice(self.column..=self.column, cells);
+        row
+    }
+}
+
+/// Write numbers as CSV, with one row for every number and one column for every format
+///
+/// The header names the formats.
+///
+/// # Errors
+///
+/// Fails if writing to `out` fails.
+///
+/// # Example
+///
+/// ```
+/// use numf::csv_io::write_numbers;
+/// use numf::format::{Format, FormatOptions};
+///
+/// let mut options = FormatOptions::default();
+/// options.set_formats(&[Format::Hex, Format::Base85]);
+/// let mut out = Vec::new();
+/// write_numbers(&mut out, &options, &[0x1337, 10]).unwrap();
+/// assert_eq!(out, b"Hex,Base85\n1337,'/o\nA,$3\n");
+/// ```
+pub fn write_numbers(
+    out: &mut impl Write,
+    options: &FormatOptions,
+    numbers: &[NumberType],
+) -> anyhow::Result<()> {
+    let formats = options.formats();
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(formats.iter().map(|format| format.to_string()))?;
+    for num in numbers {
+        writer.write_record(
+            formats
+                .iter()
+                .map(|format| format.format_str(*num, options)),
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
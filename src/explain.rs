@@ -0,0 +1,311 @@
+//! This module explains how [numf](crate) formats numbers.
+//!
+//! It is used by the `--explain` flag of the executable, and shows the steps that lead to the
+//! output of a [Format]: the remainders of the repeated divisions for the formats with a base, and
+//! the grouped bits for [Format::Base64] and [Format::Base32].
+//!
+//! # Example
+//!
+//! ```
+//! use numf::explain::explain;
+//! use numf::format::Format;
+//!
+//! let lines = explain(255, Format::Hex);
+//! assert_eq!(lines[0], "255 / 16 = 15, remainder 15 -> 'F'");
+//! assert_eq!(lines.last().unwrap(), "result: FF");
+//! ```
+
+use crate::format::{
+    encode_negabinary, encode_zeckendorf, fibonacci_places, format_duration, format_radix,
+    to_bytes, Format, FormatOptions, NumberType, DURATION_UNITS, MORSE_DIGITS,
+};
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_CROCKFORD_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Explain how `num` is formatted with `format`
+///
+/// Returns the lines of the explanation, the last line is always the result. Prefixes and padding
+/// are not part of the explanation.
+pub fn explain(num: NumberType, format: Format) -> Vec<String> {
+    match format {
+        Format::Dec => explain_radix(num, 10),
+        Format::Hex => explain_radix(num, 16),
+        Format::Bin => explain_radix(num, 2),
+        Format::Octal => explain_radix(num, 8),
+        Format::Base64 => explain_bit_groups(num, 6, BASE64_ALPHABET, 4, format),
+        Format::Base32 => explain_bit_groups(num, 5, BASE32_ALPHABET, 8, format),
+        // no padding for crockford
+        Format::Base32Crockford => explain_bit_groups(num, 5, BASE32_CROCKFORD_ALPHABET, 1, format),
+        Format::Raw => explain_raw(num),
+        Format::Base85 => explain_base85(num),
+        Format::Duration => explain_duration(num),
+        Format::Base16 => explain_base16(num),
+        Format::Percent => explain_percent(num),
+        Format::Negabinary => explain_negabinary(num),
+        Format::Morse => explain_morse(num),
+        Format::Zeckendorf => explain_zeckendorf(num),
+    }
+}
+
+/// Explain how `num` is written in a base by repeatedly dividing it
+///
+/// # Panics
+///
+/// Panics if `radix` is not in `2..=36`.
+pub fn explain_radix(num: NumberType, radix: u32) -> Vec<String> {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in 2..=36, but is {radix}"
+    );
+    let mut lines = Vec::new();
+    let mut rest = num;
+    loop {
+        let quotient = rest / radix as NumberType;
+        let remainder = (rest % radix as NumberType) as u32;
+        // unwrap is fine, the remainder is always smaller than the radix
+        let digit = std::char::from_digit(remainder, radix)
+            .unwrap()
+            .to_ascii_uppercase();
+        lines.push(format!(
+            "{rest} / {radix} = {quotient}, remainder {remainder} -> '{digit}'"
+        ));
+        rest = quotient;
+        if rest == 0 {
+            break;
+        }
+    }
+    lines.push("the remainders in reverse order are the digits".to_string());
+    // unwrap is fine, we checked the radix above
+    lines.push(format!("result: {}", format_radix(num, radix).unwrap()));
+    lines
+}
+
+/// Explain how the bytes of `num` are split into groups of `bits` bits, which are then looked up
+/// in the alphabet
+fn explain_bit_groups(
+    num: NumberType,
+    bits: usize,
+    alphabet: &[u8],
+    block: usize,
+    format: Format,
+) -> Vec<String> {
+    let bytes = to_bytes(num);
+    let mut lines = vec![
+        format!("bytes: {}", hex_bytes(&bytes)),
+        format!(
+            "bits: {}",
+            bytes
+                .iter()
+                .map(|b| format!("{b:08b}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        format!("groups of {bits} bits:"),
+    ];
+    let bitstring: String = bytes.iter().map(|b| format!("{b:08b}")).collect();
+    let mut chars = 0;
+    for group in bitstring.as_bytes().chunks(bits) {
+        let group = String::from_utf8_lossy(group).to_string();
+        let missing = bits - group.len();
+        let padded = group.clone() + &"0".repeat(missing);
+        // unwrap is fine, this is a string of 0 and 1
+        let index = usize::from_str_radix(&padded, 2).unwrap();
+        let shown = if missing > 0 {
+            format!("{group} + {}", "0".repeat(missing))
+        } else {
+            group
+        };
+        lines.push(format!(
+            "  {shown} -> {index} -> '{}'",
+            alphabet[index] as char
+        ));
+        chars += 1;
+    }
+    let padding = (block - chars % block) % block;
+    if padding > 0 {
+        lines.push(format!(
+            "padding: {} to a multiple of {block} characters",
+            "=".repeat(padding)
+        ));
+    }
+    lines.push(format!(
+        "result: {}",
+        format.format_str(num, &FormatOptions::default())
+    ));
+    lines
+}
+
+/// Explain how groups of 4 bytes are written as 5 base85 digits
+fn explain_base85(num: NumberType) -> Vec<String> {
+    let bytes = to_bytes(num);
+    let mut lines = vec![
+        format!("bytes: {}", hex_bytes(&bytes)),
+        "groups of 4 bytes, as 5 digits in base 85, offset by '!':".to_string(),
+    ];
+    for chunk in bytes.chunks(4) {
+        if chunk == [0, 0, 0, 0] {
+            lines.push(format!("  {} -> 0 -> 'z'", hex_bytes(chunk)));
+            continue;
+        }
+        let missing = 4 - chunk.len();
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from_be_bytes(group);
+        let mut digits = [0u32; 5];
+        let mut rest = value;
+        for d in digits.iter_mut().rev() {
+            *d = rest % 85;
+            rest /= 85;
+        }
+        let chars: String = digits
+            .iter()
+            .take(chunk.len() + 1)
+            .map(|d| (*d as u8 + b'!') as char)
+            .collect();
+        let shown = if missing > 0 {
+            format!("{} + {}", hex_bytes(chunk), hex_bytes(&vec![0; missing]))
+        } else {
+            hex_bytes(chunk)
+        };
+        lines.push(format!("  {shown} -> {value} -> {digits:?} -> '{chars}'"));
+    }
+    lines.push(format!(
+        "result: {}",
+        Format::Base85.format_str(num, &FormatOptions::default())
+    ));
+    lines
+}
+
+/// Explain how an amount of seconds is split into the units of a duration
+fn explain_duration(num: NumberType) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = num;
+    for (unit, length) in DURATION_UNITS {
+        if length == 1 {
+            lines.push(format!("{rest} seconds left -> {rest}{unit}"));
+        } else {
+            lines.push(format!(
+                "{rest} / {length} = {}, remainder {} -> {}{unit}",
+                rest / length,
+                rest % length,
+                rest / length
+            ));
+        }
+        rest %= length;
+    }
+    lines.push("units that are zero are left out".to_string());
+    lines.push(format!("result: {}", format_duration(num, false)));
+    lines
+}
+
+/// Explain how every byte becomes two hexadecimal digits
+fn explain_base16(num: NumberType) -> Vec<String> {
+    let bytes = to_bytes(num);
+    let mut lines = vec![format!("bytes: {}", hex_bytes(&bytes))];
+    for b in &bytes {
+        lines.push(format!(
+            "  {b} / 16 = {}, remainder {} -> '{b:02X}'",
+            b / 16,
+            b % 16
+        ));
+    }
+    lines.push(format!(
+        "result: {}",
+        Format::Base16.format_str(num, &FormatOptions::default())
+    ));
+    lines
+}
+
+/// Explain how every byte is written as `%` and two hexadecimal digits
+fn explain_percent(num: NumberType) -> Vec<String> {
+    let bytes = to_bytes(num);
+    let mut lines = vec![format!("bytes: {}", hex_bytes(&bytes))];
+    for b in &bytes {
+        lines.push(format!("  0x{b:02X} -> '%{b:02X}'"));
+    }
+    lines.push(format!(
+        "result: {}",
+        Format::Percent.format_str(num, &FormatOptions::default())
+    ));
+    lines
+}
+
+/// Explain how `num` is written in base -2, by dividing it by -2 with a remainder of 0 or 1
+fn explain_negabinary(num: NumberType) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = num;
+    let mut negative = false;
+    loop {
+        let digit = rest % 2;
+        let quotient = if negative { rest / 2 + digit } else { rest / 2 };
+        let sign = |negative: bool, n: NumberType| if negative && n != 0 { "-" } else { "" };
+        lines.push(format!(
+            "{}{rest} / -2 = {}{quotient}, remainder {digit} -> '{digit}'",
+            sign(negative, rest),
+            sign(!negative, quotient)
+        ));
+        negative = !negative && quotient != 0;
+        rest = quotient;
+        if rest == 0 {
+            break;
+        }
+    }
+    lines.push("the remainders in reverse order are the digits".to_string());
+    lines.push(format!("result: {}", encode_negabinary(num)));
+    lines
+}
+
+/// Explain how every decimal digit is written in Morse code
+fn explain_morse(num: NumberType) -> Vec<String> {
+    let mut lines = vec![format!("decimal digits: {num}")];
+    for digit in num.to_string().bytes() {
+        lines.push(format!(
+            "  '{}' -> {}",
+            digit as char,
+            MORSE_DIGITS[(digit - b'0') as usize]
+        ));
+    }
+    lines.push("the codes are separated by spaces".to_string());
+    lines.push(format!(
+        "result: {}",
+        Format::Morse.format_str(num, &FormatOptions::default())
+    ));
+    lines
+}
+
+/// Explain how `num` is split into Fibonacci numbers, always taking the largest one that fits
+fn explain_zeckendorf(num: NumberType) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut rest = num;
+    for place in fibonacci_places().into_iter().rev() {
+        if place <= rest {
+            lines.push(format!(
+                "{rest} - {place} = {} -> '1' for {place}",
+                rest - place
+            ));
+            rest -= place;
+        }
+    }
+    lines.push("the places of the Fibonacci numbers that were not taken are '0'".to_string());
+    lines.push(format!("result: {}", encode_zeckendorf(num)));
+    lines
+}
+
+/// Explain which bytes are written for [Format::Raw]
+fn explain_raw(num: NumberType) -> Vec<String> {
+    let bytes = to_bytes(num);
+    vec![
+        format!("the minimal big-endian bytes of {num} are written as is"),
+        format!("result: {}", hex_bytes(&bytes)),
+    ]
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("0x{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
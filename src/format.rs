@@ -32,7 +32,10 @@
 //! ```
 
 #![allow(dead_code)]
+use std::borrow::Cow;
 use std::fmt::Display;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 
 // this is exported to lib.rs
 use anyhow::anyhow;
@@ -40,10 +43,68 @@ use clap::{ArgGroup, Parser};
 use libpt::bintols::{join, split};
 use libpt::cli::args::VerbosityLevel;
 use libpt::log::{debug, trace};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// The number type [numf](crate) uses
 pub type NumberType = u128;
 
+/// A value that can be formatted like a number, see [Format::format_value]
+///
+/// This is implemented for the unsigned integer types. Implement it for your own types, like a
+/// newtype around [u64], to format them without converting them first. Only [Self::to_number] is
+/// needed, the other methods are there for convenience.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{Format, FormatOptions, Formattable, NumberType};
+///
+/// struct Register(u64);
+///
+/// impl Formattable for Register {
+///     fn to_number(&self) -> NumberType {
+///         self.0 as NumberType
+///     }
+/// }
+///
+/// let options = FormatOptions::default();
+/// assert_eq!(Format::Hex.format_value(&Register(0x1337), &options), b"1337");
+/// assert_eq!(Register(0x1337).to_be_bytes_minimal(), vec![0x13, 0x37]);
+/// assert_eq!(Register(255).to_digits(2).unwrap(), "11111111");
+/// ```
+pub trait Formattable {
+    /// the value as the [NumberType] that is formatted
+    fn to_number(&self) -> NumberType;
+
+    /// the minimal big-endian bytes of the value, see [to_bytes]
+    fn to_be_bytes_minimal(&self) -> Vec<u8> {
+        to_bytes(self.to_number())
+    }
+
+    /// the digits of the value in a base from 2 to 36, see [format_radix]
+    ///
+    /// # Errors
+    ///
+    /// Fails if the radix is not in `2..=36`.
+    fn to_digits(&self, radix: u32) -> anyhow::Result<String> {
+        format_radix(self.to_number(), radix)
+    }
+}
+
+macro_rules! impl_formattable {
+    ($($t:ty),*) => {
+        $(
+            impl Formattable for $t {
+                fn to_number(&self) -> NumberType {
+                    *self as NumberType
+                }
+            }
+        )*
+    };
+}
+
+impl_formattable!(u8, u16, u32, u64, u128, usize);
+
 /// Describes a format for numbers
 ///
 /// [Format] can be used to convert unsigned integers into a textual or other representation. See
@@ -58,8 +119,73 @@ pub enum Format {
     Octal,
     Base64,
     Base32,
+    /// Base32 with the alphabet by Douglas Crockford
+    ///
+    /// The alphabet leaves out `I`, `L`, `O` and `U` to avoid confusion, and there is no padding.
+    /// When parsing, lowercase letters are accepted, `I` and `L` are read as `1`, `O` as `0`, and
+    /// hyphens are ignored. The optional check symbol is not supported.
+    Base32Crockford,
     /// Write raw data, not text
+    ///
+    /// The number is written as its minimal big-endian bytes (see [to_bytes]), so `0` is a
+    /// single `0x00` byte. With [FormatOptions::set_raw_full_width], all 16 bytes are written
+    /// instead. The [prefix](Format::prefix) is a single `0x00` byte in front of that.
+    /// [FormatOptions::set_raw_minimal] makes sure that only the minimal bytes are written.
+    ///
+    /// [numf_parser] reads this back: a single `0x00` is `0`, and if there is more data, a
+    /// leading `0x00` is treated as the prefix. The prefix byte can be changed with
+    /// [FormatOptions::set_raw_prefix], use [FormatOptions::parse_bytes] to parse such data.
+    ///
+    /// Without the prefix, raw data that happens to be valid text (like `0x3132`, which is
+    /// `"12"`) is parsed as text, so use the prefix when the output should be parsed again.
     Raw,
+    /// Ascii85 as used by Adobe in PostScript and PDF
+    ///
+    /// Every group of 4 bytes is written as 5 characters from `!` to `u`, a group of 4 zero bytes
+    /// is shortened to `z`. The `<~` and `~>` delimiters are not written.
+    Base85,
+    /// A duration, the number is the amount of seconds
+    ///
+    /// The number is written in weeks, days, hours, minutes and seconds, like `1h2m3s`, leaving
+    /// out the units that are zero. With [FormatOptions::set_clock], it is written like a clock
+    /// instead, like `01:02:03`, the hours may be more than 24 then.
+    ///
+    /// When parsing, both styles are accepted, with or without the [prefix](Format::prefix). Use
+    /// the prefix if the output should be parsed again, as `0s` without it is read as
+    /// [Format::Base64].
+    Duration,
+    /// Base16 as defined in RFC 4648
+    ///
+    /// Unlike [Format::Hex], which writes the number like a programmer would, this encodes the
+    /// bytes of the number (see [to_bytes]), so there are always two uppercase digits per byte,
+    /// like `0F` for 15. [FormatOptions::padding] and the other options for [Format::Hex] have no
+    /// effect. When parsing, lowercase digits are accepted, but the length must be even.
+    Base16,
+    /// URL percent-encoding of the bytes of the number, like `%13%37`
+    ///
+    /// Every byte of [to_bytes] is encoded, even the ones that would not need it in a URL, so `0`
+    /// is `%00`. The digits are uppercase, as RFC 3986 recommends. When parsing, lowercase digits
+    /// are accepted, and the [prefix](Format::prefix) is optional.
+    Percent,
+    /// Negabinary, the digits `0` and `1` in base -2
+    ///
+    /// The places are worth 1, -2, 4, -8 and so on, so `2` is `110` (4 - 2). Base -2 can show
+    /// negative numbers without a sign, but only the unsigned numbers of [NumberType] are
+    /// formatted here. [u128::MAX] needs 129 digits.
+    Negabinary,
+    /// The decimal digits of the number in Morse code, like `.---- ...--` for 13
+    ///
+    /// The digits are separated by a single space. When parsing, any amount of spaces between the
+    /// digits is accepted, and the [prefix](Format::prefix) is optional. A number is a single
+    /// word, so the word separator `/` is an error.
+    Morse,
+    /// The Zeckendorf representation, the number as a sum of Fibonacci numbers
+    ///
+    /// Every number is a sum of Fibonacci numbers of which no two are next to each other, and
+    /// there is only one such sum. The digits say which Fibonacci numbers are used, the largest
+    /// first, so the places are worth 1, 2, 3, 5, 8 and so on. For example, `12` is `10101`
+    /// (8 + 3 + 1). When parsing, two `1` next to each other are an error.
+    Zeckendorf,
 }
 
 impl Display for Format {
@@ -68,6 +194,601 @@ impl Display for Format {
     }
 }
 
+/// Parse a [Format] from its name
+///
+/// The names are case insensitive. Besides the full names, the short flags of the executable and
+/// a few common abbreviations are accepted:
+///
+/// | Format | Names |
+/// |--------|-------|
+/// | [Format::Dec] | `dec`, `decimal`, `d` |
+/// | [Format::Hex] | `hex`, `hexadecimal`, `x` |
+/// | [Format::Bin] | `bin`, `binary`, `b` |
+/// | [Format::Octal] | `oct`, `octal`, `o` |
+/// | [Format::Base64] | `base64`, `b64`, `s` |
+/// | [Format::Base32] | `base32`, `b32`, `z` |
+/// | [Format::Base32Crockford] | `base32crockford`, `base32-crockford`, `crockford` |
+/// | [Format::Raw] | `raw`, `a` |
+/// | [Format::Base85] | `base85`, `ascii85`, `b85` |
+/// | [Format::Duration] | `duration`, `time`, `t` |
+/// | [Format::Base16] | `base16`, `b16` |
+/// | [Format::Percent] | `percent`, `url` |
+/// | [Format::Negabinary] | `negabinary`, `nega` |
+/// | [Format::Morse] | `morse` |
+/// | [Format::Zeckendorf] | `zeckendorf`, `fibonacci`, `fib` |
+///
+/// # Example
+///
+/// ```
+/// use numf::format::Format;
+///
+/// assert_eq!("hex".parse::<Format>().unwrap(), Format::Hex);
+/// assert_eq!("Base64".parse::<Format>().unwrap(), Format::Base64);
+/// assert_eq!("b".parse::<Format>().unwrap(), Format::Bin);
+/// assert!("foo".parse::<Format>().is_err());
+/// ```
+impl std::str::FromStr for Format {
+    type Err = NumfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "dec" | "decimal" | "d" => Format::Dec,
+            "hex" | "hexadecimal" | "x" => Format::Hex,
+            "bin" | "binary" | "b" => Format::Bin,
+            "oct" | "octal" | "o" => Format::Octal,
+            "base64" | "b64" | "s" => Format::Base64,
+            "base32" | "b32" | "z" => Format::Base32,
+            "base32crockford" | "base32-crockford" | "crockford" => Format::Base32Crockford,
+            "raw" | "a" => Format::Raw,
+            "base85" | "ascii85" | "b85" => Format::Base85,
+            "duration" | "time" | "t" => Format::Duration,
+            "base16" | "b16" => Format::Base16,
+            "percent" | "url" => Format::Percent,
+            "negabinary" | "nega" => Format::Negabinary,
+            "morse" => Format::Morse,
+            "zeckendorf" | "fibonacci" | "fib" => Format::Zeckendorf,
+            _ => return Err(NumfError::UnknownFormatName(s.to_string())),
+        })
+    }
+}
+
+/// Same as [Format::name]
+impl From<Format> for &'static str {
+    fn from(value: Format) -> Self {
+        value.name()
+    }
+}
+
+/// Same as [Format::from_str](std::str::FromStr::from_str)
+impl TryFrom<&str> for Format {
+    type Error = NumfError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Get the [Format] at that index of [Format::ALL]
+///
+/// # Example
+///
+/// ```
+/// use numf::format::Format;
+///
+/// assert_eq!(Format::try_from(0).unwrap(), Format::Dec);
+/// assert_eq!(Format::try_from(1).unwrap(), Format::Hex);
+/// assert!(Format::try_from(200).is_err());
+/// ```
+impl TryFrom<u8> for Format {
+    type Error = NumfError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Format::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or(NumfError::InvalidFormatIndex(value))
+    }
+}
+
+/// Errors that are specific to [numf](crate)
+///
+/// The parsers return [anyhow::Result], so these errors are usually wrapped in an
+/// [anyhow::Error]. Use [anyhow::Error::downcast_ref] to inspect them.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum NumfError {
+    /// The input was a number with a leading `-`, but [numf](crate) only works with unsigned
+    /// numbers
+    NegativeNotSupported(String),
+    /// A base was requested that is not in `2..=36`
+    InvalidRadix(u32),
+    /// The digit is not valid for numbers in that base
+    InvalidDigit { digit: char, radix: u32 },
+    /// There is no [Format] with that name
+    UnknownFormatName(String),
+    /// There is no [Format] at that index of [Format::ALL]
+    InvalidFormatIndex(u8),
+    /// A bit width was requested that is not in `1..=128`
+    InvalidWidth(u32),
+    /// The value does not fit into that many bits
+    ValueTooWide { value: NumberType, width: u32 },
+    /// A bit width was requested that is not a multiple of 8
+    NotByteAligned(u32),
+    /// The input matches none of the text [Formats](Format), and raw input is not accepted
+    UnknownFormat(String),
+    /// The input looks like that [Format], but is not a valid number in it
+    InvalidNumber { format: Format, reason: String },
+    /// The alphabet for [format_alphabet] can not be used, the string is the reason
+    InvalidAlphabet(String),
+    /// That symbol is not in the alphabet, see [numf_parser_alphabet]
+    NotInAlphabet(String),
+    /// The grouping for [BaseGroup] can not be used, the string is the reason
+    InvalidBaseGroup(String),
+    /// The buffer for [Format::format_slice] is too small, `needed` bytes would be needed
+    BufferTooSmall { needed: usize, capacity: usize },
+    /// That [Format] can not be formatted without allocating, see [Format::format_slice]
+    NeedsAllocation(Format),
+}
+
+impl Display for NumfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NegativeNotSupported(s) => {
+                write!(f, "negative numbers are not supported: {s}")
+            }
+            Self::InvalidRadix(radix) => write!(f, "base must be in 2..=36, not {radix}"),
+            Self::InvalidDigit { digit, radix } => {
+                write!(f, "'{digit}' is not a valid digit in base {radix}")
+            }
+            Self::UnknownFormatName(name) => write!(f, "unknown format: {name}"),
+            Self::InvalidFormatIndex(index) => write!(f, "no format at index {index}"),
+            Self::InvalidWidth(width) => write!(f, "width must be in 1..=128, not {width}"),
+            Self::ValueTooWide { value, width } => {
+                write!(f, "{value} does not fit into {width} bits")
+            }
+            Self::NotByteAligned(width) => {
+                write!(f, "width must be a multiple of 8, not {width}")
+            }
+            Self::UnknownFormat(s) => write!(f, "not a number in any known format: {s:?}"),
+            Self::InvalidNumber { format, reason } => {
+                write!(f, "not a valid number in {format}: {reason}")
+            }
+            Self::InvalidAlphabet(reason) => write!(f, "invalid alphabet: {reason}"),
+            Self::NotInAlphabet(symbol) => write!(f, "'{symbol}' is not in the alphabet"),
+            Self::InvalidBaseGroup(reason) => write!(f, "invalid grouping: {reason}"),
+            Self::BufferTooSmall { needed, capacity } => {
+                write!(
+                    f,
+                    "the buffer has {capacity} bytes, but {needed} are needed"
+                )
+            }
+            Self::NeedsAllocation(format) => {
+                write!(f, "{format} can not be formatted without allocating")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NumfError {}
+
+/// The IEEE-754 floating point types, for reading and showing numbers as floats
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum FloatType {
+    /// single precision, 32 bits
+    F32,
+    /// double precision, 64 bits
+    F64,
+}
+
+impl FloatType {
+    /// the amount of bits of that type
+    pub fn bits(&self) -> u32 {
+        match self {
+            FloatType::F32 => 32,
+            FloatType::F64 => 64,
+        }
+    }
+}
+
+impl Display for FloatType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FloatType::F32 => write!(f, "f32"),
+            FloatType::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+/// The unsigned integer types of C, for writing numbers with exactly the size of that type
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum CType {
+    /// `uint8_t`, 8 bits
+    U8,
+    /// `uint16_t`, 16 bits
+    U16,
+    /// `uint32_t`, 32 bits
+    U32,
+    /// `uint64_t`, 64 bits
+    U64,
+    /// `unsigned __int128`, 128 bits
+    U128,
+}
+
+impl CType {
+    /// the amount of bits of that type
+    pub fn bits(&self) -> u32 {
+        match self {
+            CType::U8 => 8,
+            CType::U16 => 16,
+            CType::U32 => 32,
+            CType::U64 => 64,
+            CType::U128 => 128,
+        }
+    }
+
+    /// the amount of bytes of that type
+    pub fn bytes(&self) -> usize {
+        self.bits() as usize / 8
+    }
+
+    /// Check that `num` fits into this type
+    ///
+    /// # Errors
+    ///
+    /// Returns [NumfError::ValueTooWide] if the number has more bits than the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::CType;
+    ///
+    /// assert!(CType::U16.check(0xFFFF).is_ok());
+    /// assert!(CType::U16.check(0x10000).is_err());
+    /// ```
+    pub fn check(&self, num: NumberType) -> Result<(), NumfError> {
+        if self.bits() < NumberType::BITS && num >> self.bits() != 0 {
+            return Err(NumfError::ValueTooWide {
+                value: num,
+                width: self.bits(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Display for CType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "u{}", self.bits())
+    }
+}
+
+/// Groups of characters for [Format::Base64] and [Format::Base32], like `QUFC-Qg==`
+///
+/// The groups are counted from the left, and the `=` padding is part of them.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BaseGroup {
+    /// the amount of characters in a group
+    size: NonZeroUsize,
+    /// what is put between the groups
+    separator: String,
+}
+
+impl BaseGroup {
+    /// Groups of `size` characters, separated by `separator`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the separator is empty, or if it contains characters of base64 or base32, because
+    /// then it could not be removed from the input again.
+    pub fn new(size: NonZeroUsize, separator: &str) -> Result<BaseGroup, NumfError> {
+        let invalid = |reason: &str| NumfError::InvalidBaseGroup(reason.to_string());
+        if separator.is_empty() {
+            return Err(invalid("the separator must not be empty"));
+        }
+        if separator
+            .chars()
+            .any(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='))
+        {
+            return Err(invalid(
+                "the separator can not contain characters of base64 or base32",
+            ));
+        }
+        Ok(BaseGroup {
+            size,
+            separator: separator.to_string(),
+        })
+    }
+
+    /// the amount of characters in a group
+    pub fn size(&self) -> usize {
+        self.size.get()
+    }
+
+    /// what is put between the groups
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+}
+
+/// Parse a [BaseGroup] from `N:SEP`, like `4:-`
+///
+/// The separator must not be empty, and it can not contain characters of base64 or base32,
+/// so that it can be removed from the input again.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::BaseGroup;
+///
+/// let group: BaseGroup = "4:-".parse().unwrap();
+/// assert_eq!(group.size(), 4);
+/// assert_eq!(group.separator(), "-");
+/// assert!("0:-".parse::<BaseGroup>().is_err());
+/// assert!("4:".parse::<BaseGroup>().is_err());
+/// assert!("4:A".parse::<BaseGroup>().is_err());
+/// ```
+impl std::str::FromStr for BaseGroup {
+    type Err = NumfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| NumfError::InvalidBaseGroup(reason.to_string());
+        let (size, separator) = s
+            .split_once(':')
+            .ok_or_else(|| invalid("expected N:SEP, like 4:-"))?;
+        let size: usize = size
+            .parse()
+            .map_err(|_| invalid("the size must be a number"))?;
+        let size = NonZeroUsize::new(size).ok_or_else(|| invalid("the size must be at least 1"))?;
+        BaseGroup::new(size, separator)
+    }
+}
+
+impl Display for BaseGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.size, self.separator)
+    }
+}
+
+/// The ways to combine a list of numbers into one, see [Reduce::reduce]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum Reduce {
+    /// add all numbers, wrapping around on overflow
+    Sum,
+    /// multiply all numbers, wrapping around on overflow
+    Product,
+    /// bitwise exclusive or of all numbers
+    Xor,
+    /// bitwise and of all numbers
+    And,
+    /// bitwise or of all numbers
+    Or,
+    /// the smallest number
+    Min,
+    /// the largest number
+    Max,
+}
+
+impl Reduce {
+    /// Combine `numbers` into a single number
+    ///
+    /// [Reduce::Sum] and [Reduce::Product] wrap around at the bounds of [NumberType] instead of
+    /// failing, like the unsigned arithmetic in most languages. Returns [None] if `numbers` is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::Reduce;
+    ///
+    /// assert_eq!(Reduce::Sum.reduce(&[1, 2, 3]), Some(6));
+    /// assert_eq!(Reduce::Sum.reduce(&[u128::MAX, 2]), Some(1));
+    /// assert_eq!(Reduce::Xor.reduce(&[0xFF, 0x0F]), Some(0xF0));
+    /// assert_eq!(Reduce::Max.reduce(&[1, 3, 2]), Some(3));
+    /// assert_eq!(Reduce::Min.reduce(&[]), None);
+    /// ```
+    pub fn reduce(&self, numbers: &[NumberType]) -> Option<NumberType> {
+        let numbers = numbers.iter().copied();
+        match self {
+            Reduce::Sum => numbers.reduce(NumberType::wrapping_add),
+            Reduce::Product => numbers.reduce(NumberType::wrapping_mul),
+            Reduce::Xor => numbers.reduce(|a, b| a ^ b),
+            Reduce::And => numbers.reduce(|a, b| a & b),
+            Reduce::Or => numbers.reduce(|a, b| a | b),
+            Reduce::Min => numbers.min(),
+            Reduce::Max => numbers.max(),
+        }
+    }
+}
+
+impl Display for Reduce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reduce::Sum => write!(f, "sum"),
+            Reduce::Product => write!(f, "product"),
+            Reduce::Xor => write!(f, "xor"),
+            Reduce::And => write!(f, "and"),
+            Reduce::Or => write!(f, "or"),
+            Reduce::Min => write!(f, "min"),
+            Reduce::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// The conventions for the prefixes of the formats, see [Format::prefix_for]
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrefixStyle {
+    /// the prefixes of numf, like `0d` for decimal and `0o` for octal
+    #[default]
+    Numf,
+    /// the prefixes of C, no prefix for decimal and `0` for octal
+    ///
+    /// The formats that C has no prefix for keep the prefixes of numf.
+    C,
+}
+
+impl Display for PrefixStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefixStyle::Numf => write!(f, "numf"),
+            PrefixStyle::C => write!(f, "c"),
+        }
+    }
+}
+
+/// The programming languages for `--lang`, which writes the numbers as literals of that language
+///
+/// Only [Format::Dec], [Format::Hex], [Format::Bin] and [Format::Octal] can be written as literals.
+/// For 4919, or `0x1337`, they are:
+///
+/// | Language | Dec | Hex | Bin | Octal |
+/// |----------|-----|-----|-----|-------|
+/// | rust | `4_919u32` | `0x1337u32` | `0b1_0011_0011_0111u32` | `0o11467u32` |
+/// | c | `4919U` | `0x1337U` | `0b1001100110111U` | `011467U` |
+/// | python | `4919` | `0x1337` | `0b1001100110111` | `0o11467` |
+///
+/// Rust groups the digits with `_`, in groups of 3 for decimal and 4 for hex and binary. Its
+/// suffix is the type of `--ctype`, or else the smallest of `u32`, `u64` and `u128` that fits the
+/// number. C has the suffix `U` up to 32 bits and `ULL` up to 64 bits, there are no literals for
+/// larger numbers in C. Python has no suffixes.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum Lang {
+    /// literals of Rust, like `0xDEAD_BEEFu32`
+    Rust,
+    /// literals of C, like `0xDEADBEEFU`
+    C,
+    /// literals of Python, like `0xDEADBEEF`
+    Python,
+}
+
+impl Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lang::Rust => write!(f, "rust"),
+            Lang::C => write!(f, "c"),
+            Lang::Python => write!(f, "python"),
+        }
+    }
+}
+
+/// How text is read as numbers with `--text-in`, see [text_to_numbers]
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum TextIn {
+    /// one number for every byte, like 65 and 66 for `AB`
+    ///
+    /// Characters that are not ASCII have more than one byte in UTF-8, so they are more than
+    /// one number.
+    #[default]
+    Bytes,
+    /// all bytes are one big-endian number, like `0x4142` for `AB`
+    Combined,
+}
+
+impl Display for TextIn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextIn::Bytes => write!(f, "bytes"),
+            TextIn::Combined => write!(f, "combined"),
+        }
+    }
+}
+
+/// The check digits that `--check` appends to decimal numbers
+///
+/// # Example
+///
+/// ```
+/// use numf::format::Check;
+///
+/// assert_eq!(Check::Luhn.compute(7992739871), "3");
+/// assert_eq!(Check::Mod97.compute(123456), "76");
+/// ```
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, clap::ValueEnum)]
+pub enum Check {
+    /// one digit of the Luhn algorithm, like for credit card numbers
+    Luhn,
+    /// two digits of ISO 7064 MOD 97-10, like for IBANs
+    Mod97,
+}
+
+impl Check {
+    /// how many digits are appended
+    pub fn width(&self) -> usize {
+        match self {
+            Check::Luhn => 1,
+            Check::Mod97 => 2,
+        }
+    }
+
+    /// the check digits for the decimal digits of `num`
+    pub fn compute(&self, num: NumberType) -> String {
+        match self {
+            Check::Luhn => {
+                // every second digit is doubled, starting right next to the check digit
+                let mut sum = 0;
+                let mut rest = num;
+                let mut double = true;
+                loop {
+                    let mut digit = (rest % 10) as u32;
+                    if double {
+                        digit *= 2;
+                        if digit > 9 {
+                            digit -= 9;
+                        }
+                    }
+                    sum += digit;
+                    double = !double;
+                    rest /= 10;
+                    if rest == 0 {
+                        break;
+                    }
+                }
+                ((10 - sum % 10) % 10).to_string()
+            }
+            Check::Mod97 => {
+                // the number with the check digits appended is 1 modulo 97
+                let remainder = (num % 97) * 100 % 97;
+                format!("{:02}", 98 - remainder)
+            }
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Luhn => write!(f, "luhn"),
+            Check::Mod97 => write!(f, "mod97"),
+        }
+    }
+}
+
+/// The case of the letters of a [Format], see [Format::case]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Case {
+    /// uppercase letters, like `0xFF`
+    Upper,
+    /// lowercase letters, like `0xff`
+    Lower,
+}
+
+impl Case {
+    /// change the ASCII letters of `text` to this case
+    pub fn apply(&self, text: &mut [u8]) {
+        match self {
+            Case::Upper => text.make_ascii_uppercase(),
+            Case::Lower => text.make_ascii_lowercase(),
+        }
+    }
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Case::Upper => write!(f, "upper"),
+            Case::Lower => write!(f, "lower"),
+        }
+    }
+}
+
 /// Describes what the formatter should do exactly
 ///
 /// Use [Self::default] to get a basic variant or create a object yourself.
@@ -109,7 +830,8 @@ Author: {author-with-newline}
 )]
 #[clap(group(
             ArgGroup::new("format")
-                .args(&["hex", "bin", "oct", "dec", "base64", "base32", "raw"]),
+                .args(&["hex", "bin", "oct", "dec", "base64", "base32", "base32_crockford", "raw", "base85", "duration", "base16", "percent", "negabinary", "morse", "zeckendorf"])
+                .multiple(true),
         ))]
 pub struct FormatOptions {
     #[arg(short, long)]
@@ -119,7 +841,9 @@ pub struct FormatOptions {
     /// add a padding to make the number at least one byte long
     ///
     /// For example, `0b1100` will be `0b00001100` with this.
-    /// This does not apply to all formats, only hexadecimal and binary.
+    /// This does not apply to all formats, only hexadecimal, binary and octal. Octal is padded to
+    /// as many digits as the whole bytes need, so `0o644` will be `0o000644`. Use --min-digits
+    /// for other widths, and for decimal.
     padding: bool,
     #[arg(short = 'x', long)]
     /// format to hexadecimal
@@ -152,7 +876,37 @@ pub struct FormatOptions {
     #[arg(short = 'z', long)]
     /// format to base32
     base32: bool,
-    #[clap(value_parser=numf_parser_str::<NumberType>, required=false)]
+    #[arg(long)]
+    /// format to base32 with the Crockford alphabet
+    base32_crockford: bool,
+    #[arg(long)]
+    /// format to base85 (Adobe Ascii85)
+    base85: bool,
+    #[arg(long)]
+    /// format as a duration, the numbers are seconds
+    ///
+    /// For example, 3723 will be 1h2m3s.
+    duration: bool,
+    #[arg(long)]
+    /// format to base16 as in RFC 4648, two digits for every byte
+    base16: bool,
+    #[arg(long)]
+    /// format to URL percent-encoding, like %13%37
+    percent: bool,
+    #[arg(long)]
+    /// format to negabinary, base -2
+    negabinary: bool,
+    #[arg(long)]
+    /// format the decimal digits to Morse code, like .---- ...-- for 13
+    morse: bool,
+    #[arg(long)]
+    /// format to the Zeckendorf representation, a sum of Fibonacci numbers like 10101 for 12
+    zeckendorf: bool,
+    #[clap(
+        required = false,
+        value_name = "NUMBERS",
+        allow_negative_numbers = true
+    )]
     /// numbers that should be formatted
     ///
     /// Any of the [Formats](Format::format) are supported, but the prefixes are needed for formats
@@ -160,7 +914,7 @@ pub struct FormatOptions {
     ///
     /// Formats:
     ///
-    /// * '0x' - Hexadecimal
+    /// * '0x' - Hexadecimal, '#' works too (like the colors of HTML, '#1a2b3c')
     ///
     /// * '0b' - Binary
     ///
@@ -170,18 +924,603 @@ pub struct FormatOptions {
     ///
     /// * '032s' - Base32
     ///
+    /// * '032c' - Base32 (Crockford)
+    ///
+    /// * '085s' - Base85 (Ascii85)
+    ///
+    /// * '0t' - Duration (like '0t1h2m3s' or '0t01:02:03')
+    ///
+    /// * '016s' - Base16 (RFC 4648)
+    ///
+    /// * '0p' - Percent-encoding (like '%13%37', the prefix is optional)
+    ///
+    /// * '0n' - Negabinary (base -2)
+    ///
+    /// * '0m' - Morse code (like '.---- ...--', the prefix is optional)
+    ///
+    /// * '0z' - Zeckendorf representation (a sum of Fibonacci numbers)
+    ///
     /// The numbers may be left empty at first, if numbers are provided from the stdin.
+    raw_numbers: Vec<String>,
+    #[arg(skip)]
     numbers: Vec<NumberType>,
-
-    #[command(flatten)]
-    pub(crate) verbosity: VerbosityLevel,
-}
-
-impl FormatOptions {
-    /// get the format that the user has configured
-    pub fn format(&self) -> Format {
-        trace!("self.hex: {}", self.hex);
-        if self.oct {
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2..=36))]
+    /// parse the input numbers in this base (2 to 36)
+    ///
+    /// The prefixes are not detected with this, all numbers must be written in this base.
+    from_base: Option<u32>,
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2..=36), conflicts_with = "format")]
+    /// format to this base (2 to 36)
+    ///
+    /// Digits above 9 are written as uppercase letters, like with hexadecimal.
+    to_base: Option<u32>,
+    #[arg(long, default_value_t = 0, value_parser=numf_parser_str::<u8>)]
+    /// the byte used as prefix for raw data
+    ///
+    /// This is used when formatting raw data with --prefix, and is skipped when it's the first
+    /// byte of raw input. The default is 0x00, which can not be told apart from data that starts
+    /// with zero bytes.
+    raw_prefix: u8,
+    #[arg(long)]
+    /// print how many digits each number needs instead of the number
+    ///
+    /// The digits are counted in the base of the selected format, or in the base given with
+    /// --to-base. This only works for formats with a base, like hexadecimal or binary.
+    digits: bool,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(1..=128))]
+    /// reverse the order of the bits of the numbers, with that many bits
+    ///
+    /// For example, 0b00000001 with a width of 8 becomes 0b10000000. This is done before the
+    /// numbers are formatted, and before --byte-swap.
+    bit_reverse: Option<u32>,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(8..=128))]
+    /// reverse the order of the bytes of the numbers, with that many bits
+    ///
+    /// This changes the endianness of the numbers, the width must be a multiple of 8. This is
+    /// done before the numbers are formatted.
+    byte_swap: Option<u32>,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(2..=128))]
+    /// store the numbers as sign-magnitude numbers with that many bits
+    ///
+    /// The numbers are read as two's complement, so -5 (with --signed) or 0xFB with a width of 8
+    /// becomes 0x85: the sign in the top bit, and the magnitude in the other bits. The smallest
+    /// number, like -128 for 8 bits, has no sign-magnitude form and is an error. This is done
+    /// after the other transformations. With --signed, decimal output reads the sign-magnitude
+    /// number back, so -5 stays -5.
+    sign_magnitude: Option<u32>,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(1..=128))]
+    /// show the numbers as signed decimal numbers with that many bits
+    ///
+    /// The numbers are interpreted as two's complement, so 0xFFFFFFFF with a width of 32 is -1.
+    /// Numbers that do not fit into the width are an error.
+    as_signed: Option<u32>,
+    #[arg(long, requires = "width", conflicts_with = "float")]
+    /// read and write decimal numbers as signed two's complement numbers with --width bits
+    ///
+    /// Input numbers may start with '-' then, so -1 with a width of 16 is 0xFFFF. Input like
+    /// 0xFFFF is taken as the bits of the number, and decimal output shows it as -1. Numbers that
+    /// do not fit into the width are an error.
+    signed: bool,
+    #[arg(long, conflicts_with = "from_base")]
+    /// show how many of the input numbers were in which format
+    ///
+    /// The summary is printed to stderr after the output. Random numbers are not counted.
+    stats: bool,
+    #[arg(long)]
+    /// show the steps of the conversion instead of just the result
+    ///
+    /// For formats with a base, the remainders of the divisions are shown. For base64 and base32,
+    /// the grouped bits are shown.
+    explain: bool,
+    #[arg(long)]
+    /// machine-readable output for scripts
+    ///
+    /// Disables logging and prints errors as a single line to stderr, without the usage. The
+    /// output is always one number per line (unless the format is raw).
+    porcelain: bool,
+    #[arg(long, value_name = "PATH")]
+    /// write the output to this file instead of the stdout
+    ///
+    /// The file is created or truncated. Together with --raw, this writes a binary blob.
+    output_file: Option<PathBuf>,
+    #[arg(long)]
+    /// write durations like a clock, as 01:02:03 instead of 1h2m3s
+    clock: bool,
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=128))]
+    /// the width of the numbers in bits
+    ///
+    /// Random numbers from --rand are generated to fit into this width, unless --rand-max is
+    /// even lower.
+    width: Option<u32>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    /// separate binary numbers into groups of N bits with spaces
+    ///
+    /// The groups are counted from the right, after the padding, so 0x1337 with --padding and a
+    /// group of 4 is 0001 0011 0011 0111. The prefix is not part of the groups.
+    bin_group: Option<usize>,
+    #[arg(long, value_name = "TYPE", conflicts_with = "from_base")]
+    /// read the input numbers as floats and use their bits
+    ///
+    /// For example, 1.5 is 0x3FF8000000000000 as f64 and 0x3FC00000 as f32, and -2 is
+    /// 0xC000000000000000 as f64. NaN and inf are accepted too, but -inf has to come after --, so
+    /// that it is not read as a flag.
+    float: Option<FloatType>,
+    #[arg(long, value_name = "TYPE", conflicts_with = "as_signed")]
+    /// show the numbers as floats, reading their bits as that type
+    ///
+    /// The numbers must fit into the width of the type, 32 bits for f32 and 64 bits for f64.
+    as_float: Option<FloatType>,
+    #[arg(long = "no-base-padding")]
+    /// leave out the '=' padding of base64 and base32
+    ///
+    /// Input without the padding is always accepted.
+    strip_base_padding: bool,
+    #[arg(long)]
+    /// check that every formatted number parses back to the same number
+    ///
+    /// The numbers are formatted with the prefix for this check, so that the format can be
+    /// detected. A mismatch is an error, and nothing is written then.
+    verify: bool,
+    #[arg(long)]
+    /// do not accept raw input, only numbers in one of the text formats
+    ///
+    /// Without this, anything that is not a known text format is read as raw bytes, so a typo
+    /// like "12a" becomes a number.
+    strict: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    /// repeat the list of numbers N times
+    ///
+    /// This is done after the random numbers were added, so `--count 2 1 2` outputs 1, 2, 1, 2.
+    count: Option<usize>,
+    #[arg(long, value_name = "N")]
+    /// add leading zeros until the number has at least N digits
+    ///
+    /// This applies to hexadecimal, binary, octal and decimal, after --padding. For example,
+    /// `--octal --min-digits 4` formats 420 as 0644.
+    min_digits: Option<usize>,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(8..=128))]
+    /// show hexadecimal and binary numbers with their bytes in little-endian order
+    ///
+    /// The number is shown with all bytes of the width, so 0x1337 with a width of 32 is 37130000.
+    /// Unlike --byte-swap, this only changes how the number is shown, the width must be a multiple
+    /// of 8.
+    le_display: Option<u32>,
+    #[arg(long, value_name = "STR", default_value = "")]
+    /// put this label in front of every line of output
+    ///
+    /// The label is separated from the number by a space, raw output is not labeled. An empty
+    /// label is the same as no label.
+    tag: String,
+    #[arg(long, value_name = "CHARS", default_value = "")]
+    /// ignore these characters in input numbers, like the commas in 1,000,000
+    ///
+    /// For example, `--digit-separators ", '"` accepts 1,000,000 and 1'000'000 and "1 000 000".
+    /// Input from the stdin is split at whitespace before this, so spaces only work in arguments.
+    /// Raw input is not changed, also if it is text like "a,b" that is no number without the
+    /// separators either.
+    digit_separators: String,
+    #[arg(long)]
+    /// skip input numbers that can not be parsed, instead of stopping
+    ///
+    /// Every skipped input is reported on stderr. If some inputs were skipped, the exit code is 3
+    /// after the output. If all of them were skipped, the exit code is 2, like without this flag.
+    keep_going: bool,
+    #[arg(long, value_name = "C", default_value_t = '0')]
+    /// the character for --padding and --min-digits
+    ///
+    /// Only '0' can go between the prefix and the digits without changing the number. Any other
+    /// character, like a space, is put in front of the prefix instead, to align the output. For
+    /// example, `--pad-char ' ' --min-digits 4 -xp 15` gives "   0xF".
+    pad_char: char,
+    #[arg(long, value_name = "N")]
+    /// fail if a formatted number is wider than N
+    ///
+    /// The prefix is not counted, unless --max-width-with-prefix is given. Nothing is written if
+    /// any number is too wide. Raw output is counted in bytes.
+    max_width: Option<usize>,
+    #[arg(long, requires = "max_width")]
+    /// count the prefix for --max-width
+    max_width_with_prefix: bool,
+    #[arg(long = "raw-full")]
+    /// write all 16 bytes of the numbers for --raw, not just the ones that are needed
+    ///
+    /// The bytes are big-endian, so the leading bytes are zero for smaller numbers.
+    raw_full_width: bool,
+    #[arg(long)]
+    /// read numbers line by line from an interactive prompt and format them as they are typed
+    ///
+    /// Lines starting with `:` are commands: `:hex`, `:bin` or any other format name switches the
+    /// format, `:q` quits. Only works if the stdin is a terminal.
+    interactive: bool,
+    #[arg(long)]
+    /// print all formats with the flags that select them and their prefixes, then exit
+    format_list: bool,
+    #[arg(
+        long = "wrap",
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    /// wrap base64 and base32 output into lines of N characters, like 76 for MIME
+    ///
+    /// The prefix is not counted. The stdin is split at line breaks, so wrapped numbers can not
+    /// be read again from it, every line would be a number of its own.
+    base64_wrap: Option<usize>,
+    #[arg(long, value_name = "OP")]
+    /// combine all input numbers into one with OP and format only that
+    ///
+    /// sum and product wrap around if the result does not fit into 128 bits. This is done
+    /// before transformations like --byte-swap.
+    reduce: Option<Reduce>,
+    #[arg(long, value_name = "MASK", value_parser = numf_parser_str::<NumberType>)]
+    /// bitwise and every number with MASK before formatting it
+    and: Option<NumberType>,
+    #[arg(long, value_name = "MASK", value_parser = numf_parser_str::<NumberType>)]
+    /// bitwise or every number with MASK before formatting it
+    or: Option<NumberType>,
+    #[arg(long, value_name = "MASK", value_parser = numf_parser_str::<NumberType>)]
+    /// bitwise exclusive or every number with MASK before formatting it
+    xor: Option<NumberType>,
+    #[arg(long)]
+    /// invert all 128 bits of every number before formatting it
+    not: bool,
+    #[arg(
+        long,
+        value_name = "WIDTH",
+        value_parser = clap::value_parser!(u32).range(1..=128),
+        conflicts_with = "not"
+    )]
+    /// invert only the lowest WIDTH bits of every number before formatting it
+    ///
+    /// This is the one's complement, so 0x00FF with a width of 16 becomes 0xFF00. Numbers that
+    /// do not fit into WIDTH bits are rejected.
+    invert: Option<u32>,
+    #[arg(long, value_name = "BITS")]
+    /// shift every number BITS bits to the left before formatting it
+    ///
+    /// Bits that are shifted out are lost, shifting by 128 or more results in 0.
+    shl: Option<u32>,
+    #[arg(long, value_name = "BITS")]
+    /// shift every number BITS bits to the right before formatting it
+    ///
+    /// Bits that are shifted out are lost, shifting by 128 or more results in 0.
+    shr: Option<u32>,
+    #[arg(long, value_name = "STYLE", value_enum, default_value_t)]
+    /// which prefixes to use with --prefix
+    ///
+    /// c uses no prefix for decimal and 0 for octal, like 0644. With c, input numbers that start
+    /// with 0 and only have octal digits are read as octal too, so decimal numbers are padded
+    /// with spaces instead of zeros.
+    prefix_style: PrefixStyle,
+    #[arg(
+        long,
+        value_name = "BYTES",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=16)
+    )]
+    /// show the numbers as decimal groups of BYTES bytes, joined with dots
+    ///
+    /// With 1 and --width 32, this is an IPv4 address like 10.20.30.40. The groups are counted
+    /// from the right, so the first one may have fewer bytes. Dotted input numbers are accepted
+    /// then too.
+    dotted: Option<usize>,
+    #[arg(long)]
+    /// write one JSON object per line and format, like {"value":"0x1337","format":"Hex"}
+    ///
+    /// The value of raw output is base64 encoded, to keep it valid JSON.
+    ndjson: bool,
+    #[arg(
+        long = "zero-pad-base64",
+        value_name = "BYTES",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=16)
+    )]
+    /// extend the numbers with zero bytes to BYTES bytes before encoding them in base64 or base32
+    ///
+    /// This makes the output the same length for all numbers that fit into BYTES bytes.
+    base_zero_pad: Option<usize>,
+    #[arg(long = "no-raw-prefix")]
+    /// do not write the prefix byte for raw output, even with --prefix
+    ///
+    /// The other formats still get their prefixes. Raw output without the prefix is read back
+    /// as is, unless its first byte happens to be the --raw-prefix byte, which is then skipped.
+    no_raw_prefix: bool,
+    #[arg(long, conflicts_with_all = ["raw_full_width", "ctype"])]
+    /// write only the minimal bytes for raw output, without leading zeros or the prefix byte
+    ///
+    /// This is for tools that expect exactly the bytes of the number. The prefix byte is left out
+    /// even with --prefix, and 0 is a single zero byte.
+    raw_minimal: bool,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(1..=128))]
+    /// print how many leading zeros the numbers have with that many bits
+    ///
+    /// 0 has WIDTH leading zeros. With --ctz too, both are printed, separated by a space.
+    clz: Option<u32>,
+    #[arg(long, value_name = "WIDTH", value_parser = clap::value_parser!(u32).range(1..=128))]
+    /// print how many trailing zeros the numbers have with that many bits
+    ///
+    /// 0 has WIDTH trailing zeros.
+    ctz: Option<u32>,
+    #[arg(long)]
+    /// format how many bits are set in the numbers, instead of the numbers
+    ///
+    /// The counts are formatted like any other number, so --popcount -b shows them in binary.
+    popcount: bool,
+    #[arg(
+        long,
+        value_name = "CHARS",
+        value_parser = parse_alphabet,
+        conflicts_with_all = ["format", "to_base"]
+    )]
+    /// format to the base of the length of CHARS, with the characters of CHARS as digits
+    ///
+    /// The first character is the digit for 0, so --alphabet 01 is binary. There must be at least
+    /// two characters, and none may repeat. Emoji and other characters that are made of several
+    /// code points are a single digit. Use --from-alphabet with the same CHARS to read the
+    /// numbers again.
+    alphabet: Option<String>,
+    #[arg(
+        long,
+        value_name = "CHARS",
+        value_parser = parse_alphabet,
+        conflicts_with = "from_base"
+    )]
+    /// parse the input numbers with the characters of CHARS as digits, see --alphabet
+    from_alphabet: Option<String>,
+    #[arg(long, value_name = "STR")]
+    /// parse all numbers in STR, separated by whitespace or newlines like on the stdin
+    ///
+    /// This is like giving every number as its own argument, and useful when the numbers are in
+    /// a single variable, like in a Makefile. The numbers are added after the other arguments.
+    numbers_blob: Option<String>,
+    #[arg(long, value_name = "TYPE")]
+    /// write the numbers with exactly the size of that C type
+    ///
+    /// Raw output has exactly as many bytes as the type, and hexadecimal and binary output are
+    /// padded to its width. Numbers that do not fit into the type are an error. The bytes are
+    /// big-endian, use --byte-swap with the width of the type to get them little-endian, like
+    /// in the memory of most machines. --le-display does the same for hexadecimal and binary,
+    /// but does not change raw output.
+    ctype: Option<CType>,
+    #[arg(
+        long,
+        conflicts_with_all = ["raw", "ndjson", "alphabet", "to_base", "base64_wrap"]
+    )]
+    /// write every number as a row of a table, with one column for each format
+    ///
+    /// The first row names the formats. The columns are as wide as their widest value, but long
+    /// values like base64 of large numbers only widen their column up to 24 characters. Wider
+    /// values are written in full and move the rest of their row to the right, unless the table
+    /// has to fit into the terminal, see --term-width.
+    tabular: bool,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        requires = "tabular"
+    )]
+    /// fit the table of --tabular into N columns of characters
+    ///
+    /// By default, the table fits into the terminal if the output goes to one, and is not
+    /// limited otherwise. The widest columns are made narrower until the table fits, and values
+    /// that are cut end with '…'.
+    term_width: Option<usize>,
+    #[arg(long)]
+    /// write nothing and exit successfully if there are no numbers
+    ///
+    /// Without this, no numbers is an error, which breaks pipelines that sometimes have no
+    /// numbers. Input that can not be parsed is still an error.
+    allow_empty: bool,
+    #[arg(long)]
+    /// print how many numbers were formatted, like "# 42 numbers formatted"
+    ///
+    /// The line is printed to stderr after the output, with how many numbers came from the
+    /// arguments, the stdin and --rand.
+    report: bool,
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = str::parse::<Format>,
+        conflicts_with_all = ["from_base", "from_alphabet", "float"]
+    )]
+    /// parse all input numbers in FORMAT, instead of detecting their format
+    ///
+    /// The prefix is optional then, so base64 like QUFCQg== can be read without the 0s prefix.
+    /// Input that would look like another format, like 1234 with --from base64, is read in
+    /// FORMAT too. The names are the same as for the `:` commands of --interactive, like hex,
+    /// base64 or base32.
+    from: Option<Format>,
+    #[arg(
+        long,
+        value_name = "N:SEP",
+        value_parser = str::parse::<BaseGroup>,
+        conflicts_with = "base64_wrap"
+    )]
+    /// put SEP between groups of N characters of base64 and base32, like QUFC-Qg== for 4:-
+    ///
+    /// The groups are counted from the left and include the padding, the prefix is not part of
+    /// them. Input with the base64 or base32 prefix (or --from base64 or base32) has SEP removed
+    /// before it is parsed, so that grouped output can be read again.
+    base_group: Option<BaseGroup>,
+    #[arg(long, conflicts_with_all = ["from", "from_base", "from_alphabet", "float"])]
+    /// read all of the stdin as the big-endian bytes of a single number
+    ///
+    /// This is for binary files, like `numf --bytes-in -x < key.bin`. The stdin is not split or
+    /// parsed as text. Leading zero bytes are ignored, but there may be at most 16 other bytes.
+    bytes_in: bool,
+    #[arg(long, value_name = "CHAR")]
+    /// split the numbers from the stdin and --numbers-blob at CHAR instead of whitespace
+    ///
+    /// Lines are always separated, and the spaces around the numbers are removed, so
+    /// `--delimiter-in ,` reads "1, 2,3" as 1, 2 and 3.
+    delimiter_in: Option<char>,
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["raw", "tabular", "ndjson", "alphabet", "to_base", "signed", "pad_char", "bin_group"]
+    )]
+    /// write the numbers as literals of a programming language, like 0x1337u32 for rust
+    ///
+    /// This sets the prefix, and rust also groups the digits with '_' and adds the type as a
+    /// suffix. Only dec, hex, bin and octal can be written as literals.
+    lang: Option<Lang>,
+    #[arg(long, conflicts_with_all = ["raw", "reduce", "count", "popcount", "tabular", "ndjson"])]
+    /// compare two numbers bitwise, by formatting their XOR and listing the bits that differ
+    ///
+    /// Exactly two numbers must be given. The bits are counted from 0 for the least significant
+    /// bit, like "3 bits differ: 0, 4-5".
+    diff: bool,
+    #[arg(long)]
+    /// shuffle the numbers before formatting them
+    ///
+    /// This is done after all numbers were collected, including --rand and --count. Use --seed
+    /// to shuffle them the same way every time.
+    shuffle: bool,
+    #[arg(long, value_name = "N")]
+    /// generate the numbers of --rand and the order of --shuffle from the seed N
+    ///
+    /// The same seed always gives the same output. Without a seed, the random source of the
+    /// operating system is used.
+    seed: Option<u64>,
+    #[arg(long)]
+    /// accept decimal digits of other scripts in input numbers, like the fullwidth １２３
+    ///
+    /// They are read as the ASCII digits 0 to 9. The supported digits are listed in the
+    /// documentation of the library, at numf::format::normalize_digits.
+    unicode_digits: bool,
+    #[arg(long, conflicts_with_all = ["reduce", "diff", "max"])]
+    /// format only the smallest of the input numbers
+    ///
+    /// Like --reduce min, but with -v, the index of the smallest number is logged too.
+    min: bool,
+    #[arg(long, conflicts_with_all = ["reduce", "diff"])]
+    /// format only the largest of the input numbers
+    ///
+    /// Like --reduce max, but with -v, the index of the largest number is logged too.
+    max: bool,
+    #[arg(long, requires = "prefix", conflicts_with_all = ["lang", "tabular", "ndjson"])]
+    /// write the prefix only for the first number
+    ///
+    /// This only works with a single format, with more than one format every number keeps its
+    /// prefix, so that the formats can be told apart.
+    prefix_once: bool,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "bytes",
+        conflicts_with_all = ["bytes_in", "delimiter_in", "from", "from_base", "from_alphabet", "float", "unicode_digits"]
+    )]
+    /// read the inputs as text, and format the codes of their characters
+    ///
+    /// The text is not parsed, every byte is a number, so `--text-in AB` gives 65 and 66. With
+    /// `--text-in=combined`, all bytes of an input are one big-endian number instead, 0x4142 for
+    /// AB, which fits at most 16 bytes. The stdin is one input, only a single line break at its
+    /// end is removed.
+    text_in: Option<TextIn>,
+    #[arg(long, value_enum, value_name = "ALGORITHM", conflicts_with_all = ["lang", "verify"])]
+    /// append check digits to decimal numbers, like 79927398713 for 7992739871 with luhn
+    ///
+    /// The check digits are computed over the decimal digits, and padding with --min-digits
+    /// includes them. The other formats are not changed.
+    check: Option<Check>,
+    #[arg(
+        long,
+        conflicts_with_all = ["explain", "digits", "lang", "as_signed", "as_float", "dotted"]
+    )]
+    /// show the numbers in all formats except raw, one format per line with its name
+    ///
+    /// The selected formats are ignored then, but the prefix and padding are not. With
+    /// --tabular, every number is a row with a column for every format instead.
+    all: bool,
+    #[arg(long)]
+    /// warn about input numbers without a prefix that would be different in another base
+    ///
+    /// Numbers like 11 are read as decimal, but they could be meant as hex, octal or binary. The
+    /// warning suggests a prefix, the numbers are still read as decimal.
+    warn_ambiguous: bool,
+    #[arg(long, conflicts_with = "lowercase")]
+    /// write the letters of hex, base32, base16 and percent-encoding in uppercase, like 0xFF
+    ///
+    /// This is already the default for them. The prefixes, and the formats where the case is part
+    /// of the number, like base64, are never changed.
+    uppercase: bool,
+    #[arg(long)]
+    /// write the letters of hex, base32, base16 and percent-encoding in lowercase, like 0xff
+    ///
+    /// The prefixes, and the formats where the case is part of the number, like base64, are never
+    /// changed.
+    lowercase: bool,
+    #[cfg(feature = "csv")]
+    #[arg(
+        long,
+        value_name = "COL",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+        conflicts_with_all = ["bytes_in", "delimiter_in", "rand", "reduce", "count", "interactive", "shuffle"]
+    )]
+    /// read the numbers from column COL of a CSV table on the stdin
+    ///
+    /// The columns are counted from 1, and the first row is the header. Quoted cells are
+    /// unquoted. Empty and missing cells are skipped, with --csv-out their rows are kept as they
+    /// are. Without --csv-out, only the numbers are written.
+    csv_in: Option<usize>,
+    #[cfg(feature = "csv")]
+    #[arg(
+        long,
+        conflicts_with_all = ["raw", "tabular", "ndjson", "explain", "digits", "clz", "ctz", "as_signed", "dotted", "lang", "diff"]
+    )]
+    /// write the output as CSV, with a column for every format
+    ///
+    /// With --csv-in, the table is written again with the numbers formatted in their column,
+    /// which is replaced by a column for every format if there are more than one.
+    csv_out: bool,
+
+    #[command(flatten)]
+    pub(crate) verbosity: VerbosityLevel,
+}
+
+impl FormatOptions {
+    /// get a [FormatOptionsBuilder] to construct [FormatOptions] step by step
+    pub fn builder() -> FormatOptionsBuilder {
+        FormatOptionsBuilder::default()
+    }
+
+    /// get the [default](Self::default) options, but with `format` selected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    ///
+    /// let options = FormatOptions::from_format(Format::Bin);
+    /// assert_eq!(options.format(), Format::Bin);
+    /// assert_eq!(Format::Bin.format_str(5, &options), "101");
+    /// ```
+    pub fn from_format(format: Format) -> Self {
+        let mut options = Self::default();
+        options.set_format(format);
+        options
+    }
+
+    /// predict the length of `num` formatted with [Self::format], see [Format::predicted_len]
+    pub fn predicted_len(&self, num: NumberType) -> usize {
+        self.format().predicted_len(num, self)
+    }
+
+    /// get the format that the user has configured
+    ///
+    /// If more than one format is selected, this is only one of them, use [Self::formats] to get
+    /// all of them.
+    pub fn format(&self) -> Format {
+        trace!("self.hex: {}", self.hex);
+        if self.oct {
             Format::Octal
         } else if self.bin {
             Format::Bin
@@ -191,6 +1530,22 @@ impl FormatOptions {
             Format::Base64
         } else if self.base32 {
             Format::Base32
+        } else if self.base32_crockford {
+            Format::Base32Crockford
+        } else if self.base85 {
+            Format::Base85
+        } else if self.duration {
+            Format::Duration
+        } else if self.base16 {
+            Format::Base16
+        } else if self.percent {
+            Format::Percent
+        } else if self.negabinary {
+            Format::Negabinary
+        } else if self.morse {
+            Format::Morse
+        } else if self.zeckendorf {
+            Format::Zeckendorf
         } else if self.hex {
             Format::Hex
         } else if self.raw {
@@ -202,23 +1557,114 @@ impl FormatOptions {
         }
     }
 
+    /// get all formats that the user has configured
+    ///
+    /// The formats are in the order of [Format::ALL], not in the order they were given. If none
+    /// was selected, this is just the default [Format]. With [Self::all], these are all formats
+    /// except [Format::Raw], like for [format_all_formats].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    ///
+    /// let mut options = FormatOptions::default();
+    /// assert_eq!(options.formats(), vec![Format::Hex]);
+    ///
+    /// options.set_formats(&[Format::Bin, Format::Dec]);
+    /// assert_eq!(options.formats(), vec![Format::Dec, Format::Bin]);
+    /// ```
+    pub fn formats(&self) -> Vec<Format> {
+        let formats: Vec<Format> = Format::ALL
+            .into_iter()
+            .filter(|format| match self.all {
+                true => *format != Format::Raw,
+                false => self.format_flag(*format),
+            })
+            .collect();
+        if formats.is_empty() {
+            vec![Format::default()]
+        } else {
+            formats
+        }
+    }
+
     /// set the format manually
+    ///
+    /// Any other format that was selected before is no longer selected.
     pub fn set_format(&mut self, format: Format) {
-        self.bin = false;
-        self.oct = false;
-        self.dec = false;
-        self.hex = false;
-        self.base64 = false;
-        self.raw = false;
-        self.base32 = false;
+        self.set_formats(&[format]);
+    }
+
+    /// set multiple formats manually
+    ///
+    /// Any other format that was selected before is no longer selected.
+    pub fn set_formats(&mut self, formats: &[Format]) {
+        for format in Format::ALL {
+            *self.format_flag_mut(format) = formats.contains(&format);
+        }
+    }
+
+    /// get if the flag for that format is set
+    fn format_flag(&self, format: Format) -> bool {
+        match format {
+            Format::Bin => self.bin,
+            Format::Raw => self.raw,
+            Format::Hex => self.hex,
+            Format::Octal => self.oct,
+            Format::Base64 => self.base64,
+            Format::Base32 => self.base32,
+            Format::Base32Crockford => self.base32_crockford,
+            Format::Base85 => self.base85,
+            Format::Duration => self.duration,
+            Format::Base16 => self.base16,
+            Format::Percent => self.percent,
+            Format::Negabinary => self.negabinary,
+            Format::Morse => self.morse,
+            Format::Zeckendorf => self.zeckendorf,
+            Format::Dec => self.dec,
+        }
+    }
+
+    /// get the flag for that format to change it
+    fn format_flag_mut(&mut self, format: Format) -> &mut bool {
         match format {
-            Format::Bin => self.bin = true,
-            Format::Raw => self.raw = true,
-            Format::Hex => self.hex = true,
-            Format::Octal => self.oct = true,
-            Format::Base64 => self.base64 = true,
-            Format::Base32 => self.base32 = true,
-            Format::Dec => self.dec = true,
+            Format::Bin => &mut self.bin,
+            Format::Raw => &mut self.raw,
+            Format::Hex => &mut self.hex,
+            Format::Octal => &mut self.oct,
+            Format::Base64 => &mut self.base64,
+            Format::Base32 => &mut self.base32,
+            Format::Base32Crockford => &mut self.base32_crockford,
+            Format::Base85 => &mut self.base85,
+            Format::Duration => &mut self.duration,
+            Format::Base16 => &mut self.base16,
+            Format::Percent => &mut self.percent,
+            Format::Negabinary => &mut self.negabinary,
+            Format::Morse => &mut self.morse,
+            Format::Zeckendorf => &mut self.zeckendorf,
+            Format::Dec => &mut self.dec,
+        }
+    }
+
+    /// get the id of the argument that selects that format, the name of its field
+    pub(crate) fn format_arg_id(format: Format) -> &'static str {
+        match format {
+            Format::Bin => "bin",
+            Format::Raw => "raw",
+            Format::Hex => "hex",
+            Format::Octal => "oct",
+            Format::Base64 => "base64",
+            Format::Base32 => "base32",
+            Format::Base32Crockford => "base32_crockford",
+            Format::Base85 => "base85",
+            Format::Duration => "duration",
+            Format::Base16 => "base16",
+            Format::Percent => "percent",
+            Format::Negabinary => "negabinary",
+            Format::Morse => "morse",
+            Format::Zeckendorf => "zeckendorf",
+            Format::Dec => "dec",
         }
     }
 
@@ -227,226 +1673,3834 @@ impl FormatOptions {
         self.numbers.as_ref()
     }
 
-    /// set numbers manually
-    pub fn set_numbers(&mut self, numbers: Vec<NumberType>) {
-        self.numbers = numbers;
+    /// get the numbers as they were given on the command line, before parsing
+    ///
+    /// They are not in [Self::numbers] until they are parsed, use [Self::parse_raw_numbers] for
+    /// that.
+    pub fn raw_numbers(&self) -> &[String] {
+        self.raw_numbers.as_ref()
     }
 
-    /// set padding manually
-    pub fn set_padding(&mut self, value: bool) {
-        self.padding = value
+    /// get the blob of whitespace separated numbers, which are parsed like [Self::raw_numbers]
+    pub fn numbers_blob(&self) -> Option<&str> {
+        self.numbers_blob.as_deref()
     }
 
-    /// get padding
-    pub fn padding(&self) -> bool {
-        self.padding
+    /// set the blob of whitespace separated numbers manually
+    pub fn set_numbers_blob(&mut self, blob: Option<String>) {
+        self.numbers_blob = blob;
     }
 
-    /// get prefix
-    pub fn prefix(&self) -> bool {
-        self.prefix
+    /// get the C type that numbers are written as, see [CType]
+    pub fn ctype(&self) -> Option<CType> {
+        self.ctype
     }
 
-    /// set prefix manually
-    pub fn set_prefix(&mut self, value: bool) {
-        self.prefix = value;
+    /// set the C type that numbers are written as manually
+    ///
+    /// The numbers should fit into the type, see [CType::check]. Raw output of numbers that
+    /// are too wide is cut to the lowest bytes.
+    pub fn set_ctype(&mut self, ctype: Option<CType>) {
+        self.ctype = ctype;
     }
 
-    /// manually add a number
-    pub fn push_number(&mut self, value: NumberType) {
-        self.numbers.push(value)
+    /// get tabular, if the formats of a number are written as the row of a table
+    pub fn tabular(&self) -> bool {
+        self.tabular
     }
 
-    /// get rand
-    pub fn rand(&self) -> NumberType {
-        self.rand
+    /// set tabular manually
+    pub fn set_tabular(&mut self, tabular: bool) {
+        self.tabular = tabular;
     }
 
-    /// set amount of extra random numbers manually
-    pub fn set_rand(&mut self, rand: NumberType) {
-        self.rand = rand;
+    /// get the width that the table of [Self::tabular] must fit into
+    pub fn term_width(&self) -> Option<usize> {
+        self.term_width
     }
 
-    /// get highes allowed random value
-    pub fn rand_max(&self) -> NumberType {
-        self.rand_max
+    /// set the width that the table of [Self::tabular] must fit into manually
+    pub fn set_term_width(&mut self, width: Option<usize>) {
+        self.term_width = width;
     }
 
-    /// set highes allowed random value
-    pub fn set_rand_max(&mut self, rand_max: NumberType) {
-        self.rand_max = rand_max;
+    /// get allow_empty, if no numbers are not an error
+    pub fn allow_empty(&self) -> bool {
+        self.allow_empty
     }
-}
 
-impl Default for FormatOptions {
-    fn default() -> Self {
-        Self {
-            padding: false,
-            prefix: false,
-            oct: false,
-            hex: false,
-            bin: false,
-            raw: false,
-            base32: false,
-            base64: false,
-            dec: false,
-            numbers: vec![],
-            rand: 0,
-            rand_max: NumberType::MAX,
-            verbosity: VerbosityLevel::default(),
-        }
+    /// set allow_empty manually
+    pub fn set_allow_empty(&mut self, allow_empty: bool) {
+        self.allow_empty = allow_empty;
     }
-}
 
-impl Format {
-    /// Get the perfix for that [Format] as [Vec<u8>].
-    ///
-    /// # Example
+    /// get report, if the amount of formatted numbers is printed to stderr
+    pub fn report(&self) -> bool {
+        self.report
+    }
+
+    /// set report manually
+    pub fn set_report(&mut self, report: bool) {
+        self.report = report;
+    }
+
+    /// get the format that input numbers are parsed in, see [Format::parse]
+    pub fn from(&self) -> Option<Format> {
+        self.from
+    }
+
+    /// set the format that input numbers are parsed in manually
     ///
-    /// ```
-    /// # use numf::format::Format;
-    /// assert_eq!(Format::Bin.prefix_str(), "0b");
-    /// assert_eq!(Format::Dec.prefix_str(), "0d");
-    /// assert_eq!(Format::Hex.prefix_str(), "0x");
-    /// assert_eq!(Format::Octal.prefix_str(), "0o");
-    /// assert_eq!(Format::Base64.prefix_str(), "0s");
-    /// assert_eq!(Format::Base32.prefix_str(), "032s");
-    /// assert_eq!(Format::Raw.prefix_str(), "\x00");
-    /// ```
-    pub fn prefix_str(&self) -> String {
-        String::from_utf8_lossy(&self.prefix()).to_string()
+    /// With [None], the format of every number is detected.
+    pub fn set_from(&mut self, format: Option<Format>) {
+        self.from = format;
+    }
+
+    /// get the grouping of [Format::Base64] and [Format::Base32], see [BaseGroup]
+    pub fn base_group(&self) -> Option<&BaseGroup> {
+        self.base_group.as_ref()
+    }
+
+    /// set the grouping of base64 and base32 manually
+    pub fn set_base_group(&mut self, base_group: Option<BaseGroup>) {
+        self.base_group = base_group;
+    }
+
+    /// get the character that input numbers are separated by, see [split_input_by]
+    pub fn delimiter_in(&self) -> Option<char> {
+        self.delimiter_in
     }
 
-    /// Get the perfix for that [Format] as [Vec<u8>].
+    /// set the character that input numbers are separated by manually
     ///
-    /// # Example
+    /// With [None], they are separated by whitespace, see [split_input].
+    pub fn set_delimiter_in(&mut self, delimiter: Option<char>) {
+        self.delimiter_in = delimiter;
+    }
+
+    /// get the language that the numbers are written as literals of
+    pub fn lang(&self) -> Option<Lang> {
+        self.lang
+    }
+
+    /// set the language of the literals manually
+    pub fn set_lang(&mut self, lang: Option<Lang>) {
+        self.lang = lang;
+    }
+
+    /// get diff, if two numbers are compared bitwise, see [bit_ranges]
+    pub fn diff(&self) -> bool {
+        self.diff
+    }
+
+    /// set diff manually
+    pub fn set_diff(&mut self, diff: bool) {
+        self.diff = diff;
+    }
+
+    /// get shuffle, if the numbers are shuffled before formatting them
+    pub fn shuffle(&self) -> bool {
+        self.shuffle
+    }
+
+    /// set shuffle manually
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+    }
+
+    /// get the seed for the random numbers and the shuffling, see [Self::rng]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// set the seed manually
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// get the random number generator for [Self::generate_random_with] and
+    /// [Self::shuffle_numbers_with], like the executable uses it
     ///
-    /// ```
-    /// # use numf::format::Format;
-    /// assert_eq!(Format::Bin.prefix(), b"0b");
-    /// assert_eq!(Format::Dec.prefix(), b"0d");
-    /// assert_eq!(Format::Hex.prefix(), b"0x");
-    /// assert_eq!(Format::Octal.prefix(), b"0o");
-    /// assert_eq!(Format::Base64.prefix(), b"0s");
-    /// assert_eq!(Format::Base32.prefix(), b"032s");
-    /// assert_eq!(Format::Raw.prefix(), vec![0x00]);
-    /// ```
-    pub fn prefix(&self) -> Vec<u8> {
-        match self {
-            // apperently used nowhere, sometimes 0 is used as a prefix but I
-            // think this makes it more clear that this is decimal
-            Format::Dec => b"0d".to_vec(),
-            Format::Raw => [0x00].to_vec(),
-            // very common
-            Format::Hex => b"0x".to_vec(),
-            // very common
-            Format::Bin => b"0b".to_vec(),
-            // somewhat common
-            Format::Octal => b"0o".to_vec(),
-            // perl and a few other programs seem to use this too
-            Format::Base64 => b"0s".to_vec(),
-            // no idea, I made this up
-            Format::Base32 => b"032s".to_vec(),
+    /// With a [seed](Self::seed), this is always the same generator for the same seed, otherwise
+    /// it is the random source of the operating system.
+    pub fn rng(&self) -> Box<dyn rand::RngCore> {
+        match self.seed {
+            Some(seed) => Box::new(<rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(
+                seed,
+            )),
+            None => Box::new(rand::rngs::OsRng),
         }
     }
-    /// format a number with a [Format] and [FormatOptions] to a [String]
-    ///
-    /// If you need raw byte outputs, use [Format::format] instead.
+
+    /// shuffle the [numbers](Self::numbers) with a random number generator
     ///
     /// # Example
     ///
     /// ```
-    /// use numf::format::{Format, FormatOptions};
+    /// use numf::format::FormatOptions;
     /// let mut options = FormatOptions::default();
+    /// options.set_numbers(vec![1, 2, 3, 4, 5]);
+    /// options.set_seed(Some(1337));
     ///
-    /// assert_eq!(Format::Bin.format_str(256, &options), "100000000");
-    /// assert_eq!(Format::Hex.format_str(256, &options), "100");
-    /// assert_eq!(Format::Base64.format_str(256, &options), "AQA=");
-    ///
-    /// options.set_prefix(true);
-    /// options.set_padding(true);
-    ///
-    /// assert_eq!(Format::Bin.format_str(256, &options), "0b0000000100000000");
-    /// assert_eq!(Format::Hex.format_str(256, &options), "0x0100");
-    /// assert_eq!(Format::Base64.format_str(256, &options), "0sAQA=");
+    /// let mut other = options.clone();
+    /// options.shuffle_numbers_with(&mut options.rng());
+    /// other.shuffle_numbers_with(&mut other.rng());
+    /// assert_eq!(options.numbers(), other.numbers());
     ///
+    /// let mut sorted = options.numbers().to_vec();
+    /// sorted.sort();
+    /// assert_eq!(sorted, vec![1, 2, 3, 4, 5]);
     /// ```
-    pub fn format_str(&self, num: NumberType, options: &FormatOptions) -> String {
-        String::from_utf8_lossy(&self.format(num, options)).to_string()
+    pub fn shuffle_numbers_with<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) {
+        rand::seq::SliceRandom::shuffle(self.numbers.as_mut_slice(), rng);
     }
 
-    /// format a number with a [Format] and [FormatOptions] to a byte vector [Vec<u8>]
-    ///
-    /// If you need [String] outputs, use [Format::format_str] instead.
+    /// get unicode_digits, if input numbers may have the digits of other scripts, see
+    /// [normalize_digits]
+    pub fn unicode_digits(&self) -> bool {
+        self.unicode_digits
+    }
+
+    /// set unicode_digits manually
+    pub fn set_unicode_digits(&mut self, unicode_digits: bool) {
+        self.unicode_digits = unicode_digits;
+    }
+
+    /// get min, if only the smallest number is formatted
+    pub fn min(&self) -> bool {
+        self.min
+    }
+
+    /// set min manually
+    pub fn set_min(&mut self, min: bool) {
+        self.min = min;
+    }
+
+    /// get max, if only the largest number is formatted
+    pub fn max(&self) -> bool {
+        self.max
+    }
+
+    /// set max manually
+    pub fn set_max(&mut self, max: bool) {
+        self.max = max;
+    }
+
+    /// get prefix_once, if only the first number gets the prefix
+    pub fn prefix_once(&self) -> bool {
+        self.prefix_once
+    }
+
+    /// set prefix_once manually
+    pub fn set_prefix_once(&mut self, prefix_once: bool) {
+        self.prefix_once = prefix_once;
+    }
+
+    /// get how the inputs are read as text, [None] if they are parsed as numbers
+    pub fn text_in(&self) -> Option<TextIn> {
+        self.text_in
+    }
+
+    /// set text_in manually
+    pub fn set_text_in(&mut self, text_in: Option<TextIn>) {
+        self.text_in = text_in;
+    }
+
+    /// get the algorithm of the check digits for decimal numbers
+    pub fn check(&self) -> Option<Check> {
+        self.check
+    }
+
+    /// set the algorithm of the check digits manually
+    pub fn set_check(&mut self, check: Option<Check>) {
+        self.check = check;
+    }
+
+    /// get all, if the numbers are shown in all formats, see [format_all_formats]
+    pub fn all(&self) -> bool {
+        self.all
+    }
+
+    /// set all manually
+    pub fn set_all(&mut self, all: bool) {
+        self.all = all;
+    }
+
+    /// get warn_ambiguous, if input numbers that could be in another base are warned about
+    pub fn warn_ambiguous(&self) -> bool {
+        self.warn_ambiguous
+    }
+
+    /// set warn_ambiguous manually
+    pub fn set_warn_ambiguous(&mut self, warn_ambiguous: bool) {
+        self.warn_ambiguous = warn_ambiguous;
+    }
+
+    /// get the case that overrides the [default case](Format::case) of all formats
+    pub fn case(&self) -> Option<Case> {
+        if self.uppercase {
+            Some(Case::Upper)
+        } else if self.lowercase {
+            Some(Case::Lower)
+        } else {
+            None
+        }
+    }
+
+    /// set the case of all formats manually, [None] for the default case of every format
+    pub fn set_case(&mut self, case: Option<Case>) {
+        self.uppercase = case == Some(Case::Upper);
+        self.lowercase = case == Some(Case::Lower);
+    }
+
+    /// get the case that `format` is written in with these options
+    ///
+    /// This is [Self::case] if it is set, or else the [default case](Format::case) of the format.
+    /// Formats that have no default case are never changed, so this is [None] for them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Case, Format, FormatOptions};
+    /// let mut options = FormatOptions::default();
+    ///
+    /// assert_eq!(options.case_for(Format::Hex), Some(Case::Upper));
+    /// options.set_case(Some(Case::Lower));
+    /// assert_eq!(options.case_for(Format::Hex), Some(Case::Lower));
+    /// assert_eq!(options.case_for(Format::Base64), None);
+    /// ```
+    pub fn case_for(&self, format: Format) -> Option<Case> {
+        format.case().map(|default| self.case().unwrap_or(default))
+    }
+
+    /// get the CSV column that the numbers are read from, counted from 1
+    #[cfg(feature = "csv")]
+    pub fn csv_in(&self) -> Option<usize> {
+        self.csv_in
+    }
+
+    /// set the CSV column that the numbers are read from manually
+    #[cfg(feature = "csv")]
+    pub fn set_csv_in(&mut self, column: Option<usize>) {
+        self.csv_in = column;
+    }
+
+    /// get csv_out, if the output is written as CSV
+    #[cfg(feature = "csv")]
+    pub fn csv_out(&self) -> bool {
+        self.csv_out
+    }
+
+    /// set csv_out manually
+    #[cfg(feature = "csv")]
+    pub fn set_csv_out(&mut self, value: bool) {
+        self.csv_out = value;
+    }
+
+    /// get bytes_in, if the stdin is read as the bytes of a single number
+    pub fn bytes_in(&self) -> bool {
+        self.bytes_in
+    }
+
+    /// set bytes_in manually
+    pub fn set_bytes_in(&mut self, bytes_in: bool) {
+        self.bytes_in = bytes_in;
+    }
+
+    /// parse a number according to the configured options
+    ///
+    /// By default, the format of the number is detected by its prefix like with
+    /// [numf_parser_str], using the configured [raw prefix](Self::raw_prefix). If
+    /// [Self::from_base] is set, the number is parsed in that base with [numf_parser_radix]
+    /// instead.
+    pub fn parse_number(&self, s: &str) -> anyhow::Result<NumberType> {
+        self.parse_bytes(s.as_bytes())
+    }
+
+    /// the inputs from the arguments: the [raw numbers](Self::raw_numbers), then the numbers of
+    /// the [blob](Self::numbers_blob)
+    ///
+    /// The blob is split like the stdin, with [split_input], or with [split_input_by] if there
+    /// is an [input delimiter](Self::delimiter_in).
+    pub fn inputs(&self) -> Vec<String> {
+        let blob = self.numbers_blob.as_deref().unwrap_or_default().as_bytes();
+        let parts = match self.delimiter_in {
+            Some(delimiter) => split_input_by(blob, delimiter),
+            None => split_input(blob),
+        };
+        self.raw_numbers
+            .iter()
+            .cloned()
+            .chain(
+                parts
+                    .into_iter()
+                    .map(|part| String::from_utf8_lossy(part).to_string()),
+            )
+            .collect()
+    }
+
+    /// parse one input into its numbers
+    ///
+    /// With [text_in](Self::text_in), the input is text and may be any amount of numbers, see
+    /// [text_to_numbers]. Otherwise, it is a single number parsed with [Self::parse_number].
+    pub fn parse_input(&self, s: &str) -> anyhow::Result<Vec<NumberType>> {
+        match self.text_in {
+            Some(mode) => Ok(text_to_numbers(s.as_bytes(), mode)?),
+            None => Ok(vec![self.parse_number(s)?]),
+        }
+    }
+
+    /// parse the [inputs](Self::inputs) and add them to the [numbers](Self::numbers)
+    ///
+    /// The binary does this itself, after the options were parsed. A library user that builds
+    /// the options with [clap::Parser::parse_from] has to call this to get the numbers of the
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the first input that can not be parsed, the numbers are not changed then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clap::Parser;
+    /// use numf::format::FormatOptions;
+    ///
+    /// let mut options = FormatOptions::parse_from(["numf", "-x", "0b101", "17"]);
+    /// assert!(options.numbers().is_empty());
+    /// options.parse_raw_numbers().unwrap();
+    /// assert_eq!(options.numbers(), [5, 17]);
+    /// ```
+    pub fn parse_raw_numbers(&mut self) -> anyhow::Result<()> {
+        let mut numbers = Vec::new();
+        for s in self.inputs() {
+            numbers.extend(
+                self.parse_input(&s)
+                    .map_err(|e| e.context(format!("could not parse number {s:?}")))?,
+            );
+        }
+        self.numbers.extend(numbers);
+        Ok(())
+    }
+
+    /// detect the [Format] of input data, like [numf_parser_detect] but with these options
+    ///
+    /// With [Self::from], that is the format. With [PrefixStyle::C], a leading `0` with
+    /// only octal digits after it is [Format::Octal], just like [Self::parse_bytes] reads it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions, PrefixStyle};
+    /// let mut options = FormatOptions::default();
+    ///
+    /// assert_eq!(options.detect_format(b"0644"), Format::Dec);
+    /// options.set_prefix_style(PrefixStyle::C);
+    /// assert_eq!(options.detect_format(b"0644"), Format::Octal);
+    /// assert_eq!(options.detect_format(b"0"), Format::Dec);
+    /// ```
+    pub fn detect_format(&self, data: &[u8]) -> Format {
+        if let Some(format) = self.from {
+            return format;
+        }
+        if self.prefix_style == PrefixStyle::C && strip_c_octal_prefix(data).is_some() {
+            return Format::Octal;
+        }
+        numf_parser_detect(data)
+    }
+
+    /// the bases that input data without a prefix could be meant in, for `--warn-ambiguous`
+    ///
+    /// Data with only decimal digits is read as [Format::Dec], but it's also valid in
+    /// [Format::Hex], and maybe in [Format::Bin] and [Format::Octal]. If any of them would read it
+    /// as a different number, these formats are returned, with [Format::Dec] first. Otherwise,
+    /// and if the input is not read by its prefix anyway, like with [Self::from], the
+    /// result is empty. This does not change how the data is parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let options = FormatOptions::default();
+    ///
+    /// assert_eq!(
+    ///     options.ambiguous_formats(b"11"),
+    ///     vec![Format::Dec, Format::Hex, Format::Bin, Format::Octal]
+    /// );
+    /// assert_eq!(options.ambiguous_formats(b"19"), vec![Format::Dec, Format::Hex]);
+    /// assert!(options.ambiguous_formats(b"7").is_empty());
+    /// assert!(options.ambiguous_formats(b"0x11").is_empty());
+    /// ```
+    pub fn ambiguous_formats(&self, data: &[u8]) -> Vec<Format> {
+        if self.from_base.is_some()
+            || self.from_alphabet.is_some()
+            || self.float.is_some()
+            || self.detect_format(data) != Format::Dec
+            || !data.iter().all(u8::is_ascii_digit)
+        {
+            return Vec::new();
+        }
+        let text = String::from_utf8_lossy(data);
+        let Ok(dec) = text.parse::<NumberType>() else {
+            return Vec::new();
+        };
+        let others: Vec<Format> = [Format::Hex, Format::Bin, Format::Octal]
+            .into_iter()
+            .filter(|format| {
+                // unwrap is fine, these formats have a base
+                numf_parser_radix::<NumberType>(&text, format.radix().unwrap())
+                    .is_ok_and(|num| num != dec)
+            })
+            .collect();
+        if others.is_empty() {
+            return others;
+        }
+        std::iter::once(Format::Dec).chain(others).collect()
+    }
+
+    /// parse any data (as bytes) according to the configured options
+    ///
+    /// This works like [numf_parser], but uses the configured [raw prefix](Self::raw_prefix), or
+    /// like [numf_parser_strict] if [Self::strict] is set. With [PrefixStyle::C], octal numbers
+    /// with just a `0` in front are accepted too. If [Self::from_base] is set, the data
+    /// is parsed as text in that base instead, and if [Self::float] is set, it is parsed as a float
+    /// with [float_bits].
+    ///
+    /// With [Self::dotted], text with dots is parsed with [parse_dotted], and with
+    /// [Self::from_alphabet], all data is parsed with [numf_parser_alphabet]. With
+    /// [Self::from], all data is parsed in that format with [Format::parse].
+    ///
+    /// The [digit separators](Self::digit_separators) are removed from text data first, and the
+    /// separators of [Self::base_group] from base64 and base32.
+    ///
+    /// If [Self::signed] is set together with a [width](Self::width), a leading `-` is accepted
+    /// and the number becomes its two's complement with that width, see [twos_complement].
+    /// Numbers that do not fit into the width are an error then. Decimal numbers without a `-` are
+    /// values too, so they must be below `2^(width-1)`, but other formats may set the sign bit.
+    pub fn parse_bytes(&self, data: &[u8]) -> anyhow::Result<NumberType> {
+        let data = self.remove_digit_separators(data);
+        let data = self.normalize_unicode_digits(&data);
+        let data = self.remove_base_group_separators(&data);
+        let data = data.as_ref();
+        if let (true, Some(width)) = (self.signed, self.width) {
+            if let Some(rest) = data.strip_prefix(b"-") {
+                return twos_complement(self.parse_unsigned(rest)?, width);
+            }
+            let num = self.parse_unsigned(data)?;
+            check_width(num, width)?;
+            let max = NumberType::MAX >> (NumberType::BITS - width + 1);
+            if num > max && self.reads_decimal(data) {
+                return Err(anyhow!(
+                    "{num} does not fit into {width} bits as a signed number"
+                ));
+            }
+            return Ok(num);
+        }
+        self.parse_unsigned(data)
+    }
+
+    /// whether [Self::parse_unsigned] reads `data` as a decimal number
+    fn reads_decimal(&self, data: &[u8]) -> bool {
+        if self.float.is_some()
+            || self.from_alphabet.is_some()
+            || (self.dotted.is_some() && data.contains(&b'.'))
+        {
+            return false;
+        }
+        match (self.from, self.from_base) {
+            (None, Some(radix)) => radix == 10,
+            _ => self.detect_format(data) == Format::Dec,
+        }
+    }
+
+    /// check that `num` is read back the same after it was formatted with `format`, for
+    /// [Self::verify]
+    ///
+    /// The number is formatted with the prefix, so that its format can be detected, and then
+    /// parsed with [Self::parse_bytes], like output that is read by numf again. The options that
+    /// only change how input is read, like [Self::from] or [Self::strict], are not used
+    /// for this. The spaces of [Self::bin_group] and the alignment with a
+    /// [pad char](Self::pad_char) are removed before parsing, and numbers that are shown
+    /// [little-endian](Self::le_display) must be read as their swapped bytes.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the formatted number can not be parsed, or if it is a different number.
     ///
     /// # Example
     ///
     /// ```
     /// use numf::format::{Format, FormatOptions};
     /// let mut options = FormatOptions::default();
+    /// options.set_signed(true);
+    /// options.set_width(Some(16));
     ///
-    /// assert_eq!(Format::Bin.format(256, &options), b"100000000");
-    /// assert_eq!(Format::Hex.format(256, &options), b"100");
-    /// assert_eq!(Format::Hex.format(256, &options), [49, 48, 48]);
-    /// assert_eq!(Format::Base64.format(256, &options), b"AQA=");
-    /// assert_eq!(Format::Raw.format(256, &options), [1, 0]);
+    /// assert!(options.verify_number(Format::Dec, 0xFFFF).is_ok());
+    /// assert!(options.verify_number(Format::Base64, 0xFFFF).is_ok());
+    /// ```
+    pub fn verify_number(&self, format: Format, num: NumberType) -> anyhow::Result<()> {
+        let mut reading = self.clone();
+        reading.prefix = true;
+        reading.from = None;
+        reading.from_base = None;
+        reading.from_alphabet = None;
+        reading.float = None;
+        reading.dotted = None;
+        reading.text_in = None;
+        reading.strict = false;
+        reading.digit_separators = String::new();
+        let formatted = format.format(num, &reading);
+        let data = match format {
+            Format::Raw => formatted,
+            _ => {
+                let text = String::from_utf8_lossy(&formatted);
+                // the alignment is in front of the prefix, and a sign may be made of the pad char
+                let text = match self.pad_char {
+                    '0' | '-' => text.trim(),
+                    pad_char => text.trim().trim_start_matches(pad_char),
+                };
+                let text = match (format, self.bin_group) {
+                    (Format::Bin, Some(_)) => text.replace(' ', ""),
+                    _ => text.to_string(),
+                };
+                text.into_bytes()
+            }
+        };
+        let expected = match (format, signed_decimal(num, self), self.width) {
+            (Format::Hex | Format::Bin, _, _) => {
+                le_display(num, self).map_or(num, |(swapped, _)| swapped)
+            }
+            // a sign-magnitude number is shown with its sign, which is read as two's complement
+            (Format::Dec, Some(signed), Some(width)) if self.sign_magnitude.is_some() => {
+                match signed < 0 {
+                    true => twos_complement(signed.unsigned_abs(), width)?,
+                    false => signed as NumberType,
+                }
+            }
+            _ => num,
+        };
+        let shown = String::from_utf8_lossy(&data);
+        match reading.parse_bytes(&data) {
+            Ok(parsed) if parsed == expected => Ok(()),
+            Ok(parsed) => Err(anyhow!(
+                "{num} was formatted as {shown:?}, but parsed back as {parsed}"
+            )),
+            Err(e) => Err(anyhow!(
+                "{num} was formatted as {shown:?}, but could not be parsed back: {e:#}"
+            )),
+        }
+    }
+
+    /// parse data without a sign, see [Self::parse_bytes]
+    fn parse_unsigned(&self, data: &[u8]) -> anyhow::Result<NumberType> {
+        if let Some(float) = self.float {
+            return float_bits(&String::from_utf8_lossy(data), float);
+        }
+        if let Some(group) = self.dotted {
+            if data.contains(&b'.') {
+                return parse_dotted(&String::from_utf8_lossy(data), group);
+            }
+        }
+        if let Some(alphabet) = &self.from_alphabet {
+            return numf_parser_alphabet(&String::from_utf8_lossy(data), alphabet);
+        }
+        if let Some(format) = self.from {
+            return format.parse(data);
+        }
+        match self.from_base {
+            Some(radix) => numf_parser_radix(&String::from_utf8_lossy(data), radix),
+            None if self.strict => numf_parser_styled(data, None, self.prefix_style),
+            None => numf_parser_styled(data, Some(self.raw_prefix), self.prefix_style),
+        }
+    }
+
+    /// remove the [digit separators](Self::digit_separators) from text data
     ///
-    /// options.set_prefix(true);
-    /// options.set_padding(true);
+    /// Raw input is not changed: data that is not valid UTF-8 is returned as is, and so is text
+    /// that would still be read as [Format::Raw] without the separators, like `a,b`. With
+    /// [Self::from_base] or [Self::from_alphabet], the data is always text.
+    fn remove_digit_separators<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        if self.digit_separators.is_empty() {
+            return Cow::Borrowed(data);
+        }
+        let Ok(text) = std::str::from_utf8(data) else {
+            return Cow::Borrowed(data);
+        };
+        let stripped: Vec<u8> = text
+            .chars()
+            .filter(|c| !self.digit_separators.contains(*c))
+            .collect::<String>()
+            .into_bytes();
+        let text_input = self.from_base.is_some() || self.from_alphabet.is_some();
+        let detected = self.detect_format(&self.normalize_unicode_digits(&stripped));
+        if !text_input && detected == Format::Raw {
+            return Cow::Borrowed(data);
+        }
+        Cow::Owned(stripped)
+    }
+
+    /// replace the digits of other scripts with ASCII digits, if [Self::unicode_digits] is set
     ///
-    /// assert_eq!(Format::Bin.format(256, &options), b"0b0000000100000000");
-    /// assert_eq!(Format::Hex.format(256, &options), b"0x0100");
-    /// assert_eq!(Format::Hex.format(256, &options), [48, 120, 48, 49, 48, 48]);
-    /// assert_eq!(Format::Base64.format(256, &options), b"0sAQA=");
-    /// assert_eq!(Format::Raw.format(256, &options), [0, 1, 0]);
-    /// assert_eq!(Format::Raw.format(255, &options), [0, 255]);
-    /// assert_eq!(Format::Raw.format(32000, &options), [0, 125, 0]);
+    /// Data that is not valid UTF-8 is returned as is, so raw input is not changed.
+    fn normalize_unicode_digits<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        if !self.unicode_digits {
+            return Cow::Borrowed(data);
+        }
+        match std::str::from_utf8(data) {
+            Ok(text) => match normalize_digits(text) {
+                Cow::Borrowed(_) => Cow::Borrowed(data),
+                Cow::Owned(text) => Cow::Owned(text.into_bytes()),
+            },
+            Err(_) => Cow::Borrowed(data),
+        }
+    }
+
+    /// remove the separators of [Self::base_group] from base64 and base32 data
+    ///
+    /// The data is base64 or base32 if it has the prefix of one of them, or if that is the
+    /// [input format](Self::from).
+    fn remove_base_group_separators<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        let Some(group) = &self.base_group else {
+            return Cow::Borrowed(data);
+        };
+        let format = self
+            .from
+            .or_else(|| Format::from_prefix(data).map(|(format, _)| format));
+        if !matches!(format, Some(Format::Base64 | Format::Base32)) {
+            return Cow::Borrowed(data);
+        }
+        match std::str::from_utf8(data) {
+            Ok(text) => Cow::Owned(text.replace(group.separator(), "").into_bytes()),
+            Err(_) => Cow::Borrowed(data),
+        }
+    }
+
+    /// set numbers manually
+    pub fn set_numbers(&mut self, numbers: Vec<NumberType>) {
+        self.numbers = numbers;
+    }
+
+    /// set padding manually
+    pub fn set_padding(&mut self, value: bool) {
+        self.padding = value
+    }
+
+    /// get padding
+    pub fn padding(&self) -> bool {
+        self.padding
+    }
+
+    /// get prefix
+    pub fn prefix(&self) -> bool {
+        self.prefix
+    }
+
+    /// set prefix manually
+    pub fn set_prefix(&mut self, value: bool) {
+        self.prefix = value;
+    }
+
+    /// manually add a number
+    pub fn push_number(&mut self, value: NumberType) {
+        self.numbers.push(value)
+    }
+
+    /// get rand
+    pub fn rand(&self) -> NumberType {
+        self.rand
+    }
+
+    /// set amount of extra random numbers manually
+    pub fn set_rand(&mut self, rand: NumberType) {
+        self.rand = rand;
+    }
+
+    /// get highes allowed random value
+    pub fn rand_max(&self) -> NumberType {
+        self.rand_max
+    }
+
+    /// set highes allowed random value
+    pub fn set_rand_max(&mut self, rand_max: NumberType) {
+        self.rand_max = rand_max;
+    }
+
+    /// get the upper bound (exclusive) for random numbers
+    ///
+    /// This is [Self::rand_max], but if a [width](Self::width) is set, it is at most `2^width`.
+    pub fn rand_limit(&self) -> NumberType {
+        match self.width {
+            // 2^128 does not fit, the full range it is
+            Some(width) if width < NumberType::BITS => self.rand_max.min(1 << width),
+            _ => self.rand_max,
+        }
+    }
+
+    /// generate [Self::rand] random numbers below [Self::rand_limit], like the executable does
+    ///
+    /// The numbers come from the random source of the operating system, use
+    /// [Self::generate_random_with] for another (or a seeded) generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [Self::rand_limit] is 0 and numbers are requested.
+    ///
+    /// # Example
     ///
     /// ```
-    pub fn format(&self, num: NumberType, options: &FormatOptions) -> Vec<u8> {
-        debug!("formatting mode: {self}");
-        let mut buf: Vec<u8> = Vec::new();
-        if options.prefix() {
-            buf.append(&mut self.prefix());
-            debug!("prefix the buffer: {buf:X?}");
+    /// use numf::format::FormatOptions;
+    /// let mut options = FormatOptions::default();
+    /// options.set_rand(3);
+    /// options.set_rand_max(10);
+    ///
+    /// let numbers = options.generate_random();
+    /// assert_eq!(numbers.len(), 3);
+    /// assert!(numbers.iter().all(|n| *n < 10));
+    /// ```
+    pub fn generate_random(&self) -> Vec<NumberType> {
+        self.generate_random_with(&mut rand::rngs::OsRng)
+    }
+
+    /// generate [Self::rand] random numbers below [Self::rand_limit] with `rng`
+    ///
+    /// # Panics
+    ///
+    /// Panics if [Self::rand_limit] is 0 and numbers are requested.
+    pub fn generate_random_with<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Vec<NumberType> {
+        (0..self.rand)
+            .map(|_| rng.gen_range(0..self.rand_limit()))
+            .collect()
+    }
+
+    /// get the base that input numbers are parsed in
+    // named after --from-base, this is a getter and not a constructor
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base(&self) -> Option<u32> {
+        self.from_base
+    }
+
+    /// set the base that input numbers are parsed in manually
+    pub fn set_from_base(&mut self, from_base: Option<u32>) {
+        self.from_base = from_base;
+    }
+
+    /// get the base that numbers are formatted to
+    ///
+    /// If this is set, it takes precedence over [Self::format].
+    pub fn to_base(&self) -> Option<u32> {
+        self.to_base
+    }
+
+    /// set the base that numbers are formatted to manually
+    pub fn set_to_base(&mut self, to_base: Option<u32>) {
+        self.to_base = to_base;
+    }
+
+    /// get the byte used as prefix for [Format::Raw]
+    pub fn raw_prefix(&self) -> u8 {
+        self.raw_prefix
+    }
+
+    /// set the byte used as prefix for [Format::Raw] manually
+    ///
+    /// The default is `0x00`, see [Format::prefix].
+    pub fn set_raw_prefix(&mut self, raw_prefix: u8) {
+        self.raw_prefix = raw_prefix;
+    }
+
+    /// get digits
+    pub fn digits(&self) -> bool {
+        self.digits
+    }
+
+    /// set digits manually
+    pub fn set_digits(&mut self, digits: bool) {
+        self.digits = digits;
+    }
+
+    /// get the width for reversing the bits of the numbers, see [bit_reverse]
+    pub fn bit_reverse(&self) -> Option<u32> {
+        self.bit_reverse
+    }
+
+    /// set the width for reversing the bits of the numbers manually
+    pub fn set_bit_reverse(&mut self, width: Option<u32>) {
+        self.bit_reverse = width;
+    }
+
+    /// get the width for swapping the bytes of the numbers, see [byte_swap]
+    pub fn byte_swap(&self) -> Option<u32> {
+        self.byte_swap
+    }
+
+    /// set the width for swapping the bytes of the numbers manually
+    pub fn set_byte_swap(&mut self, width: Option<u32>) {
+        self.byte_swap = width;
+    }
+
+    /// get the width for storing the numbers as sign-magnitude, see [sign_magnitude]
+    pub fn sign_magnitude(&self) -> Option<u32> {
+        self.sign_magnitude
+    }
+
+    /// set the width for storing the numbers as sign-magnitude manually
+    ///
+    /// With [signed](Self::signed), decimal output shows the value of the sign-magnitude number,
+    /// see [from_sign_magnitude].
+    pub fn set_sign_magnitude(&mut self, width: Option<u32>) {
+        self.sign_magnitude = width;
+    }
+
+    /// transform a number as configured, before it is formatted
+    ///
+    /// This applies [bit_reverse] and then [byte_swap], if they are set. After that, the bitwise
+    /// operations are applied in this order: and, or, xor, not or [invert], shift left, shift
+    /// right. Last, the number is stored as [sign_magnitude].
+    pub fn transform(&self, mut num: NumberType) -> anyhow::Result<NumberType> {
+        if let Some(width) = self.bit_reverse {
+            num = bit_reverse(num, width)?;
+        }
+        if let Some(width) = self.byte_swap {
+            num = byte_swap(num, width)?;
+        }
+        if let Some(mask) = self.and {
+            num &= mask;
+        }
+        if let Some(mask) = self.or {
+            num |= mask;
+        }
+        if let Some(mask) = self.xor {
+            num ^= mask;
+        }
+        if self.not {
+            num = !num;
+        }
+        if let Some(width) = self.invert {
+            num = invert(num, width)?;
+        }
+        // shifting everything out leaves nothing, instead of overflowing
+        if let Some(bits) = self.shl {
+            num = num.checked_shl(bits).unwrap_or(0);
+        }
+        if let Some(bits) = self.shr {
+            num = num.checked_shr(bits).unwrap_or(0);
+        }
+        if let Some(width) = self.sign_magnitude {
+            num = sign_magnitude(num, width)?;
+        }
+        Ok(num)
+    }
+
+    /// get signed, if decimal numbers are read and written with a sign, see [Self::parse_bytes]
+    pub fn signed(&self) -> bool {
+        self.signed
+    }
+
+    /// set signed manually
+    ///
+    /// This only has an effect if a [width](Self::width) is set too.
+    pub fn set_signed(&mut self, signed: bool) {
+        self.signed = signed;
+    }
+
+    /// get the width for showing numbers as signed, see [as_signed]
+    pub fn as_signed(&self) -> Option<u32> {
+        self.as_signed
+    }
+
+    /// set the width for showing numbers as signed manually
+    pub fn set_as_signed(&mut self, width: Option<u32>) {
+        self.as_signed = width;
+    }
+
+    /// get stats
+    pub fn stats(&self) -> bool {
+        self.stats
+    }
+
+    /// set stats manually
+    pub fn set_stats(&mut self, stats: bool) {
+        self.stats = stats;
+    }
+
+    /// get explain
+    pub fn explain(&self) -> bool {
+        self.explain
+    }
+
+    /// set explain manually
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+
+    /// get porcelain
+    pub fn porcelain(&self) -> bool {
+        self.porcelain
+    }
+
+    /// set porcelain manually
+    pub fn set_porcelain(&mut self, porcelain: bool) {
+        self.porcelain = porcelain;
+    }
+
+    /// get the output file, if the output should not go to the stdout
+    pub fn output_file(&self) -> Option<&Path> {
+        self.output_file.as_deref()
+    }
+
+    /// set the output file manually
+    pub fn set_output_file(&mut self, output_file: Option<PathBuf>) {
+        self.output_file = output_file;
+    }
+
+    /// get clock, if [Format::Duration] is written like a clock
+    pub fn clock(&self) -> bool {
+        self.clock
+    }
+
+    /// set clock manually
+    pub fn set_clock(&mut self, clock: bool) {
+        self.clock = clock;
+    }
+
+    /// get the width of the numbers in bits
+    pub fn width(&self) -> Option<u32> {
+        self.width
+    }
+
+    /// set the width of the numbers in bits manually
+    pub fn set_width(&mut self, width: Option<u32>) {
+        self.width = width;
+    }
+
+    /// get the size of the bit groups for [Format::Bin]
+    pub fn bin_group(&self) -> Option<usize> {
+        self.bin_group
+    }
+
+    /// set the size of the bit groups for [Format::Bin] manually
+    pub fn set_bin_group(&mut self, bin_group: Option<NonZeroUsize>) {
+        self.bin_group = bin_group.map(NonZeroUsize::get);
+    }
+
+    /// get the float type that input numbers are parsed as, see [float_bits]
+    pub fn float(&self) -> Option<FloatType> {
+        self.float
+    }
+
+    /// set the float type that input numbers are parsed as manually
+    pub fn set_float(&mut self, float: Option<FloatType>) {
+        self.float = float;
+    }
+
+    /// get the float type for showing numbers as floats, see [as_float]
+    pub fn as_float(&self) -> Option<FloatType> {
+        self.as_float
+    }
+
+    /// set the float type for showing numbers as floats manually
+    pub fn set_as_float(&mut self, float: Option<FloatType>) {
+        self.as_float = float;
+    }
+
+    /// get strip_base_padding, if the `=` padding of base64 and base32 is left out
+    pub fn strip_base_padding(&self) -> bool {
+        self.strip_base_padding
+    }
+
+    /// set strip_base_padding manually
+    pub fn set_strip_base_padding(&mut self, strip_base_padding: bool) {
+        self.strip_base_padding = strip_base_padding;
+    }
+
+    /// get verify
+    pub fn verify(&self) -> bool {
+        self.verify
+    }
+
+    /// set verify manually
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify = verify;
+    }
+
+    /// get strict, if raw input is rejected, see [numf_parser_strict]
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// set strict manually
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// get how often the list of numbers is repeated
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// set how often the list of numbers is repeated manually
+    pub fn set_count(&mut self, count: Option<usize>) {
+        self.count = count;
+    }
+
+    /// get the minimum amount of digits
+    pub fn min_digits(&self) -> Option<usize> {
+        self.min_digits
+    }
+
+    /// set the minimum amount of digits manually
+    pub fn set_min_digits(&mut self, min_digits: Option<usize>) {
+        self.min_digits = min_digits;
+    }
+
+    /// get the width for showing numbers little-endian
+    pub fn le_display(&self) -> Option<u32> {
+        self.le_display
+    }
+
+    /// set the width for showing numbers little-endian manually
+    ///
+    /// Numbers that do not fit into the width are shown as they are, see [byte_swap].
+    pub fn set_le_display(&mut self, width: Option<u32>) {
+        self.le_display = width;
+    }
+
+    /// get the label for every line of output, empty if there is none
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// set the label for every line of output manually
+    pub fn set_tag(&mut self, tag: impl Into<String>) {
+        self.tag = tag.into();
+    }
+
+    /// get the characters that are ignored in input numbers
+    pub fn digit_separators(&self) -> &str {
+        &self.digit_separators
+    }
+
+    /// set the characters that are ignored in input numbers manually
+    pub fn set_digit_separators(&mut self, separators: impl Into<String>) {
+        self.digit_separators = separators.into();
+    }
+
+    /// get keep_going, if input numbers that can not be parsed are skipped
+    pub fn keep_going(&self) -> bool {
+        self.keep_going
+    }
+
+    /// set keep_going manually
+    pub fn set_keep_going(&mut self, keep_going: bool) {
+        self.keep_going = keep_going;
+    }
+
+    /// get the character for padding
+    pub fn pad_char(&self) -> char {
+        self.pad_char
+    }
+
+    /// set the character for padding manually
+    ///
+    /// Characters other than `'0'` are put in front of the prefix, to align the output.
+    pub fn set_pad_char(&mut self, pad_char: char) {
+        self.pad_char = pad_char;
+    }
+
+    /// get the character that numbers in `format` are padded with
+    ///
+    /// This is the [pad char](Self::pad_char), but with [PrefixStyle::C], prefixed decimal
+    /// numbers are padded with spaces instead of zeros. Decimal has no prefix there, and a
+    /// number like `0017` would be read as octal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions, PrefixStyle};
+    /// let mut options = FormatOptions::default();
+    /// options.set_prefix(true);
+    /// options.set_min_digits(Some(4));
+    ///
+    /// assert_eq!(Format::Dec.format_str(17, &options), "0d0017");
+    /// options.set_prefix_style(PrefixStyle::C);
+    /// assert_eq!(options.pad_char_for(Format::Dec), ' ');
+    /// assert_eq!(Format::Dec.format_str(17, &options), "  17");
+    /// assert_eq!(Format::Octal.format_str(15, &options), "00017");
+    /// ```
+    pub fn pad_char_for(&self, format: Format) -> char {
+        match (format, self.prefix_style, self.pad_char) {
+            (Format::Dec, PrefixStyle::C, '0') if self.prefix => ' ',
+            (_, _, pad_char) => pad_char,
+        }
+    }
+
+    /// get the maximum width of formatted numbers
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// set the maximum width of formatted numbers manually
+    pub fn set_max_width(&mut self, max_width: Option<usize>) {
+        self.max_width = max_width;
+    }
+
+    /// get max_width_with_prefix, if the prefix counts for the [maximum width](Self::max_width)
+    pub fn max_width_with_prefix(&self) -> bool {
+        self.max_width_with_prefix
+    }
+
+    /// set max_width_with_prefix manually
+    pub fn set_max_width_with_prefix(&mut self, with_prefix: bool) {
+        self.max_width_with_prefix = with_prefix;
+    }
+
+    /// get raw_full_width, if [Format::Raw] writes all bytes of [NumberType]
+    pub fn raw_full_width(&self) -> bool {
+        self.raw_full_width
+    }
+
+    /// set raw_full_width manually
+    pub fn set_raw_full_width(&mut self, raw_full_width: bool) {
+        self.raw_full_width = raw_full_width;
+    }
+
+    /// get interactive, if the executable should start a prompt
+    pub fn interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// set interactive manually
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.interactive = interactive;
+    }
+
+    /// get format_list, if the formats should be listed instead of formatting numbers
+    pub fn format_list(&self) -> bool {
+        self.format_list
+    }
+
+    /// set format_list manually
+    pub fn set_format_list(&mut self, format_list: bool) {
+        self.format_list = format_list;
+    }
+
+    /// get base64_wrap, the length of the lines for [Format::Base64] and [Format::Base32]
+    pub fn base64_wrap(&self) -> Option<usize> {
+        self.base64_wrap
+    }
+
+    /// set base64_wrap manually
+    ///
+    /// [None] (the default) does not wrap at all.
+    pub fn set_base64_wrap(&mut self, base64_wrap: Option<NonZeroUsize>) {
+        self.base64_wrap = base64_wrap.map(NonZeroUsize::get);
+    }
+
+    /// get reduce, how the numbers are combined into one, see [Reduce::reduce]
+    pub fn reduce(&self) -> Option<Reduce> {
+        self.reduce
+    }
+
+    /// set reduce manually
+    pub fn set_reduce(&mut self, reduce: Option<Reduce>) {
+        self.reduce = reduce;
+    }
+
+    /// get the mask for a bitwise and, see [FormatOptions::transform]
+    pub fn and(&self) -> Option<NumberType> {
+        self.and
+    }
+
+    /// set the mask for a bitwise and manually
+    pub fn set_and(&mut self, mask: Option<NumberType>) {
+        self.and = mask;
+    }
+
+    /// get the mask for a bitwise or, see [FormatOptions::transform]
+    pub fn or(&self) -> Option<NumberType> {
+        self.or
+    }
+
+    /// set the mask for a bitwise or manually
+    pub fn set_or(&mut self, mask: Option<NumberType>) {
+        self.or = mask;
+    }
+
+    /// get the mask for a bitwise exclusive or, see [FormatOptions::transform]
+    pub fn xor(&self) -> Option<NumberType> {
+        self.xor
+    }
+
+    /// set the mask for a bitwise exclusive or manually
+    pub fn set_xor(&mut self, mask: Option<NumberType>) {
+        self.xor = mask;
+    }
+
+    /// get not, if the bits are inverted, see [FormatOptions::transform]
+    pub fn not(&self) -> bool {
+        self.not
+    }
+
+    /// set not manually
+    pub fn set_not(&mut self, not: bool) {
+        self.not = not;
+    }
+
+    /// get the width for inverting the bits, see [invert]
+    pub fn invert(&self) -> Option<u32> {
+        self.invert
+    }
+
+    /// set the width for inverting the bits manually
+    pub fn set_invert(&mut self, width: Option<u32>) {
+        self.invert = width;
+    }
+
+    /// get the amount of bits to shift left, see [FormatOptions::transform]
+    pub fn shl(&self) -> Option<u32> {
+        self.shl
+    }
+
+    /// set the amount of bits to shift left manually
+    pub fn set_shl(&mut self, bits: Option<u32>) {
+        self.shl = bits;
+    }
+
+    /// get the amount of bits to shift right, see [FormatOptions::transform]
+    pub fn shr(&self) -> Option<u32> {
+        self.shr
+    }
+
+    /// set the amount of bits to shift right manually
+    pub fn set_shr(&mut self, bits: Option<u32>) {
+        self.shr = bits;
+    }
+
+    /// get the style of the prefixes
+    pub fn prefix_style(&self) -> PrefixStyle {
+        self.prefix_style
+    }
+
+    /// set the style of the prefixes manually
+    pub fn set_prefix_style(&mut self, style: PrefixStyle) {
+        self.prefix_style = style;
+    }
+
+    /// get the bytes per group for showing numbers dotted, see [format_dotted]
+    pub fn dotted(&self) -> Option<usize> {
+        self.dotted
+    }
+
+    /// set the bytes per group for showing numbers dotted manually
+    pub fn set_dotted(&mut self, bytes: Option<usize>) {
+        self.dotted = bytes;
+    }
+
+    /// get ndjson, if the output is newline delimited JSON
+    pub fn ndjson(&self) -> bool {
+        self.ndjson
+    }
+
+    /// set ndjson manually
+    pub fn set_ndjson(&mut self, ndjson: bool) {
+        self.ndjson = ndjson;
+    }
+
+    /// get the amount of bytes the numbers are extended to for [Format::Base64] and
+    /// [Format::Base32]
+    pub fn base_zero_pad(&self) -> Option<usize> {
+        self.base_zero_pad
+    }
+
+    /// set the amount of bytes the numbers are extended to for base64 and base32 manually
+    ///
+    /// Numbers with more bytes are not cut.
+    pub fn set_base_zero_pad(&mut self, bytes: Option<usize>) {
+        self.base_zero_pad = bytes;
+    }
+
+    /// get no_raw_prefix, if [Format::Raw] has no prefix even with [Self::prefix]
+    pub fn no_raw_prefix(&self) -> bool {
+        self.no_raw_prefix
+    }
+
+    /// set no_raw_prefix manually
+    ///
+    /// Without the prefix, raw output that starts with the [raw prefix](Self::raw_prefix) byte is
+    /// misread by [numf_parser], which skips that byte. Use [numf_parser_raw] to read it exactly.
+    pub fn set_no_raw_prefix(&mut self, no_raw_prefix: bool) {
+        self.no_raw_prefix = no_raw_prefix;
+    }
+
+    /// get the width for counting leading zeros, see [clz]
+    pub fn clz(&self) -> Option<u32> {
+        self.clz
+    }
+
+    /// set the width for counting leading zeros manually
+    pub fn set_clz(&mut self, width: Option<u32>) {
+        self.clz = width;
+    }
+
+    /// get the width for counting trailing zeros, see [ctz]
+    pub fn ctz(&self) -> Option<u32> {
+        self.ctz
+    }
+
+    /// set the width for counting trailing zeros manually
+    pub fn set_ctz(&mut self, width: Option<u32>) {
+        self.ctz = width;
+    }
+
+    /// get popcount, if the set bits are counted instead of formatting the numbers
+    pub fn popcount(&self) -> bool {
+        self.popcount
+    }
+
+    /// set popcount manually
+    pub fn set_popcount(&mut self, popcount: bool) {
+        self.popcount = popcount;
+    }
+
+    /// get the alphabet that numbers are formatted with, see [format_alphabet]
+    pub fn alphabet(&self) -> Option<&str> {
+        self.alphabet.as_deref()
+    }
+
+    /// set the alphabet that numbers are formatted with manually
+    ///
+    /// # Errors
+    ///
+    /// Fails if the alphabet has less than two characters or a character repeats.
+    pub fn set_alphabet(&mut self, alphabet: Option<&str>) -> Result<(), NumfError> {
+        self.alphabet = alphabet.map(parse_alphabet).transpose()?;
+        Ok(())
+    }
+
+    /// get the alphabet that input numbers are parsed with, see [numf_parser_alphabet]
+    // named after --from-alphabet, this is a getter and not a constructor
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_alphabet(&self) -> Option<&str> {
+        self.from_alphabet.as_deref()
+    }
+
+    /// set the alphabet that input numbers are parsed with manually
+    ///
+    /// # Errors
+    ///
+    /// Fails if the alphabet has less than two characters or a character repeats.
+    pub fn set_from_alphabet(&mut self, alphabet: Option<&str>) -> Result<(), NumfError> {
+        self.from_alphabet = alphabet.map(parse_alphabet).transpose()?;
+        Ok(())
+    }
+
+    /// get raw_minimal, if [Format::Raw] writes only the minimal bytes, see [to_bytes]
+    pub fn raw_minimal(&self) -> bool {
+        self.raw_minimal
+    }
+
+    /// set raw_minimal manually
+    ///
+    /// This wins over [Self::raw_full_width], [Self::ctype] and [Self::prefix] for
+    /// [Format::Raw].
+    pub fn set_raw_minimal(&mut self, raw_minimal: bool) {
+        self.raw_minimal = raw_minimal;
+    }
+}
+
+/// Summarizes the active settings, like `format=Hex prefix=true padding=false rand=0`
+///
+/// This is meant for debugging and bug reports, the numbers are not included.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{Format, FormatOptions};
+/// let mut options = FormatOptions::default();
+/// options.set_format(Format::Bin);
+/// options.set_prefix(true);
+///
+/// assert_eq!(
+///     options.to_string(),
+///     format!("format=Bin prefix=true padding=false rand=0 rand_max={}", u128::MAX)
+/// );
+/// ```
+impl Display for FormatOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "format={} prefix={} padding={} rand={} rand_max={}",
+            self.formats()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            self.prefix,
+            self.padding,
+            self.rand,
+            self.rand_max
+        )?;
+        if let Some(radix) = self.from_base {
+            write!(f, " from_base={radix}")?;
+        }
+        if let Some(radix) = self.to_base {
+            write!(f, " to_base={radix}")?;
+        }
+        if self.raw_prefix != 0x00 {
+            write!(f, " raw_prefix={:#04x}", self.raw_prefix)?;
+        }
+        if self.digits {
+            write!(f, " digits=true")?;
+        }
+        if let Some(width) = self.bit_reverse {
+            write!(f, " bit_reverse={width}")?;
+        }
+        if let Some(width) = self.byte_swap {
+            write!(f, " byte_swap={width}")?;
+        }
+        if let Some(width) = self.sign_magnitude {
+            write!(f, " sign_magnitude={width}")?;
+        }
+        if self.signed {
+            write!(f, " signed=true")?;
+        }
+        if let Some(width) = self.as_signed {
+            write!(f, " as_signed={width}")?;
+        }
+        if self.stats {
+            write!(f, " stats=true")?;
+        }
+        if self.explain {
+            write!(f, " explain=true")?;
+        }
+        if self.porcelain {
+            write!(f, " porcelain=true")?;
+        }
+        if let Some(path) = &self.output_file {
+            write!(f, " output_file={}", path.display())?;
+        }
+        if self.clock {
+            write!(f, " clock=true")?;
+        }
+        if let Some(width) = self.width {
+            write!(f, " width={width}")?;
+        }
+        if let Some(group) = self.bin_group {
+            write!(f, " bin_group={group}")?;
+        }
+        if let Some(float) = self.float {
+            write!(f, " float={float}")?;
+        }
+        if let Some(float) = self.as_float {
+            write!(f, " as_float={float}")?;
+        }
+        if self.strip_base_padding {
+            write!(f, " strip_base_padding=true")?;
+        }
+        if self.verify {
+            write!(f, " verify=true")?;
+        }
+        if self.strict {
+            write!(f, " strict=true")?;
+        }
+        if let Some(count) = self.count {
+            write!(f, " count={count}")?;
+        }
+        if let Some(min) = self.min_digits {
+            write!(f, " min_digits={min}")?;
+        }
+        if let Some(width) = self.le_display {
+            write!(f, " le_display={width}")?;
+        }
+        if !self.tag.is_empty() {
+            write!(f, " tag={:?}", self.tag)?;
+        }
+        if !self.digit_separators.is_empty() {
+            write!(f, " digit_separators={:?}", self.digit_separators)?;
+        }
+        if self.keep_going {
+            write!(f, " keep_going=true")?;
+        }
+        if self.pad_char != '0' {
+            write!(f, " pad_char={:?}", self.pad_char)?;
+        }
+        if let Some(max) = self.max_width {
+            write!(f, " max_width={max}")?;
+        }
+        if self.max_width_with_prefix {
+            write!(f, " max_width_with_prefix=true")?;
+        }
+        if self.raw_full_width {
+            write!(f, " raw_full_width=true")?;
+        }
+        if self.interactive {
+            write!(f, " interactive=true")?;
+        }
+        if self.format_list {
+            write!(f, " format_list=true")?;
+        }
+        if let Some(wrap) = self.base64_wrap {
+            write!(f, " base64_wrap={wrap}")?;
+        }
+        if let Some(reduce) = self.reduce {
+            write!(f, " reduce={reduce}")?;
+        }
+        if let Some(mask) = self.and {
+            write!(f, " and={mask:#X}")?;
+        }
+        if let Some(mask) = self.or {
+            write!(f, " or={mask:#X}")?;
+        }
+        if let Some(mask) = self.xor {
+            write!(f, " xor={mask:#X}")?;
+        }
+        if self.not {
+            write!(f, " not=true")?;
+        }
+        if let Some(width) = self.invert {
+            write!(f, " invert={width}")?;
+        }
+        if let Some(bits) = self.shl {
+            write!(f, " shl={bits}")?;
+        }
+        if let Some(bits) = self.shr {
+            write!(f, " shr={bits}")?;
+        }
+        if self.prefix_style != PrefixStyle::Numf {
+            write!(f, " prefix_style={}", self.prefix_style)?;
+        }
+        if let Some(bytes) = self.dotted {
+            write!(f, " dotted={bytes}")?;
+        }
+        if self.ndjson {
+            write!(f, " ndjson=true")?;
+        }
+        if let Some(bytes) = self.base_zero_pad {
+            write!(f, " base_zero_pad={bytes}")?;
+        }
+        if self.no_raw_prefix {
+            write!(f, " no_raw_prefix=true")?;
+        }
+        if self.raw_minimal {
+            write!(f, " raw_minimal=true")?;
+        }
+        if let Some(width) = self.clz {
+            write!(f, " clz={width}")?;
+        }
+        if let Some(width) = self.ctz {
+            write!(f, " ctz={width}")?;
+        }
+        if self.popcount {
+            write!(f, " popcount=true")?;
+        }
+        if let Some(alphabet) = &self.alphabet {
+            write!(f, " alphabet={alphabet:?}")?;
+        }
+        if let Some(alphabet) = &self.from_alphabet {
+            write!(f, " from_alphabet={alphabet:?}")?;
+        }
+        if let Some(blob) = &self.numbers_blob {
+            write!(f, " numbers_blob={blob:?}")?;
+        }
+        if let Some(ctype) = self.ctype {
+            write!(f, " ctype={ctype}")?;
+        }
+        if self.tabular {
+            write!(f, " tabular=true")?;
+        }
+        if let Some(width) = self.term_width {
+            write!(f, " term_width={width}")?;
+        }
+        if self.allow_empty {
+            write!(f, " allow_empty=true")?;
+        }
+        if self.report {
+            write!(f, " report=true")?;
+        }
+        if let Some(format) = self.from {
+            write!(f, " from={format}")?;
+        }
+        if let Some(group) = &self.base_group {
+            write!(f, " base_group={group}")?;
+        }
+        if self.bytes_in {
+            write!(f, " bytes_in=true")?;
+        }
+        if let Some(delimiter) = self.delimiter_in {
+            write!(f, " delimiter_in={delimiter:?}")?;
+        }
+        if let Some(lang) = self.lang {
+            write!(f, " lang={lang}")?;
+        }
+        if self.diff {
+            write!(f, " diff=true")?;
+        }
+        if self.shuffle {
+            write!(f, " shuffle=true")?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, " seed={seed}")?;
+        }
+        if self.unicode_digits {
+            write!(f, " unicode_digits=true")?;
+        }
+        if self.min {
+            write!(f, " min=true")?;
+        }
+        if self.max {
+            write!(f, " max=true")?;
+        }
+        if self.prefix_once {
+            write!(f, " prefix_once=true")?;
+        }
+        if let Some(text_in) = self.text_in {
+            write!(f, " text_in={text_in}")?;
+        }
+        if let Some(check) = self.check {
+            write!(f, " check={check}")?;
+        }
+        if self.all {
+            write!(f, " all=true")?;
+        }
+        if self.warn_ambiguous {
+            write!(f, " warn_ambiguous=true")?;
+        }
+        if let Some(case) = self.case() {
+            write!(f, " case={case}")?;
+        }
+        #[cfg(feature = "csv")]
+        if let Some(column) = self.csv_in {
+            write!(f, " csv_in={column}")?;
+        }
+        #[cfg(feature = "csv")]
+        if self.csv_out {
+            write!(f, " csv_out=true")?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            padding: false,
+            prefix: false,
+            oct: false,
+            hex: false,
+            bin: false,
+            raw: false,
+            base32: false,
+            base32_crockford: false,
+            base85: false,
+            duration: false,
+            base16: false,
+            percent: false,
+            negabinary: false,
+            morse: false,
+            zeckendorf: false,
+            base64: false,
+            dec: false,
+            raw_numbers: vec![],
+            numbers: vec![],
+            from_base: None,
+            to_base: None,
+            rand: 0,
+            rand_max: NumberType::MAX,
+            raw_prefix: 0x00,
+            digits: false,
+            bit_reverse: None,
+            byte_swap: None,
+            sign_magnitude: None,
+            as_signed: None,
+            signed: false,
+            stats: false,
+            explain: false,
+            porcelain: false,
+            output_file: None,
+            clock: false,
+            width: None,
+            bin_group: None,
+            float: None,
+            as_float: None,
+            strip_base_padding: false,
+            verify: false,
+            strict: false,
+            count: None,
+            min_digits: None,
+            le_display: None,
+            tag: String::new(),
+            digit_separators: String::new(),
+            keep_going: false,
+            pad_char: '0',
+            max_width: None,
+            max_width_with_prefix: false,
+            raw_full_width: false,
+            interactive: false,
+            format_list: false,
+            base64_wrap: None,
+            reduce: None,
+            and: None,
+            or: None,
+            xor: None,
+            not: false,
+            invert: None,
+            shl: None,
+            shr: None,
+            prefix_style: PrefixStyle::Numf,
+            dotted: None,
+            ndjson: false,
+            base_zero_pad: None,
+            no_raw_prefix: false,
+            raw_minimal: false,
+            clz: None,
+            ctz: None,
+            popcount: false,
+            alphabet: None,
+            from_alphabet: None,
+            numbers_blob: None,
+            ctype: None,
+            tabular: false,
+            term_width: None,
+            allow_empty: false,
+            report: false,
+            from: None,
+            base_group: None,
+            bytes_in: false,
+            delimiter_in: None,
+            lang: None,
+            diff: false,
+            shuffle: false,
+            seed: None,
+            unicode_digits: false,
+            min: false,
+            max: false,
+            prefix_once: false,
+            text_in: None,
+            check: None,
+            all: false,
+            warn_ambiguous: false,
+            uppercase: false,
+            lowercase: false,
+            #[cfg(feature = "csv")]
+            csv_in: None,
+            #[cfg(feature = "csv")]
+            csv_out: false,
+            verbosity: VerbosityLevel::default(),
+        }
+    }
+}
+
+/// Builder for [FormatOptions]
+///
+/// Get one with [FormatOptions::builder]. Everything that is not set is the same as in
+/// [FormatOptions::default].
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{Format, FormatOptions};
+///
+/// let options = FormatOptions::builder()
+///     .format(Format::Hex)
+///     .prefix(true)
+///     .padding(true)
+///     .build();
+///
+/// assert_eq!(options.format(), Format::Hex);
+/// assert_eq!(options.format().format_str(0xFFF, &options), "0x0FFF");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FormatOptionsBuilder {
+    options: FormatOptions,
+}
+
+impl FormatOptionsBuilder {
+    /// set the format
+    pub fn format(mut self, format: Format) -> Self {
+        self.options.set_format(format);
+        self
+    }
+
+    /// set multiple formats
+    pub fn formats(mut self, formats: &[Format]) -> Self {
+        self.options.set_formats(formats);
+        self
+    }
+
+    /// set prefix
+    pub fn prefix(mut self, prefix: bool) -> Self {
+        self.options.set_prefix(prefix);
+        self
+    }
+
+    /// set padding
+    pub fn padding(mut self, padding: bool) -> Self {
+        self.options.set_padding(padding);
+        self
+    }
+
+    /// set the numbers
+    pub fn numbers(mut self, numbers: Vec<NumberType>) -> Self {
+        self.options.set_numbers(numbers);
+        self
+    }
+
+    /// set amount of extra random numbers
+    pub fn rand(mut self, rand: NumberType) -> Self {
+        self.options.set_rand(rand);
+        self
+    }
+
+    /// set highes allowed random value
+    pub fn rand_max(mut self, rand_max: NumberType) -> Self {
+        self.options.set_rand_max(rand_max);
+        self
+    }
+
+    /// set the base that input numbers are parsed in
+    // named after --from-base like the other builder methods, this is not a constructor
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base(mut self, radix: Option<u32>) -> Self {
+        self.options.set_from_base(radix);
+        self
+    }
+
+    /// set the base that numbers are formatted to
+    // named after --to-base like the other builder methods, this is not a conversion
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_base(mut self, radix: Option<u32>) -> Self {
+        self.options.set_to_base(radix);
+        self
+    }
+
+    /// set the byte used as prefix for [Format::Raw]
+    pub fn raw_prefix(mut self, raw_prefix: u8) -> Self {
+        self.options.set_raw_prefix(raw_prefix);
+        self
+    }
+
+    /// set the width for reversing the bits of the numbers
+    pub fn bit_reverse(mut self, width: Option<u32>) -> Self {
+        self.options.set_bit_reverse(width);
+        self
+    }
+
+    /// set the width for swapping the bytes of the numbers
+    pub fn byte_swap(mut self, width: Option<u32>) -> Self {
+        self.options.set_byte_swap(width);
+        self
+    }
+
+    /// set the width for storing the numbers as sign-magnitude
+    pub fn sign_magnitude(mut self, width: Option<u32>) -> Self {
+        self.options.set_sign_magnitude(width);
+        self
+    }
+
+    /// set the width of the numbers in bits
+    pub fn width(mut self, width: Option<u32>) -> Self {
+        self.options.set_width(width);
+        self
+    }
+
+    /// get the finished [FormatOptions]
+    pub fn build(self) -> FormatOptions {
+        self.options
+    }
+}
+
+impl Format {
+    /// All [Formats](Format), in the order of their declaration
+    ///
+    /// This order is stable, it's also used to convert an index to a [Format] with
+    /// [TryFrom<u8>](Format::try_from).
+    pub const ALL: [Format; 15] = [
+        Format::Dec,
+        Format::Hex,
+        Format::Bin,
+        Format::Octal,
+        Format::Base64,
+        Format::Base32,
+        Format::Base32Crockford,
+        Format::Raw,
+        Format::Base85,
+        Format::Duration,
+        Format::Base16,
+        Format::Percent,
+        Format::Negabinary,
+        Format::Morse,
+        Format::Zeckendorf,
+    ];
+
+    /// Get the default case of the letters of that [Format]
+    ///
+    /// This is the one place where the case of every format is decided. [Format::format] writes
+    /// the letters in this case, unless [FormatOptions::case] overrides it. Formats without
+    /// letters, and formats where the case is part of the number, like [Format::Base64], have no
+    /// case, they are never changed. The units of [Format::Duration] are always lowercase.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::{Case, Format};
+    /// assert_eq!(Format::Hex.case(), Some(Case::Upper));
+    /// assert_eq!(Format::Base16.case(), Some(Case::Upper));
+    /// assert_eq!(Format::Base64.case(), None);
+    /// assert_eq!(Format::Dec.case(), None);
+    /// ```
+    pub fn case(&self) -> Option<Case> {
+        match self {
+            Format::Hex
+            | Format::Base32
+            | Format::Base32Crockford
+            | Format::Base16
+            | Format::Percent => Some(Case::Upper),
+            Format::Dec
+            | Format::Bin
+            | Format::Octal
+            | Format::Raw
+            | Format::Base64
+            | Format::Base85
+            | Format::Duration
+            | Format::Negabinary
+            | Format::Morse
+            | Format::Zeckendorf => None,
+        }
+    }
+
+    /// Get the canonical name of that [Format]
+    ///
+    /// The names are lowercase, stable and the first name of the format for
+    /// [Format::from_str](std::str::FromStr::from_str), so they can be parsed again. Unlike the
+    /// [Display] output, they do not depend on the names of the variants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::Format;
+    /// assert_eq!(Format::Hex.name(), "hex");
+    /// assert_eq!(Format::Base32Crockford.name(), "base32crockford");
+    /// assert_eq!(Format::Octal.name().parse::<Format>().unwrap(), Format::Octal);
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Dec => "dec",
+            Format::Hex => "hex",
+            Format::Bin => "bin",
+            Format::Octal => "oct",
+            Format::Base64 => "base64",
+            Format::Base32 => "base32",
+            Format::Base32Crockford => "base32crockford",
+            Format::Raw => "raw",
+            Format::Base85 => "base85",
+            Format::Duration => "duration",
+            Format::Base16 => "base16",
+            Format::Percent => "percent",
+            Format::Negabinary => "negabinary",
+            Format::Morse => "morse",
+            Format::Zeckendorf => "zeckendorf",
+        }
+    }
+
+    /// Get the base of that [Format], if it writes the number with digits in a base
+    ///
+    /// The formats that encode the bytes of the number, like [Format::Base64], have no base.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::Format;
+    /// assert_eq!(Format::Dec.radix(), Some(10));
+    /// assert_eq!(Format::Hex.radix(), Some(16));
+    /// assert_eq!(Format::Base64.radix(), None);
+    /// ```
+    pub fn radix(&self) -> Option<u32> {
+        match self {
+            Format::Dec => Some(10),
+            Format::Hex => Some(16),
+            Format::Bin => Some(2),
+            Format::Octal => Some(8),
+            Format::Base64
+            | Format::Base32
+            | Format::Base32Crockford
+            | Format::Raw
+            | Format::Base85
+            | Format::Duration
+            | Format::Base16
+            | Format::Percent
+            | Format::Negabinary
+            | Format::Morse
+            | Format::Zeckendorf => None,
+        }
+    }
+
+    /// Get the perfix for that [Format] as bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::Format;
+    /// assert_eq!(Format::Bin.prefix_str(), "0b");
+    /// assert_eq!(Format::Dec.prefix_str(), "0d");
+    /// assert_eq!(Format::Hex.prefix_str(), "0x");
+    /// assert_eq!(Format::Octal.prefix_str(), "0o");
+    /// assert_eq!(Format::Base64.prefix_str(), "0s");
+    /// assert_eq!(Format::Base32.prefix_str(), "032s");
+    /// assert_eq!(Format::Base32Crockford.prefix_str(), "032c");
+    /// assert_eq!(Format::Base85.prefix_str(), "085s");
+    /// assert_eq!(Format::Duration.prefix_str(), "0t");
+    /// assert_eq!(Format::Base16.prefix_str(), "016s");
+    /// assert_eq!(Format::Percent.prefix_str(), "0p");
+    /// assert_eq!(Format::Negabinary.prefix_str(), "0n");
+    /// assert_eq!(Format::Morse.prefix_str(), "0m");
+    /// assert_eq!(Format::Zeckendorf.prefix_str(), "0z");
+    /// assert_eq!(Format::Raw.prefix_str(), "\x00");
+    /// ```
+    pub fn prefix_str(&self) -> String {
+        String::from_utf8_lossy(self.prefix()).to_string()
+    }
+
+    /// Get the perfix for that [Format] as bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::Format;
+    /// assert_eq!(Format::Bin.prefix(), b"0b");
+    /// assert_eq!(Format::Dec.prefix(), b"0d");
+    /// assert_eq!(Format::Hex.prefix(), b"0x");
+    /// assert_eq!(Format::Octal.prefix(), b"0o");
+    /// assert_eq!(Format::Base64.prefix(), b"0s");
+    /// assert_eq!(Format::Base32.prefix(), b"032s");
+    /// assert_eq!(Format::Base32Crockford.prefix(), b"032c");
+    /// assert_eq!(Format::Base85.prefix(), b"085s");
+    /// assert_eq!(Format::Duration.prefix(), b"0t");
+    /// assert_eq!(Format::Base16.prefix(), b"016s");
+    /// assert_eq!(Format::Percent.prefix(), b"0p");
+    /// assert_eq!(Format::Negabinary.prefix(), b"0n");
+    /// assert_eq!(Format::Morse.prefix(), b"0m");
+    /// assert_eq!(Format::Zeckendorf.prefix(), b"0z");
+    /// assert_eq!(Format::Raw.prefix(), [0x00]);
+    /// ```
+    pub fn prefix(&self) -> &'static [u8] {
+        match self {
+            // apperently used nowhere, sometimes 0 is used as a prefix but I
+            // think this makes it more clear that this is decimal
+            Format::Dec => b"0d",
+            Format::Raw => &[0x00],
+            // very common
+            Format::Hex => b"0x",
+            // very common
+            Format::Bin => b"0b",
+            // somewhat common
+            Format::Octal => b"0o",
+            // perl and a few other programs seem to use this too
+            Format::Base64 => b"0s",
+            // no idea, I made this up
+            Format::Base32 => b"032s",
+            // same idea, c for crockford
+            Format::Base32Crockford => b"032c",
+            // like base64 and base32
+            Format::Base85 => b"085s",
+            // t for time, the units already say what it is
+            Format::Duration => b"0t",
+            // like base64 and base32, so it is not mistaken for hex
+            Format::Base16 => b"016s",
+            // p for percent, the % already says what it is
+            Format::Percent => b"0p",
+            // n for negative, 0b would be misleading
+            Format::Negabinary => b"0n",
+            // m for morse, the dots and dashes already say what it is
+            Format::Morse => b"0m",
+            // z for zeckendorf, 0f could be mistaken for hexadecimal
+            Format::Zeckendorf => b"0z",
+        }
+    }
+
+    /// Get the prefix for that [Format] in a [PrefixStyle]
+    ///
+    /// [PrefixStyle::Numf] is the same as [Format::prefix].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::{Format, PrefixStyle};
+    /// assert_eq!(Format::Dec.prefix_for(PrefixStyle::C), b"");
+    /// assert_eq!(Format::Octal.prefix_for(PrefixStyle::C), b"0");
+    /// assert_eq!(Format::Hex.prefix_for(PrefixStyle::C), b"0x");
+    /// assert_eq!(Format::Base64.prefix_for(PrefixStyle::C), b"0s");
+    /// assert_eq!(Format::Octal.prefix_for(PrefixStyle::Numf), b"0o");
+    /// ```
+    pub fn prefix_for(&self, style: PrefixStyle) -> &'static [u8] {
+        match (style, self) {
+            (PrefixStyle::C, Format::Dec) => b"",
+            (PrefixStyle::C, Format::Octal) => b"0",
+            _ => self.prefix(),
+        }
+    }
+
+    /// Find the [Format] whose [prefix](Format::prefix) `data` starts with
+    ///
+    /// Returns the [Format] and the rest of `data` after the prefix. If more than one prefix
+    /// matches, the longest one wins. [Format::Raw] is never returned, its prefix is just a
+    /// `0x00` byte (see [FormatOptions::raw_prefix]) and would match all kinds of data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use numf::format::Format;
+    /// assert_eq!(Format::from_prefix(b"0x1337"), Some((Format::Hex, &b"1337"[..])));
+    /// assert_eq!(Format::from_prefix(b"032sCM======"), Some((Format::Base32, &b"CM======"[..])));
+    /// assert_eq!(Format::from_prefix(b"0"), None);
+    /// assert_eq!(Format::from_prefix(b"1337"), None);
+    /// ```
+    pub fn from_prefix(data: &[u8]) -> Option<(Format, &[u8])> {
+        Format::ALL
+            .into_iter()
+            .filter(|f| *f != Format::Raw)
+            .map(|f| (f, f.prefix()))
+            .filter(|(_, prefix)| data.starts_with(prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(f, prefix)| (f, &data[prefix.len()..]))
+    }
+    /// format a number with a [Format] and [FormatOptions] to a [String]
+    ///
+    /// If you need raw byte outputs, use [Format::format] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let mut options = FormatOptions::default();
+    ///
+    /// assert_eq!(Format::Bin.format_str(256, &options), "100000000");
+    /// assert_eq!(Format::Hex.format_str(256, &options), "100");
+    /// assert_eq!(Format::Base64.format_str(256, &options), "AQA=");
+    ///
+    /// options.set_prefix(true);
+    /// options.set_padding(true);
+    ///
+    /// assert_eq!(Format::Bin.format_str(256, &options), "0b0000000100000000");
+    /// assert_eq!(Format::Hex.format_str(256, &options), "0x0100");
+    /// assert_eq!(Format::Base64.format_str(256, &options), "0sAQA=");
+    ///
+    /// ```
+    pub fn format_str(&self, num: NumberType, options: &FormatOptions) -> String {
+        let mut s = String::new();
+        self.format_str_into(num, options, &mut s);
+        s
+    }
+
+    /// format a number with a [Format] and [FormatOptions], appending it to a [String]
+    ///
+    /// This is [Format::format_str] for a [String] that can be reused. All formats except
+    /// [Format::Raw] are valid UTF-8. The bytes of [Format::Raw] are converted lossily, so
+    /// anything that is not UTF-8 becomes `U+FFFD`, use [Format::format] if you need them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let options = FormatOptions::default();
+    ///
+    /// let mut s = String::new();
+    /// for num in [1, 2, 255] {
+    ///     Format::Hex.format_str_into(num, &options, &mut s);
+    ///     s.push(' ');
+    /// }
+    /// assert_eq!(s, "1 2 FF ");
+    /// ```
+    pub fn format_str_into(&self, num: NumberType, options: &FormatOptions, s: &mut String) {
+        // enough for 128 grouped binary digits with the prefix and a sign, like format_to_writer
+        let mut stack = [0u8; 512];
+        // borrows the bytes if they are valid UTF-8, which the text formats always are
+        match self.format_slice(num, options, &mut stack) {
+            Ok(len) => s.push_str(&String::from_utf8_lossy(&stack[..len])),
+            Err(_) => s.push_str(&String::from_utf8_lossy(&self.format(num, options))),
+        }
+    }
+
+    /// format a number with a [Format] and [FormatOptions] to a byte vector [Vec<u8>]
+    ///
+    /// If you need [String] outputs, use [Format::format_str] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let mut options = FormatOptions::default();
+    ///
+    /// assert_eq!(Format::Bin.format(256, &options), b"100000000");
+    /// assert_eq!(Format::Hex.format(256, &options), b"100");
+    /// assert_eq!(Format::Hex.format(256, &options), [49, 48, 48]);
+    /// assert_eq!(Format::Base64.format(256, &options), b"AQA=");
+    /// assert_eq!(Format::Raw.format(256, &options), [1, 0]);
+    ///
+    /// options.set_prefix(true);
+    /// options.set_padding(true);
+    ///
+    /// assert_eq!(Format::Bin.format(256, &options), b"0b0000000100000000");
+    /// assert_eq!(Format::Hex.format(256, &options), b"0x0100");
+    /// assert_eq!(Format::Hex.format(256, &options), [48, 120, 48, 49, 48, 48]);
+    /// assert_eq!(Format::Base64.format(256, &options), b"0sAQA=");
+    /// assert_eq!(Format::Raw.format(256, &options), [0, 1, 0]);
+    /// assert_eq!(Format::Raw.format(255, &options), [0, 255]);
+    /// assert_eq!(Format::Raw.format(32000, &options), [0, 125, 0]);
+    ///
+    /// ```
+    pub fn format(&self, num: NumberType, options: &FormatOptions) -> Vec<u8> {
+        debug!("formatting mode: {self}");
+        let mut buf: Vec<u8> = Vec::new();
+        if options.prefix() {
+            match self {
+                // the prefix of raw output can be turned off on its own
+                Format::Raw if options.no_raw_prefix() || options.raw_minimal() => (),
+                Format::Raw => buf.push(options.raw_prefix()),
+                _ => buf.extend_from_slice(self.prefix_for(options.prefix_style())),
+            }
+            debug!("prefix the buffer: {buf:X?}");
+        }
+        let prefix_len = buf.len();
+        let pad_char = options.pad_char_for(*self);
+        // how many bytes of padding are in front of the prefix, to align the number
+        let mut fill = 0;
+        if let Some(layout) = self.digit_layout(num, options) {
+            // the sign goes in front of the prefix, where the parser expects it
+            if layout.negative {
+                buf.insert(0, b'-');
+            }
+            let digits = layout.digits();
+            fill = pad_digits(
+                &mut buf,
+                digits,
+                layout.len,
+                layout.group,
+                pad_char,
+                options,
+            );
+        }
+        match self {
+            // the formats with a base are laid out above
+            Format::Hex | Format::Bin | Format::Octal | Format::Dec => (),
+            Format::Base64 => {
+                let mut encoded = fast32::base64::RFC4648.encode(&zero_padded_bytes(num, options));
+                if options.strip_base_padding() {
+                    encoded.truncate(encoded.trim_end_matches('=').len());
+                }
+                if let Some(width) = options.base64_wrap() {
+                    encoded = wrap_lines(&encoded, width);
+                }
+                if let Some(group) = options.base_group() {
+                    encoded = group_chars(&encoded, group.size(), group.separator());
+                }
+                buf.append(&mut encoded.into_bytes())
+            }
+            Format::Base32 => {
+                let mut encoded = fast32::base32::RFC4648.encode(&zero_padded_bytes(num, options));
+                if options.strip_base_padding() {
+                    encoded.truncate(encoded.trim_end_matches('=').len());
+                }
+                if let Some(width) = options.base64_wrap() {
+                    encoded = wrap_lines(&encoded, width);
+                }
+                if let Some(group) = options.base_group() {
+                    encoded = group_chars(&encoded, group.size(), group.separator());
+                }
+                buf.append(&mut encoded.into_bytes())
+            }
+            Format::Base32Crockford => buf.append(
+                &mut fast32::base32::CROCKFORD
+                    .encode(&to_bytes(num))
+                    .as_bytes()
+                    .to_owned(),
+            ),
+            Format::Raw if options.raw_minimal() => buf.append(&mut to_bytes(num)),
+            Format::Raw => match options.ctype() {
+                // the lowest bytes, as many as the type has
+                Some(ctype) => buf.extend_from_slice(
+                    &num.to_be_bytes()[NumberType::BITS as usize / 8 - ctype.bytes()..],
+                ),
+                None if options.raw_full_width() => buf.extend_from_slice(&num.to_be_bytes()),
+                None => buf.append(&mut to_bytes(num)),
+            },
+            Format::Base85 => buf.append(&mut encode_base85(&to_bytes(num)).into_bytes()),
+            Format::Duration => buf.append(&mut format_duration(num, options.clock()).into_bytes()),
+            Format::Base16 => buf.append(&mut encode_base16(&to_bytes(num)).into_bytes()),
+            Format::Percent => buf.append(&mut encode_percent(&to_bytes(num)).into_bytes()),
+            Format::Negabinary => {
+                // leading zeros do not change the number, so --min-digits works like for binary
+                let tmp = encode_negabinary(num);
+                let len = tmp.len();
+                fill = pad_digits(&mut buf, tmp, len, None, pad_char, options);
+            }
+            Format::Morse => buf.append(&mut encode_morse(num).into_bytes()),
+            Format::Zeckendorf => {
+                // leading zeros do not change the number, so --min-digits works like for binary
+                let tmp = encode_zeckendorf(num);
+                let len = tmp.len();
+                fill = pad_digits(&mut buf, tmp, len, None, pad_char, options);
+            }
+        }
+        // the case of the letters is decided here for all formats, the prefix keeps its case
+        if let Some(case) = options.case_for(*self) {
+            case.apply(&mut buf[fill + prefix_len..]);
+        }
+        buf
+    }
+
+    /// format any [Formattable] value, like [Format::format] does for a [NumberType]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let options = FormatOptions::default();
+    ///
+    /// assert_eq!(Format::Bin.format_value(&5u8, &options), b"101");
+    /// assert_eq!(Format::Hex.format_value(&u64::MAX, &options), b"FFFFFFFFFFFFFFFF");
+    /// ```
+    pub fn format_value<T: Formattable + ?Sized>(
+        &self,
+        value: &T,
+        options: &FormatOptions,
+    ) -> Vec<u8> {
+        self.format(value.to_number(), options)
+    }
+
+    /// format any [Formattable] value to a [String], like [Format::format_str] does for a
+    /// [NumberType]
+    pub fn format_value_str<T: Formattable + ?Sized>(
+        &self,
+        value: &T,
+        options: &FormatOptions,
+    ) -> String {
+        self.format_str(value.to_number(), options)
+    }
+
+    /// format a number into a buffer, without allocating
+    ///
+    /// This writes the same bytes as [Format::format] to the start of `buf` and returns how many
+    /// were written. Only [Format::Hex], [Format::Bin], [Format::Octal], [Format::Dec] and
+    /// [Format::Raw] can be formatted like this, the other formats need to allocate.
+    ///
+    /// # Errors
+    ///
+    /// Returns [NumfError::BufferTooSmall] if the output does not fit into `buf`, nothing is
+    /// written then. Use [Format::predicted_len] to know the length beforehand. Returns
+    /// [NumfError::NeedsAllocation] for all other formats, and for [Format::Dec] with
+    /// [check digits](FormatOptions::check).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions, NumfError};
+    /// let mut options = FormatOptions::default();
+    /// options.set_prefix(true);
+    ///
+    /// let mut buf = [0u8; 8];
+    /// let len = Format::Hex.format_slice(0x1337, &options, &mut buf).unwrap();
+    /// assert_eq!(&buf[..len], b"0x1337");
+    ///
+    /// assert_eq!(
+    ///     Format::Bin.format_slice(0x1337, &options, &mut buf),
+    ///     Err(NumfError::BufferTooSmall { needed: 15, capacity: 8 })
+    /// );
+    /// assert_eq!(
+    ///     Format::Base64.format_slice(0x1337, &options, &mut buf),
+    ///     Err(NumfError::NeedsAllocation(Format::Base64))
+    /// );
+    /// ```
+    pub fn format_slice(
+        &self,
+        num: NumberType,
+        options: &FormatOptions,
+        buf: &mut [u8],
+    ) -> Result<usize, NumfError> {
+        if !matches!(
+            self,
+            Format::Hex | Format::Bin | Format::Octal | Format::Dec | Format::Raw
+        ) {
+            return Err(NumfError::NeedsAllocation(*self));
+        }
+        if *self == Format::Dec && options.check().is_some() {
+            return Err(NumfError::NeedsAllocation(*self));
+        }
+        let needed = self.predicted_len(num, options);
+        if buf.len() < needed {
+            return Err(NumfError::BufferTooSmall {
+                needed,
+                capacity: buf.len(),
+            });
+        }
+
+        let Some(layout) = self.digit_layout(num, options) else {
+            // only raw is left, written like in Format::format
+            let mut pos = 0;
+            if options.prefix() && !options.no_raw_prefix() && !options.raw_minimal() {
+                buf[0] = options.raw_prefix();
+                pos = 1;
+            }
+            let bytes = match options.ctype() {
+                _ if options.raw_minimal() => byte_len(num),
+                Some(ctype) => ctype.bytes(),
+                None if options.raw_full_width() => NumberType::BITS as usize / 8,
+                None => byte_len(num),
+            };
+            let all = num.to_be_bytes();
+            buf[pos..pos + bytes].copy_from_slice(&all[all.len() - bytes..]);
+            return Ok(pos + bytes);
+        };
+
+        // the same order as in Format::format and pad_digits: the fill, the sign, the prefix,
+        // and then the digits with the zeros in front of them
+        let len = layout.len.max(options.min_digits().unwrap_or(0));
+        let fill = len.saturating_sub(layout.digits);
+        let pad_char = options.pad_char_for(*self);
+        let (zeros, fill) = match pad_char {
+            '0' => (fill, 0),
+            _ => (0, fill),
+        };
+        let mut pos = 0;
+        for _ in 0..fill {
+            pos += pad_char.encode_utf8(&mut buf[pos..]).len();
+        }
+        if layout.negative {
+            buf[pos] = b'-';
+            pos += 1;
+        }
+        if options.prefix() {
+            let prefix = self.prefix_for(options.prefix_style());
+            buf[pos..pos + prefix.len()].copy_from_slice(prefix);
+            pos += prefix.len();
+        }
+        // the digits are written from the right, the zeros are just digits of the value too
+        let digits = zeros + layout.digits;
+        let end = pos
+            + match layout.group {
+                Some(group) => grouped_len(digits, group, 1),
+                None => digits,
+            };
+        let mut rest = layout.value;
+        let mut i = end;
+        for written in 0..digits {
+            if let Some(group) = layout.group {
+                if written > 0 && written % group == 0 {
+                    i -= 1;
+                    buf[i] = b' ';
+                }
+            }
+            i -= 1;
+            buf[i] = DIGITS[(rest % layout.radix as NumberType) as usize];
+            rest /= layout.radix as NumberType;
+        }
+        if let Some(case) = options.case_for(*self) {
+            case.apply(&mut buf[pos..end]);
+        }
+        debug_assert_eq!(
+            end, needed,
+            "format_slice must write what predicted_len says"
+        );
+        Ok(end)
+    }
+
+    /// how the digits of the formats with a base are laid out
+    ///
+    /// [Format::format], [Format::format_slice] and [Format::predicted_len] all use this, so
+    /// that they agree. Returns [None] for the other formats.
+    fn digit_layout(&self, num: NumberType, options: &FormatOptions) -> Option<DigitLayout> {
+        let mut layout = DigitLayout {
+            radix: 10,
+            value: num,
+            digits: 0,
+            len: 0,
+            group: None,
+            negative: false,
+            check: None,
+        };
+        match self {
+            Format::Hex | Format::Bin => {
+                let (radix, bits, padding) = match self {
+                    Format::Hex => (16, 4, 2),
+                    _ => (2, 1, 8),
+                };
+                layout.radix = radix;
+                layout.digits = match le_display(num, options) {
+                    // these zeros are part of the bytes, not padding
+                    Some((swapped, width)) => {
+                        layout.value = swapped;
+                        width as usize / bits
+                    }
+                    None => digit_count(num, radix) as usize,
+                };
+                layout.len = layout.digits;
+                if options.padding() {
+                    layout.len = layout.len.next_multiple_of(padding);
+                }
+                if let Some(ctype) = options.ctype() {
+                    layout.len = layout.len.max(ctype.bits() as usize / bits);
+                }
+                if *self == Format::Bin {
+                    layout.group = options.bin_group();
+                }
+            }
+            Format::Octal => {
+                layout.radix = 8;
+                layout.digits = digit_count(num, 8) as usize;
+                layout.len = layout.digits;
+                if options.padding() {
+                    // as many digits as the whole bytes need, 3 bits per digit
+                    layout.len = layout.len.max((byte_len(num) * 8).div_ceil(3));
+                }
+            }
+            Format::Dec => {
+                if let Some(signed) = signed_decimal(num, options) {
+                    layout.negative = signed < 0;
+                    layout.value = signed.unsigned_abs();
+                }
+                layout.check = options.check();
+                layout.digits = digit_count(layout.value, 10) as usize
+                    + layout.check.map_or(0, |check| check.width());
+                layout.len = layout.digits;
+            }
+            _ => return None,
+        }
+        Some(layout)
+    }
+
+    /// predict the length of [Format::format] in bytes, without formatting the number
+    ///
+    /// This is meant for laying out columns before any output is made. The length is counted in
+    /// bytes, like [Vec::len] of the formatted number, so a [pad char](FormatOptions::pad_char)
+    /// that is not ASCII counts more than once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let mut options = FormatOptions::default();
+    /// options.set_prefix(true);
+    /// options.set_padding(true);
+    ///
+    /// assert_eq!(Format::Hex.predicted_len(0x1337, &options), "0x1337".len());
+    /// assert_eq!(Format::Bin.predicted_len(5, &options), "0b00000101".len());
+    /// assert_eq!(Format::Base64.predicted_len(256, &options), "0sAQA=".len());
+    /// assert_eq!(Format::Raw.predicted_len(256, &options), 3);
+    /// ```
+    pub fn predicted_len(&self, num: NumberType, options: &FormatOptions) -> usize {
+        let prefix = match self {
+            _ if !options.prefix() => 0,
+            Format::Raw => usize::from(!options.no_raw_prefix() && !options.raw_minimal()),
+            _ => self.prefix_for(options.prefix_style()).len(),
+        };
+        let pad_char = options.pad_char_for(*self);
+        if let Some(layout) = self.digit_layout(num, options) {
+            return prefix
+                + usize::from(layout.negative)
+                + padded_len(layout.digits, layout.len, layout.group, pad_char, options);
+        }
+        let digits = |radix: u32| digit_count(num, radix) as usize;
+        let bytes = byte_len(num);
+        prefix
+            + match self {
+                Format::Hex | Format::Bin | Format::Octal | Format::Dec => {
+                    unreachable!("the formats with a base have a digit layout")
+                }
+                Format::Base64 | Format::Base32 => {
+                    let bytes = bytes.max(options.base_zero_pad().unwrap_or(0));
+                    let (bits, block) = match self {
+                        Format::Base64 => (6, 4),
+                        _ => (5, 8),
+                    };
+                    let mut len = (bytes * 8).div_ceil(bits);
+                    if !options.strip_base_padding() {
+                        len = len.next_multiple_of(block);
+                    }
+                    if let Some(width) = options.base64_wrap() {
+                        len = grouped_len(len, width, 1);
+                    }
+                    if let Some(group) = options.base_group() {
+                        len = grouped_len(len, group.size(), group.separator().len());
+                    }
+                    len
+                }
+                Format::Base32Crockford => (bytes * 8).div_ceil(5),
+                Format::Raw if options.raw_minimal() => bytes,
+                Format::Raw => match options.ctype() {
+                    Some(ctype) => ctype.bytes(),
+                    None if options.raw_full_width() => NumberType::BITS as usize / 8,
+                    None => bytes,
+                },
+                Format::Base85 => {
+                    let full = &num.to_be_bytes()[NumberType::BITS as usize / 8 - bytes..];
+                    full.chunks(4)
+                        .map(|chunk| match chunk {
+                            [0, 0, 0, 0] => 1,
+                            _ => chunk.len() + 1,
+                        })
+                        .sum()
+                }
+                Format::Duration => duration_len(num, options.clock()),
+                Format::Base16 => bytes * 2,
+                Format::Percent => bytes * 3,
+                Format::Negabinary => {
+                    let digits = negabinary_len(num);
+                    padded_len(digits, digits, None, pad_char, options)
+                }
+                // every digit is 5 symbols, with spaces between them
+                Format::Morse => digits(10) * 6 - 1,
+                Format::Zeckendorf => {
+                    // there is a digit for every Fibonacci number up to the largest one that fits
+                    let digits = fibonacci_places()
+                        .iter()
+                        .filter(|place| **place <= num)
+                        .count()
+                        .max(1);
+                    padded_len(digits, digits, None, pad_char, options)
+                }
+            }
+    }
+
+    /// format a number with a [Format] and [FormatOptions] directly into a [Write](std::io::Write)
+    ///
+    /// The written bytes are the same as those of [Format::format]. No newline is written after
+    /// them. The formats that [Format::format_slice] can write are formatted on the stack, without
+    /// allocating, the others are formatted with [Format::format].
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `w` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::{Format, FormatOptions};
+    /// let options = FormatOptions::default();
+    ///
+    /// let mut out = Vec::new();
+    /// Format::Hex.format_to_writer(256, &options, &mut out).unwrap();
+    /// Format::Raw.format_to_writer(256, &options, &mut out).unwrap();
+    /// assert_eq!(out, b"100\x01\x00");
+    /// ```
+    pub fn format_to_writer<W: std::io::Write>(
+        &self,
+        num: NumberType,
+        options: &FormatOptions,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        // enough for 128 grouped binary digits with the prefix and a sign
+        let mut stack = [0u8; 512];
+        match self.format_slice(num, options, &mut stack) {
+            Ok(len) => w.write_all(&stack[..len]),
+            // formats without a digit layout, or a lot of padding with --min-digits
+            Err(_) => w.write_all(&self.format(num, options)),
+        }
+    }
+
+    /// Parse data as a number in this [Format], without detecting the format
+    ///
+    /// This is the counterpart of [Self::format]. The [prefix](Format::prefix) is optional, if the
+    /// data starts with it, it is skipped. For [Format::Raw], the data are the bytes of the
+    /// number, with an optional prefix byte `0x00` in front if there is more than one byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns [NumfError::InvalidNumber] if the data is not a valid number in this format, or
+    /// if it does not fit into [NumberType].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numf::format::Format;
+    ///
+    /// assert_eq!(Format::Hex.parse(b"1337").unwrap(), 0x1337);
+    /// assert_eq!(Format::Hex.parse(b"0x1337").unwrap(), 0x1337);
+    /// assert_eq!(Format::Bin.parse(b"101").unwrap(), 5);
+    /// assert_eq!(Format::Base64.parse(b"QUFCQg==").unwrap(), 0x41414242);
+    /// assert_eq!(Format::Raw.parse(&[0x13, 0x37]).unwrap(), 0x1337);
+    /// assert!(Format::Octal.parse(b"9").is_err());
+    /// ```
+    pub fn parse(&self, data: &[u8]) -> anyhow::Result<NumberType> {
+        if *self == Format::Raw {
+            let skip_prefix = data.len() > 1 && data[0] == Format::Raw.prefix()[0];
+            return Ok(numf_parse_raw_u128(data, skip_prefix)?);
+        }
+        let data = data.strip_prefix(self.prefix()).unwrap_or(data);
+        let text = std::str::from_utf8(data).map_err(|e| NumfError::InvalidNumber {
+            format: *self,
+            reason: e.to_string(),
+        })?;
+        Ok(numf_parse_prefixed(*self, text)?)
+    }
+}
+
+/// How the digits of a number in a base are written, see [Format::digit_layout]
+struct DigitLayout {
+    radix: u32,
+    /// the value that the digits are of, which may be swapped for little-endian display
+    value: NumberType,
+    /// how many digits there are, including zeros that are part of the value
+    digits: usize,
+    /// how many digits there should be with padding, before [FormatOptions::min_digits]
+    len: usize,
+    /// the size of the digit groups, separated by spaces
+    group: Option<usize>,
+    /// if there is a `-` in front of the digits
+    negative: bool,
+    /// the check digits that come after the digits of the value
+    check: Option<Check>,
+}
+
+impl DigitLayout {
+    /// the digits of the value with the zeros that are part of it, and the check digits
+    ///
+    /// This is without the padding and the groups, [pad_digits] adds them.
+    fn digits(&self) -> String {
+        let mut digits = match self.radix {
+            2 => format_digits::<2>(self.value),
+            8 => format_digits::<8>(self.value),
+            16 => format_digits::<16>(self.value),
+            _ => format_digits::<10>(self.value),
+        };
+        let len = self.digits - self.check.map_or(0, |check| check.width());
+        if digits.len() < len {
+            digits.insert_str(0, &"0".repeat(len - digits.len()));
+        }
+        if let Some(check) = self.check {
+            digits.push_str(&check.compute(self.value));
+        }
+        digits
+    }
+}
+
+/// The amount of bytes of [to_bytes], without making them
+fn byte_len(num: NumberType) -> usize {
+    ((NumberType::BITS - num.leading_zeros()) as usize)
+        .div_ceil(8)
+        .max(1)
+}
+
+/// The length of `len` characters with a separator of `separator` bytes after every `size` of
+/// them, like [group_chars] and [group_digits] make it
+fn grouped_len(len: usize, size: usize, separator: usize) -> usize {
+    len + len.div_ceil(size).saturating_sub(1) * separator
+}
+
+/// The length that [pad_digits] makes `digits` digits, see [Format::predicted_len]
+fn padded_len(
+    digits: usize,
+    len: usize,
+    group: Option<usize>,
+    pad_char: char,
+    options: &FormatOptions,
+) -> usize {
+    let len = len.max(options.min_digits().unwrap_or(0));
+    let fill = len.saturating_sub(digits);
+    let (digits, fill) = match pad_char {
+        '0' => (digits + fill, 0),
+        _ => (digits, fill),
+    };
+    let digits = match group {
+        Some(group) => grouped_len(digits, group, 1),
+        None => digits,
+    };
+    digits + fill * pad_char.len_utf8()
+}
+
+/// The length of [format_duration], without making it
+fn duration_len(seconds: NumberType, clock: bool) -> usize {
+    if clock {
+        return (digit_count(seconds / 3600, 10) as usize).max(2) + 6;
+    }
+    if seconds == 0 {
+        return 2;
+    }
+    let mut len = 0;
+    let mut rest = seconds;
+    for (unit, length) in DURATION_UNITS {
+        if rest >= length {
+            len += digit_count(rest / length, 10) as usize + unit.len_utf8();
+            rest %= length;
+        }
+    }
+    len
+}
+
+/// The amount of digits of [encode_negabinary], without making them
+fn negabinary_len(num: NumberType) -> usize {
+    let mut len = 0;
+    let mut magnitude = num;
+    let mut negative = false;
+    while magnitude != 0 {
+        let digit = magnitude % 2;
+        magnitude = if negative {
+            magnitude / 2 + digit
+        } else {
+            magnitude / 2
+        };
+        negative = !negative && magnitude != 0;
+        len += 1;
+    }
+    len.max(1)
+}
+
+/// The bytes of `num`, extended with zeros in front to [FormatOptions::base_zero_pad]
+fn zero_padded_bytes(num: NumberType, options: &FormatOptions) -> Vec<u8> {
+    let mut bytes = to_bytes(num);
+    if let Some(width) = options.base_zero_pad() {
+        if bytes.len() < width {
+            bytes.splice(0..0, std::iter::repeat_n(0, width - bytes.len()));
+        }
+    }
+    bytes
+}
+
+/// Swap the bytes of `num` for displaying it little-endian, if [FormatOptions::le_display] is set
+///
+/// Returns the swapped number and the width. Numbers that do not fit into the width are not
+/// swapped.
+fn le_display(num: NumberType, options: &FormatOptions) -> Option<(NumberType, u32)> {
+    let width = options.le_display()?;
+    byte_swap(num, width).ok().map(|swapped| (swapped, width))
+}
+
+/// Pad `digits` to `len` (or [FormatOptions::min_digits]) and append them to `buf`
+///
+/// With the `pad_char` `'0'`, the zeros go between the prefix in `buf` and the digits. Any other
+/// pad char would change the number there, so it goes in front of the prefix instead, to align
+/// the output. The digits are separated into groups of `group` digits after padding with zeros.
+///
+/// Returns how many bytes were put in front of the prefix.
+fn pad_digits(
+    buf: &mut Vec<u8>,
+    mut digits: String,
+    len: usize,
+    group: Option<usize>,
+    pad_char: char,
+    options: &FormatOptions,
+) -> usize {
+    let len = len.max(options.min_digits().unwrap_or(0));
+    let mut fill = len.saturating_sub(digits.len());
+    if pad_char == '0' {
+        digits = "0".repeat(fill) + &digits;
+        fill = 0;
+    }
+    if let Some(group) = group {
+        digits = group_digits(&digits, group, ' ');
+    }
+    let alignment: String = std::iter::repeat_n(pad_char, fill).collect();
+    let aligned = alignment.len();
+    buf.splice(0..0, alignment.into_bytes());
+    buf.append(&mut digits.into_bytes());
+    aligned
+}
+
+/// Insert `separator` between groups of `group` digits, counted from the right
+///
+/// # Panics
+///
+/// Panics if `group` is 0.
+pub(crate) fn group_digits(digits: &str, group: usize, separator: char) -> String {
+    let chars: Vec<char> = digits.chars().collect();
+    let first = match chars.len() % group {
+        0 => group,
+        n => n,
+    };
+    let mut out = String::with_capacity(chars.len() + chars.len() / group);
+    for (i, c) in chars.iter().enumerate() {
+        if i >= first && (i - first) % group == 0 {
+            out.push(separator);
+        }
+        out.push(*c);
+    }
+    out
+}
+
+/// Convert a number from one format to another in one step
+///
+/// The format of `input` is detected like with [FormatOptions::parse_number], so the options
+/// for parsing apply too. The number is then formatted with `to`.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{convert, Format, FormatOptions};
+///
+/// let options = FormatOptions::default();
+/// assert_eq!(convert("0x1337", Format::Dec, &options).unwrap(), "4919");
+/// assert_eq!(convert("4919", Format::Bin, &options).unwrap(), "1001100110111");
+/// assert_eq!(convert("0sQUFCQg==", Format::Hex, &options).unwrap(), "41414242");
+/// assert!(convert("-1", Format::Hex, &options).is_err());
+/// ```
+pub fn convert(input: &str, to: Format, options: &FormatOptions) -> anyhow::Result<String> {
+    let num = options.parse_number(input)?;
+    Ok(to.format_str(num, options))
+}
+
+/// Get the minimal big-endian byte representation of a number
+///
+/// This is what [Format::Raw] outputs (without the [prefix](Format::prefix)), and what
+/// [Format::Base64] and [Format::Base32] encode. Leading zero bytes are omitted, but the value `0`
+/// is still represented by a single zero byte.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::to_bytes;
+///
+/// assert_eq!(to_bytes(0x1337), vec![0x13, 0x37]);
+/// assert_eq!(to_bytes(0xFF), vec![0xFF]);
+/// assert_eq!(to_bytes(0), vec![0x00]);
+/// ```
+pub fn to_bytes(num: NumberType) -> Vec<u8> {
+    split::unsigned_to_vec(num)
+}
+
+/// Count how many digits a number needs in a base
+///
+/// This is the length of the number written in that base without leading zeros, so `0` still
+/// needs one digit.
+///
+/// # Panics
+///
+/// Panics if `radix` is smaller than 2.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::digit_count;
+///
+/// assert_eq!(digit_count(0, 16), 1);
+/// assert_eq!(digit_count(0xFF, 16), 2);
+/// assert_eq!(digit_count(0x100, 16), 3);
+/// assert_eq!(digit_count(0b1011, 2), 4);
+/// assert_eq!(digit_count(999, 10), 3);
+/// ```
+pub fn digit_count(mut num: NumberType, radix: u32) -> u32 {
+    assert!(radix >= 2, "radix must be at least 2, but is {radix}");
+    let mut count = 1;
+    while num >= radix as NumberType {
+        num /= radix as NumberType;
+        count += 1;
+    }
+    count
+}
+
+/// Count the leading zeros of a number with `width` bits
+///
+/// Unlike [u128::leading_zeros], only the lowest `width` bits are counted, so `0` has `width`
+/// leading zeros.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::clz;
+///
+/// assert_eq!(clz(1, 8).unwrap(), 7);
+/// assert_eq!(clz(0x80, 8).unwrap(), 0);
+/// assert_eq!(clz(0, 32).unwrap(), 32);
+/// assert!(clz(0x100, 8).is_err());
+/// ```
+pub fn clz(num: NumberType, width: u32) -> anyhow::Result<u32> {
+    check_width(num, width)?;
+    Ok(num.leading_zeros() - (NumberType::BITS - width))
+}
+
+/// Count the trailing zeros of a number with `width` bits
+///
+/// Unlike [u128::trailing_zeros], `0` has `width` trailing zeros, not 128.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::ctz;
+///
+/// assert_eq!(ctz(0b1000, 8).unwrap(), 3);
+/// assert_eq!(ctz(1, 8).unwrap(), 0);
+/// assert_eq!(ctz(0, 32).unwrap(), 32);
+/// ```
+pub fn ctz(num: NumberType, width: u32) -> anyhow::Result<u32> {
+    check_width(num, width)?;
+    Ok(num.trailing_zeros().min(width))
+}
+
+/// Count how many bits of a number are set
+///
+/// # Example
+///
+/// ```
+/// use numf::format::popcount;
+///
+/// assert_eq!(popcount(0), 0);
+/// assert_eq!(popcount(0b1011), 3);
+/// assert_eq!(popcount(u128::MAX), 128);
+/// ```
+pub fn popcount(num: NumberType) -> NumberType {
+    num.count_ones() as NumberType
+}
+
+/// Describe which bits of a number are set, as a list of positions and ranges of positions
+///
+/// The positions are counted from 0 for the least significant bit. Runs of set bits are written
+/// as a range, like `4-7`. A number without set bits is an empty string.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::bit_ranges;
+///
+/// assert_eq!(bit_ranges(0b1_0000_1111_0001), "0, 4-7, 12");
+/// assert_eq!(bit_ranges(0b110), "1-2");
+/// assert_eq!(bit_ranges(1 << 127), "127");
+/// assert_eq!(bit_ranges(0), "");
+/// ```
+pub fn bit_ranges(num: NumberType) -> String {
+    let mut ranges = Vec::new();
+    let mut rest = num;
+    while rest != 0 {
+        let start = rest.trailing_zeros();
+        let len = (rest >> start).trailing_ones();
+        ranges.push(match len {
+            1 => format!("{start}"),
+            _ => format!("{start}-{}", start + len - 1),
+        });
+        // clear the run, shifting by all 128 bits would overflow
+        rest = if start + len == NumberType::BITS {
+            0
+        } else {
+            rest & (NumberType::MAX << (start + len))
+        };
+    }
+    ranges.join(", ")
+}
+
+/// Interpret a number as a signed two's complement number with `width` bits
+///
+/// The highest of the `width` bits is the sign bit. This only changes how the value is read, so
+/// `0xFF` with a width of 8 is `-1`, but with a width of 16 it's `255`.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::as_signed;
+///
+/// assert_eq!(as_signed(0xFFFFFFFF, 32).unwrap(), -1);
+/// assert_eq!(as_signed(0x7FFFFFFF, 32).unwrap(), 0x7FFFFFFF);
+/// assert_eq!(as_signed(0x80, 8).unwrap(), -128);
+/// assert!(as_signed(0x100, 8).is_err());
+/// ```
+pub fn as_signed(num: NumberType, width: u32) -> anyhow::Result<i128> {
+    check_width(num, width)?;
+    if width == NumberType::BITS {
+        return Ok(num as i128);
+    }
+    let sign_bit: NumberType = 1 << (width - 1);
+    if num & sign_bit != 0 {
+        Ok(num as i128 - (1i128 << width))
+    } else {
+        Ok(num as i128)
+    }
+}
+
+/// The bits of `-magnitude` as a signed two's complement number with `width` bits
+///
+/// This is the opposite of [as_signed] for negative numbers.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and fails if
+/// `-magnitude` is smaller than the smallest number with `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{as_signed, twos_complement};
+///
+/// assert_eq!(twos_complement(1, 16).unwrap(), 0xFFFF);
+/// assert_eq!(twos_complement(128, 8).unwrap(), 0x80);
+/// assert_eq!(as_signed(twos_complement(1337, 32).unwrap(), 32).unwrap(), -1337);
+/// assert!(twos_complement(129, 8).is_err());
+/// ```
+pub fn twos_complement(magnitude: NumberType, width: u32) -> anyhow::Result<NumberType> {
+    check_width(0, width)?;
+    let min: NumberType = 1 << (width - 1);
+    if magnitude > min {
+        return Err(anyhow!(
+            "-{magnitude} does not fit into {width} bits as a signed number"
+        ));
+    }
+    let mask = NumberType::MAX >> (NumberType::BITS - width);
+    Ok(magnitude.wrapping_neg() & mask)
+}
+
+/// Store a signed two's complement number with `width` bits as a sign-magnitude number
+///
+/// The highest of the `width` bits is the sign, and the other bits are the magnitude, so `-5`
+/// with a width of 8 is `0x85`. Numbers that are not negative stay the same.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits. The smallest number
+/// with `width` bits is an error too, its magnitude would need the sign bit.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{sign_magnitude, twos_complement};
+///
+/// assert_eq!(sign_magnitude(twos_complement(5, 8).unwrap(), 8).unwrap(), 0x85);
+/// assert_eq!(sign_magnitude(5, 8).unwrap(), 5);
+/// assert_eq!(sign_magnitude(0xFFFF, 16).unwrap(), 0x8001);
+/// assert!(sign_magnitude(0x80, 8).is_err());
+/// ```
+pub fn sign_magnitude(num: NumberType, width: u32) -> anyhow::Result<NumberType> {
+    let signed = as_signed(num, width)?;
+    if signed >= 0 {
+        return Ok(num);
+    }
+    let sign_bit: NumberType = 1 << (width - 1);
+    let magnitude = signed.unsigned_abs();
+    if magnitude >= sign_bit {
+        let form = format!("{signed} has no sign-magnitude form with {width} bits");
+        return Err(anyhow!("{form}, the magnitude needs the sign bit"));
+    }
+    Ok(sign_bit | magnitude)
+}
+
+/// Read a sign-magnitude number with `width` bits, the inverse of [sign_magnitude]
+///
+/// The highest of the `width` bits is the sign, and the other bits are the magnitude. The
+/// negative zero is 0.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{from_sign_magnitude, sign_magnitude, twos_complement};
+///
+/// assert_eq!(from_sign_magnitude(0x85, 8).unwrap(), -5);
+/// assert_eq!(from_sign_magnitude(5, 8).unwrap(), 5);
+/// assert_eq!(from_sign_magnitude(0x80, 8).unwrap(), 0);
+/// let stored = sign_magnitude(twos_complement(1337, 16).unwrap(), 16).unwrap();
+/// assert_eq!(from_sign_magnitude(stored, 16).unwrap(), -1337);
+/// assert!(from_sign_magnitude(0x100, 8).is_err());
+/// ```
+pub fn from_sign_magnitude(num: NumberType, width: u32) -> anyhow::Result<i128> {
+    check_width(num, width)?;
+    let sign_bit: NumberType = 1 << (width - 1);
+    let magnitude = (num & !sign_bit) as i128;
+    match num & sign_bit {
+        0 => Ok(magnitude),
+        _ => Ok(-magnitude),
+    }
+}
+
+/// The value of `num` for decimal output with [FormatOptions::signed], if it has one
+///
+/// Numbers stored as [sign_magnitude] are read back with [from_sign_magnitude], the others as
+/// two's complement with the [width](FormatOptions::width). Numbers that do not fit into the
+/// width are shown without a sign, so they have no signed value.
+fn signed_decimal(num: NumberType, options: &FormatOptions) -> Option<i128> {
+    let width = options.width().filter(|_| options.signed())?;
+    match options.sign_magnitude() {
+        Some(width) => from_sign_magnitude(num, width).ok(),
+        None => as_signed(num, width).ok(),
+    }
+}
+
+/// Show a number as decimal groups of `group` bytes, joined with dots
+///
+/// The bytes of the number (see [to_bytes]) are extended with zeros in front to at least
+/// `min_bytes`. The groups are counted from the right, so if the amount of bytes is not a multiple
+/// of `group`, the first group has fewer bytes.
+///
+/// # Panics
+///
+/// Panics if `group` is 0.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::format_dotted;
+///
+/// assert_eq!(format_dotted(0x0A141E28, 1, 0), "10.20.30.40");
+/// assert_eq!(format_dotted(0x7F000001, 1, 4), "127.0.0.1");
+/// assert_eq!(format_dotted(1, 1, 4), "0.0.0.1");
+/// assert_eq!(format_dotted(0x010203, 2, 0), "1.515");
+/// ```
+pub fn format_dotted(num: NumberType, group: usize, min_bytes: usize) -> String {
+    assert!(group > 0, "groups must have at least one byte");
+    let mut bytes = to_bytes(num);
+    if bytes.len() < min_bytes {
+        bytes.splice(0..0, std::iter::repeat_n(0, min_bytes - bytes.len()));
+    }
+    let first = match bytes.len() % group {
+        0 => group,
+        n => n,
+    };
+    let (head, tail) = bytes.split_at(first);
+    std::iter::once(head)
+        .chain(tail.chunks(group))
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0 as NumberType, |acc, b| acc << 8 | *b as NumberType)
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Parse a number that was shown with [format_dotted]
+///
+/// Every group must be a decimal number that fits into `group` bytes.
+///
+/// # Errors
+///
+/// Fails if a group is not a decimal number, does not fit into `group` bytes, or if the whole
+/// number does not fit into [NumberType].
+///
+/// # Example
+///
+/// ```
+/// use numf::format::parse_dotted;
+///
+/// assert_eq!(parse_dotted("10.20.30.40", 1).unwrap(), 0x0A141E28);
+/// assert_eq!(parse_dotted("1.515", 2).unwrap(), 0x010203);
+/// assert!(parse_dotted("256.1", 1).is_err());
+/// ```
+pub fn parse_dotted(s: &str, group: usize) -> anyhow::Result<NumberType> {
+    assert!(group > 0, "groups must have at least one byte");
+    let mut bytes = Vec::new();
+    for part in s.split('.') {
+        let value: NumberType = part
+            .parse()
+            .map_err(|e| anyhow!("{part:?} is not a decimal group: {e}"))?;
+        let be = value.to_be_bytes();
+        let (leading, group_bytes) = be.split_at(be.len().saturating_sub(group));
+        if leading.iter().any(|b| *b != 0) {
+            return Err(anyhow!(
+                "{value} does not fit into a group of {group} bytes"
+            ));
+        }
+        bytes.extend_from_slice(group_bytes);
+    }
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    if bytes.len() > NumberType::BITS as usize / 8 {
+        return Err(anyhow!("{s} is too large for {} bits", NumberType::BITS));
+    }
+    Ok(bytes
+        .iter()
+        .fold(0 as NumberType, |acc, b| acc << 8 | *b as NumberType))
+}
+
+/// Parse a float and get its bits
+///
+/// The float is written in decimal, like `3.14`, `-2` or `1e10`. `NaN`, `inf` and `-inf` are
+/// accepted too (in any case).
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{float_bits, FloatType};
+///
+/// assert_eq!(float_bits("1.5", FloatType::F64).unwrap(), 0x3FF8000000000000);
+/// assert_eq!(float_bits("1.5", FloatType::F32).unwrap(), 0x3FC00000);
+/// assert_eq!(float_bits("-inf", FloatType::F32).unwrap(), 0xFF800000);
+/// assert!(float_bits("0x10", FloatType::F32).is_err());
+/// ```
+pub fn float_bits(s: &str, float: FloatType) -> anyhow::Result<NumberType> {
+    let s = s.trim();
+    Ok(match float {
+        FloatType::F32 => s
+            .parse::<f32>()
+            .map_err(|e| anyhow!("{s} is not a float: {e}"))?
+            .to_bits() as NumberType,
+        FloatType::F64 => s
+            .parse::<f64>()
+            .map_err(|e| anyhow!("{s} is not a float: {e}"))?
+            .to_bits() as NumberType,
+    })
+}
+
+/// Interpret the bits of a number as a float and show it
+///
+/// Infinity is shown as `inf` and `-inf`, and all NaN values are shown as `NaN`.
+///
+/// # Errors
+///
+/// Returns [NumfError::ValueTooWide] if the number does not fit into the bits of the type.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{as_float, FloatType};
+///
+/// assert_eq!(as_float(0x3FF8000000000000, FloatType::F64).unwrap(), "1.5");
+/// assert_eq!(as_float(0x3FC00000, FloatType::F32).unwrap(), "1.5");
+/// assert_eq!(as_float(0x7FC00000, FloatType::F32).unwrap(), "NaN");
+/// assert!(as_float(0x3FF8000000000000, FloatType::F32).is_err());
+/// ```
+pub fn as_float(num: NumberType, float: FloatType) -> anyhow::Result<String> {
+    check_width(num, float.bits())?;
+    Ok(match float {
+        FloatType::F32 => f32::from_bits(num as u32).to_string(),
+        FloatType::F64 => f64::from_bits(num as u64).to_string(),
+    })
+}
+
+/// Make sure that the width is in `1..=128` and that `num` fits into it
+fn check_width(num: NumberType, width: u32) -> anyhow::Result<()> {
+    if !(1..=NumberType::BITS).contains(&width) {
+        return Err(NumfError::InvalidWidth(width).into());
+    }
+    if width < NumberType::BITS && num >> width != 0 {
+        return Err(NumfError::ValueTooWide { value: num, width }.into());
+    }
+    Ok(())
+}
+
+/// Reverse the order of the lowest `width` bits of a number
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::bit_reverse;
+///
+/// assert_eq!(bit_reverse(0b00000001, 8).unwrap(), 0b10000000);
+/// assert_eq!(bit_reverse(0b110, 3).unwrap(), 0b011);
+/// assert!(bit_reverse(0x100, 8).is_err());
+/// ```
+pub fn bit_reverse(num: NumberType, width: u32) -> anyhow::Result<NumberType> {
+    check_width(num, width)?;
+    Ok(num.reverse_bits() >> (NumberType::BITS - width))
+}
+
+/// Invert the lowest `width` bits of a number, the one's complement at that width
+///
+/// Unlike the two's complement of [as_signed], this only flips the bits.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`, and
+/// [NumfError::ValueTooWide] if the number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::invert;
+///
+/// assert_eq!(invert(0x00FF, 16).unwrap(), 0xFF00);
+/// assert_eq!(invert(0, 128).unwrap(), u128::MAX);
+/// assert!(invert(0x10000, 16).is_err());
+/// ```
+pub fn invert(num: NumberType, width: u32) -> anyhow::Result<NumberType> {
+    check_width(num, width)?;
+    Ok(!num & (NumberType::MAX >> (NumberType::BITS - width)))
+}
+
+/// Reverse the order of the bytes of a number with `width` bits, changing its endianness
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidWidth] if the width is not in `1..=128`,
+/// [NumfError::NotByteAligned] if it is not a multiple of 8, and [NumfError::ValueTooWide] if the
+/// number does not fit into `width` bits.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::byte_swap;
+///
+/// assert_eq!(byte_swap(0x12345678, 32).unwrap(), 0x78563412);
+/// assert_eq!(byte_swap(0x1337, 32).unwrap(), 0x37130000);
+/// assert!(byte_swap(0x1337, 12).is_err());
+/// ```
+pub fn byte_swap(num: NumberType, width: u32) -> anyhow::Result<NumberType> {
+    check_width(num, width)?;
+    if width % 8 != 0 {
+        return Err(NumfError::NotByteAligned(width).into());
+    }
+    Ok(num.swap_bytes() >> (NumberType::BITS - width))
+}
+
+/// the digits of all bases up to 16, uppercase like everywhere else
+const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Format a number in base `RADIX`, which must be 2, 8, 10 or 16
+///
+/// This is how [Format::Dec], [Format::Hex], [Format::Bin] and [Format::Octal] write their
+/// digits. Numbers that fit into 64 bits are written by a simple loop into a buffer on the stack,
+/// which is a lot faster than [format!]. Larger numbers are left to [format!], the output is the
+/// same either way.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::format_digits;
+///
+/// assert_eq!(format_digits::<16>(255), "FF");
+/// assert_eq!(format_digits::<2>(5), "101");
+/// assert_eq!(format_digits::<8>(8), "10");
+/// assert_eq!(format_digits::<10>(0), "0");
+/// assert_eq!(format_digits::<10>(u128::MAX), u128::MAX.to_string());
+/// ```
+pub fn format_digits<const RADIX: u32>(num: NumberType) -> String {
+    const {
+        assert!(
+            matches!(RADIX, 2 | 8 | 10 | 16),
+            "RADIX must be 2, 8, 10 or 16"
+        )
+    };
+    let Ok(small) = u64::try_from(num) else {
+        return match RADIX {
+            2 => format!("{num:b}"),
+            8 => format!("{num:o}"),
+            10 => format!("{num}"),
+            _ => format!("{num:X}"),
+        };
+    };
+    // a u64 has at most 64 digits, in binary
+    let mut stack = [0u8; u64::BITS as usize];
+    let mut start = stack.len();
+    let mut rest = small;
+    loop {
+        start -= 1;
+        stack[start] = DIGITS[(rest % RADIX as u64) as usize];
+        rest /= RADIX as u64;
+        if rest == 0 {
+            break;
         }
-        match self {
-            Format::Hex => {
-                if options.padding() {
-                    let tmp = &format!("{num:X}");
-                    let tmp1 = &("0".repeat((2 - tmp.len() % 2) % 2) + tmp);
-                    buf.append(&mut tmp1.as_bytes().to_owned());
-                } else {
-                    buf.append(&mut format!("{num:X}").as_bytes().to_owned());
-                }
-            }
-            Format::Bin => {
-                if options.padding() {
-                    let tmp = &format!("{num:b}");
-                    let tmp1 = &("0".repeat((8 - tmp.len() % 8) % 8) + tmp);
-                    buf.append(&mut tmp1.as_bytes().to_owned());
+    }
+    // unwrap is fine, the digits are ASCII
+    std::str::from_utf8(&stack[start..]).unwrap().to_string()
+}
+
+/// Format a number in any base from 2 to 36
+///
+/// Digits above 9 are written as uppercase letters, just like [Format::Hex] does.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidRadix] if the radix is not in `2..=36`.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::format_radix;
+///
+/// assert_eq!(format_radix(255, 16).unwrap(), "FF");
+/// assert_eq!(format_radix(35, 36).unwrap(), "Z");
+/// assert_eq!(format_radix(7, 7).unwrap(), "10");
+/// assert!(format_radix(7, 37).is_err());
+/// ```
+pub fn format_radix(mut num: NumberType, radix: u32) -> anyhow::Result<String> {
+    if !(2..=36).contains(&radix) {
+        return Err(NumfError::InvalidRadix(radix).into());
+    }
+    let mut digits: Vec<u8> = Vec::new();
+    loop {
+        let digit = (num % radix as NumberType) as u32;
+        // unwrap is fine, the digit is always smaller than the radix
+        digits.push(
+            std::char::from_digit(digit, radix)
+                .unwrap()
+                .to_ascii_uppercase() as u8,
+        );
+        num /= radix as NumberType;
+        if num == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    Ok(String::from_utf8_lossy(&digits).to_string())
+}
+
+/// Split an alphabet into its symbols, the grapheme clusters of the string
+///
+/// A symbol is what is seen as one character, so an emoji with a skin tone or a letter with an
+/// accent made of several [chars](char) is a single symbol.
+fn alphabet_symbols(alphabet: &str) -> Vec<&str> {
+    alphabet.graphemes(true).collect()
+}
+
+/// Make sure that an alphabet can be used for [format_alphabet]
+///
+/// This is also the value parser for `--alphabet`.
+fn parse_alphabet(alphabet: &str) -> Result<String, NumfError> {
+    let symbols = alphabet_symbols(alphabet);
+    if symbols.len() < 2 {
+        return Err(NumfError::InvalidAlphabet(
+            "it needs at least two symbols".to_string(),
+        ));
+    }
+    for (i, symbol) in symbols.iter().enumerate() {
+        if symbols[..i].contains(symbol) {
+            return Err(NumfError::InvalidAlphabet(format!(
+                "'{symbol}' is in it twice"
+            )));
+        }
+    }
+    Ok(alphabet.to_string())
+}
+
+/// Format a number with the symbols of `alphabet` as digits
+///
+/// The base is the amount of symbols, and the first symbol is the digit for 0. The symbols are
+/// the grapheme clusters of the alphabet, so anything that looks like one character can be a
+/// digit, including emoji made of several [chars](char) like 👍🏽. Symbols that would merge
+/// with their neighbours, like the regional indicators of flags, can not be told apart and
+/// should not be used.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidAlphabet] if the alphabet has less than two symbols or a symbol
+/// repeats.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::format_alphabet;
+///
+/// assert_eq!(format_alphabet(5, "01").unwrap(), "101");
+/// assert_eq!(format_alphabet(255, "0123456789abcdef").unwrap(), "ff");
+/// assert_eq!(format_alphabet(4, "🙂🙃").unwrap(), "🙃🙂🙂");
+/// assert_eq!(format_alphabet(2, "👍🏻👍🏽").unwrap(), "👍🏽👍🏻");
+/// assert!(format_alphabet(5, "0").is_err());
+/// assert!(format_alphabet(5, "010").is_err());
+/// ```
+pub fn format_alphabet(mut num: NumberType, alphabet: &str) -> anyhow::Result<String> {
+    parse_alphabet(alphabet)?;
+    let symbols = alphabet_symbols(alphabet);
+    let radix = symbols.len() as NumberType;
+    let mut digits = Vec::new();
+    loop {
+        digits.push(symbols[(num % radix) as usize]);
+        num /= radix;
+        if num == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    Ok(digits.concat())
+}
+
+/// Parse a number that was formatted with [format_alphabet], with the same alphabet
+///
+/// The input is split into grapheme clusters like the alphabet.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidAlphabet] if the alphabet has less than two symbols or a symbol
+/// repeats, and [NumfError::NotInAlphabet] if a symbol of the input is not in the alphabet.
+/// Fails too if there are no digits, or the number does not fit into [NumberType].
+///
+/// # Example
+///
+/// ```
+/// use numf::format::numf_parser_alphabet;
+///
+/// assert_eq!(numf_parser_alphabet("101", "01").unwrap(), 5);
+/// assert_eq!(numf_parser_alphabet("🙃🙂🙂", "🙂🙃").unwrap(), 4);
+/// assert_eq!(numf_parser_alphabet("👍🏽👍🏻", "👍🏻👍🏽").unwrap(), 2);
+/// assert!(numf_parser_alphabet("102", "01").is_err());
+/// ```
+pub fn numf_parser_alphabet(s: &str, alphabet: &str) -> anyhow::Result<NumberType> {
+    parse_alphabet(alphabet)?;
+    let symbols = alphabet_symbols(alphabet);
+    let radix = symbols.len() as NumberType;
+    if s.is_empty() {
+        return Err(anyhow!("no digits"));
+    }
+    let mut num: NumberType = 0;
+    for symbol in s.graphemes(true) {
+        let Some(digit) = symbols.iter().position(|d| *d == symbol) else {
+            return Err(NumfError::NotInAlphabet(symbol.to_string()).into());
+        };
+        num = num
+            .checked_mul(radix)
+            .and_then(|num| num.checked_add(digit as NumberType))
+            .ok_or_else(|| anyhow!("{s} is too large for {} bits", NumberType::BITS))?;
+    }
+    Ok(num)
+}
+
+/// Split input data (like from the stdin) into the parts that should be parsed as numbers
+///
+/// Text is split at whitespace, except for lines of Morse code (with or without the prefix),
+/// which are kept together because the digits are separated by spaces. Data that is not valid
+/// UTF-8 is split at ASCII whitespace bytes, so that a stray binary token does not swallow the
+/// text numbers around it. If none of the parts is a text number though, the data is most likely
+/// a single raw number (which may contain bytes that look like whitespace), so it is returned as
+/// one part.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::split_input;
+///
+/// assert_eq!(split_input(b"1 0x2\n0b11"), vec![&b"1"[..], b"0x2", b"0b11"]);
+/// assert_eq!(split_input(b"1 \xff"), vec![&b"1"[..], b"\xff"]);
+/// assert_eq!(split_input(b"\x20\xff\xb4"), vec![&b"\x20\xff\xb4"[..]]);
+/// assert_eq!(split_input(b"0m.---- ...--\n2"), vec![&b"0m.---- ...--"[..], b"2"]);
+/// ```
+pub fn split_input(data: &[u8]) -> Vec<&[u8]> {
+    if let Ok(text) = std::str::from_utf8(data) {
+        return text
+            .lines()
+            .flat_map(|line| {
+                let line = line.trim();
+                let symbols = line.strip_prefix(Format::Morse.prefix_str().as_str());
+                let symbols = symbols.unwrap_or(line);
+                if symbols.contains(['.', '-']) && symbols.chars().all(|c| ".- ".contains(c)) {
+                    vec![line]
                 } else {
-                    buf.append(&mut format!("{num:b}").as_bytes().to_owned());
+                    line.split_whitespace().collect()
                 }
-            }
-            Format::Octal => buf.append(&mut format!("{num:o}").as_bytes().to_owned()),
-            Format::Dec => buf.append(&mut format!("{num}").as_bytes().to_owned()),
-            Format::Base64 => buf.append(
-                &mut fast32::base64::RFC4648
-                    .encode(&split::unsigned_to_vec(num))
-                    .as_bytes()
-                    .to_owned(),
-            ),
-            Format::Base32 => buf.append(
-                &mut fast32::base32::RFC4648
-                    .encode(&split::unsigned_to_vec(num))
-                    .as_bytes()
-                    .to_owned(),
-            ),
-            // Format::Raw => buf.append(&mut split::unsigned_to_vec(num)),
-            Format::Raw => buf.append(&mut split::unsigned_to_vec(num)),
+            })
+            .map(str::as_bytes)
+            .collect();
+    }
+    let parts: Vec<&[u8]> = data
+        .split(u8::is_ascii_whitespace)
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts
+        .iter()
+        .any(|part| numf_parser_detect(part) != Format::Raw)
+    {
+        parts
+    } else {
+        vec![data]
+    }
+}
+
+/// Split input data into the parts that should be parsed as numbers, at `delimiter`
+///
+/// This is like [split_input], but the numbers are separated by `delimiter` and line breaks
+/// instead of whitespace. The whitespace around the parts is removed, and empty parts are
+/// skipped. Data that is not text is a single raw number.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::split_input_by;
+///
+/// assert_eq!(split_input_by(b"1, 0x2,,3\n4", ','), vec![&b"1"[..], b"0x2", b"3", b"4"]);
+/// assert_eq!(split_input_by(b"1;2 3", ';'), vec![&b"1"[..], b"2 3"]);
+/// assert_eq!(split_input_by(b"\xff,\xb4", ','), vec![&b"\xff,\xb4"[..]]);
+/// ```
+pub fn split_input_by(data: &[u8], delimiter: char) -> Vec<&[u8]> {
+    match std::str::from_utf8(data) {
+        Ok(text) => text
+            .lines()
+            .flat_map(|line| line.split(delimiter))
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(str::as_bytes)
+            .collect(),
+        // binary data is a single raw number, there is nothing to split
+        Err(_) => vec![data],
+    }
+}
+
+/// Detect which [Format] [numf_parser] would use for the data
+///
+/// This only looks at the prefix, or whether the data is a decimal number. It does not check if
+/// the rest of the data is valid for that [Format]. Data that matches none of the text formats is
+/// [Format::Raw].
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{numf_parser_detect, Format};
+///
+/// assert_eq!(numf_parser_detect(b"1337"), Format::Dec);
+/// assert_eq!(numf_parser_detect(b"0x1337"), Format::Hex);
+/// assert_eq!(numf_parser_detect(b"032sIFAUEQQ="), Format::Base32);
+/// assert_eq!(numf_parser_detect(&[0x13, 0x37]), Format::Raw);
+/// ```
+pub fn numf_parser_detect(data: &[u8]) -> Format {
+    if let Some((format, _)) = Format::from_prefix(data) {
+        return format;
+    }
+    // same order as in numf_parser
+    if strip_hash_prefix(&String::from_utf8_lossy(data)).is_some() {
+        return Format::Hex;
+    }
+    let data_as_text = String::from_utf8_lossy(data).to_string();
+    if data_as_text.parse::<NumberType>().is_ok() {
+        Format::Dec
+    } else if decode_percent(&data_as_text).is_ok() {
+        Format::Percent
+    } else if decode_morse(&data_as_text).is_ok() {
+        Format::Morse
+    } else {
+        Format::Raw
+    }
+}
+
+/// Converts a &[str] into an unsigned integer value (like [u128]) in an explicit base
+///
+/// Unlike [numf_parser_str], no prefixes are detected, the whole string must consist of digits
+/// that are valid in `radix`. Letters are accepted in upper and lower case.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidRadix] if the radix is not in `2..=36`, and
+/// [NumfError::InvalidDigit] if the string contains a digit that is not valid in that base.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::numf_parser_radix;
+///
+/// assert_eq!(numf_parser_radix::<u32>("10", 7).unwrap(), 7);
+/// assert_eq!(numf_parser_radix::<u32>("zz", 36).unwrap(), 1295);
+/// assert!(numf_parser_radix::<u32>("18", 7).is_err());
+/// ```
+pub fn numf_parser_radix<T>(s: &str, radix: u32) -> anyhow::Result<T>
+where
+    T: num::Num,
+    <T as num::Num>::FromStrRadixErr: std::fmt::Display,
+{
+    if !(2..=36).contains(&radix) {
+        return Err(NumfError::InvalidRadix(radix).into());
+    }
+    if let Some(digit) = s.chars().find(|c| c.to_digit(radix).is_none()) {
+        return Err(NumfError::InvalidDigit { digit, radix }.into());
+    }
+    match T::from_str_radix(s, radix) {
+        Ok(r) => Ok(r),
+        Err(e) => {
+            let e = format!("{e}");
+            Err(anyhow!(e))
         }
-        buf
     }
 }
 
@@ -481,7 +5535,108 @@ impl Format {
 /// let args = Args::parse_from(&["", "-a", "0x10"]);
 /// assert_eq!(args.address, 16);
 /// ```
-pub fn numf_parser_str<T>(s: &str) -> anyhow::Result<T>
+pub fn numf_parser_str<T>(s: &str) -> anyhow::Result<T>
+where
+    T: std::str::FromStr + std::convert::TryFrom<u128>,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+    T: num::Num,
+    <T as num::Num>::FromStrRadixErr: std::fmt::Display,
+    <T as std::str::FromStr>::Err: std::fmt::Debug,
+    u128: std::convert::From<T>,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    <T as std::convert::TryFrom<u128>>::Error: std::error::Error,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Send,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Sync,
+    <T as std::convert::TryFrom<u128>>::Error: 'static,
+{
+    numf_parser(s.as_bytes())
+}
+
+/// Converts any data (as bytes) into an unsigned integer value `T` (like [u128]), according to one of the [Formats](Format)
+///
+/// If you only want to parse text data, use [numf_parser_str] instead.
+///
+/// The parser will first try to convert the data to a [String].
+///
+/// Then, the number is assumed to be base-10 by default, it is parsed as a different
+/// [Format] if the number is prefixed with the [prefix](FormatOptions::prefix),
+/// for that [Format]. So if the user inputs `0b1100` then this is parsed as
+/// [Binary](Format::Bin) and so on. Decimal numbers with the prefix may also be written in
+/// scientific notation, like `0d1e6`, as long as they are whole numbers, see [parse_scientific].
+///
+/// If none of the text [Formats](Format) matches, the data will be assumed to be raw and converted
+/// to the ingeger type directly.
+///
+/// # Errors
+///
+/// If no text [Format] matches and the data is too long for the integer `T`.
+///
+/// If the data is a text number with a leading `-` (like `-0x10` or `-16`), a
+/// [NumfError::NegativeNotSupported] is returned, as [numf](crate) only works with unsigned
+/// integers.
+///
+/// # Returns
+///
+/// This parser will only output unsigned integers, it cannot be used with signed integers.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::numf_parser;
+///
+/// let data = &[0x15, 0x92, 0xff];
+/// let result: u64 = 0x1592ff;
+/// assert_eq!(result, numf_parser(data).unwrap());
+///
+/// let data = b"0x1337";
+/// let result: u64 = 0x1337;
+/// assert_eq!(result, numf_parser(data).unwrap());
+///
+/// let data = b"0b110011";
+/// let result: u64 = 0b110011;
+/// assert_eq!(result, numf_parser(data).unwrap());
+/// ```
+pub fn numf_parser<T>(data: &[u8]) -> anyhow::Result<T>
+where
+    T: std::str::FromStr + std::convert::TryFrom<u128>,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+    T: num::Num,
+    <T as num::Num>::FromStrRadixErr: std::fmt::Display,
+    <T as std::str::FromStr>::Err: std::fmt::Debug,
+    u128: std::convert::From<T>,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    <T as std::convert::TryFrom<u128>>::Error: std::error::Error,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Send,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Sync,
+    <T as std::convert::TryFrom<u128>>::Error: 'static,
+{
+    numf_parser_impl(data, Some(Format::Raw.prefix()[0]))
+}
+
+/// Converts text data (as bytes) into an unsigned integer value `T` (like [u128]), without
+/// falling back to [Format::Raw]
+///
+/// This works like [numf_parser], but data that matches none of the text [Formats](Format) is an
+/// error instead of being read as raw bytes. Use this for input from users, where a typo should
+/// not silently become a number.
+///
+/// # Errors
+///
+/// Returns [NumfError::UnknownFormat] if no text [Format] matches, and the same errors as
+/// [numf_parser] otherwise.
+///
+/// # Example
+///
+/// ```
+/// use numf::format::{numf_parser, numf_parser_strict};
+///
+/// assert_eq!(numf_parser_strict::<u64>(b"0x1337").unwrap(), 0x1337);
+/// assert_eq!(numf_parser_strict::<u64>(b"1337").unwrap(), 1337);
+/// assert!(numf_parser_strict::<u64>(b"hello").is_err());
+/// // the lenient parser reads it as raw bytes
+/// assert_eq!(numf_parser::<u64>(b"hello").unwrap(), 0x68656c6c6f);
+/// ```
+pub fn numf_parser_strict<T>(data: &[u8]) -> anyhow::Result<T>
 where
     T: std::str::FromStr + std::convert::TryFrom<u128>,
     <T as std::str::FromStr>::Err: std::fmt::Display,
@@ -495,143 +5650,907 @@ where
     <T as std::convert::TryFrom<u128>>::Error: std::marker::Sync,
     <T as std::convert::TryFrom<u128>>::Error: 'static,
 {
-    numf_parser(s.as_bytes())
+    numf_parser_impl(data, None)
 }
 
-/// Converts any data (as bytes) into an unsigned integer value `T` (like [u128]), according to one of the [Formats](Format)
+/// Converts raw bytes into an unsigned integer value `T` (like [u128]), big-endian
 ///
-/// If you only want to parse text data, use [numf_parser_str] instead.
+/// Unlike [numf_parser], nothing is detected: the data is always read as [Format::Raw]. If
+/// `skip_prefix` is true, the first byte is the prefix and is skipped, whatever its value is,
+/// otherwise all bytes are part of the number. Use this for binary data, where the guessing of
+/// [numf_parser] (which skips a leading prefix byte if there is more than one byte) could be
+/// wrong.
 ///
-/// The parser will first try to convert the data to a [String].
+/// # Errors
 ///
-/// Then, the number is assumed to be base-10 by default, it is parsed as a different
-/// [Format] if the number is prefixed with the [prefix](FormatOptions::prefix),
-/// for that [Format]. So if the user inputs `0b1100` then this is parsed as
-/// [Binary](Format::Bin) and so on.
+/// Fails if the number has more bytes than fit into `T`, or if `skip_prefix` is true but there
+/// is no byte to skip.
 ///
-/// If none of the text [Formats](Format) matches, the data will be assumed to be raw and converted
-/// to the ingeger type directly.
+/// # Example
 ///
-/// # Errors
+/// ```
+/// use numf::format::{numf_parser, numf_parser_raw};
 ///
-/// If no text [Format] matches and the data is too long for the integer `T`.
+/// assert_eq!(numf_parser_raw::<u32>(&[0x00, 0x13, 0x37], false).unwrap(), 0x1337);
+/// assert_eq!(numf_parser_raw::<u32>(&[0x13, 0x37], true).unwrap(), 0x37);
+/// assert_eq!(numf_parser_raw::<u32>(&[0x13, 0x37], false).unwrap(), 0x1337);
+/// ```
+pub fn numf_parser_raw<T>(data: &[u8], skip_prefix: bool) -> anyhow::Result<T>
+where
+    T: std::convert::TryFrom<u128>,
+    <T as std::convert::TryFrom<u128>>::Error: std::error::Error,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Send,
+    <T as std::convert::TryFrom<u128>>::Error: std::marker::Sync,
+    <T as std::convert::TryFrom<u128>>::Error: 'static,
+{
+    Ok(T::try_from(numf_parse_raw_u128(data, skip_prefix)?)?)
+}
+
+/// Format a number in all [Formats](Format) except [Format::Raw]
 ///
-/// # Returns
+/// The formats are in the order of [Format::ALL], each with the number formatted as a [String]
+/// with `options`, so the prefix and padding apply. Raw is left out because its bytes are not
+/// text, use [Format::format] for it.
 ///
-/// This parser will only output unsigned integers, it cannot be used with signed integers.
+/// # Example
+///
+/// ```
+/// use numf::format::{format_all_formats, Format, FormatOptions};
+///
+/// let all = format_all_formats(255, &FormatOptions::default());
+/// assert_eq!(all[0], (Format::Dec, "255".to_string()));
+/// assert_eq!(all[1], (Format::Hex, "FF".to_string()));
+/// assert!(all.iter().all(|(format, _)| *format != Format::Raw));
+/// ```
+pub fn format_all_formats(num: NumberType, options: &FormatOptions) -> Vec<(Format, String)> {
+    Format::ALL
+        .into_iter()
+        .filter(|format| *format != Format::Raw)
+        .map(|format| (format, format.format_str(num, options)))
+        .collect()
+}
+
+/// Read the bytes of `text` as numbers, for `--text-in`
+///
+/// With [TextIn::Bytes], every byte is its own number. With [TextIn::Combined], all bytes are
+/// a single big-endian number, and there is no number for empty text.
+///
+/// # Errors
+///
+/// Fails for [TextIn::Combined] if the text has more bytes than fit into a [NumberType].
 ///
 /// # Example
 ///
 /// ```
-/// use numf::format::numf_parser;
+/// use numf::format::{text_to_numbers, TextIn};
 ///
-/// let data = &[0x15, 0x92, 0xff];
-/// let result: u64 = 0x1592ff;
-/// assert_eq!(result, numf_parser(data).unwrap());
+/// assert_eq!(text_to_numbers(b"AB", TextIn::Bytes).unwrap(), vec![65, 66]);
+/// assert_eq!(text_to_numbers(b"AB", TextIn::Combined).unwrap(), vec![0x4142]);
+/// ```
+pub fn text_to_numbers(text: &[u8], mode: TextIn) -> Result<Vec<NumberType>, NumfError> {
+    match mode {
+        TextIn::Bytes => Ok(text.iter().map(|b| *b as NumberType).collect()),
+        TextIn::Combined if text.is_empty() => Ok(Vec::new()),
+        TextIn::Combined => {
+            let max = NumberType::BITS as usize / 8;
+            if text.len() > max {
+                return Err(NumfError::InvalidNumber {
+                    format: Format::Raw,
+                    reason: format!(
+                        "the text has {} bytes, but at most {max} fit into a number",
+                        text.len()
+                    ),
+                });
+            }
+            Ok(vec![text
+                .iter()
+                .fold(0, |num, b| num << 8 | *b as NumberType)])
+        }
+    }
+}
+
+/// The actual implementation of [numf_parser_raw]
+fn numf_parse_raw_u128(data: &[u8], skip_prefix: bool) -> Result<u128, NumfError> {
+    let invalid = |reason: &dyn Display| NumfError::InvalidNumber {
+        format: Format::Raw,
+        reason: reason.to_string(),
+    };
+    let bytes = if skip_prefix {
+        data.get(1..)
+            .ok_or_else(|| invalid(&"there is no prefix byte to skip"))?
+    } else {
+        data
+    };
+    join::array_to_unsigned(bytes).map_err(|e| invalid(&e))
+}
+
+/// Converts any data (as bytes) into a [u128], according to one of the [Formats](Format)
 ///
-/// let data = b"0x1337";
-/// let result: u64 = 0x1337;
-/// assert_eq!(result, numf_parser(data).unwrap());
+/// This is the same as [numf_parser], but not generic, so it is easier to use and faster to
+/// compile if you only need [NumberType] anyway. The errors are always [NumfError].
+///
+/// # Errors
+///
+/// Returns [NumfError::NegativeNotSupported] for negative numbers, and
+/// [NumfError::InvalidNumber] if the data has the prefix of a [Format] but is not valid in it,
+/// or if it is raw data that is too long.
+///
+/// # Example
 ///
-/// let data = b"0b110011";
-/// let result: u64 = 0b110011;
-/// assert_eq!(result, numf_parser(data).unwrap());
 /// ```
-pub fn numf_parser<T>(data: &[u8]) -> anyhow::Result<T>
+/// use numf::format::{numf_parse_u128, Format, NumfError};
+///
+/// assert_eq!(numf_parse_u128(b"0x1337"), Ok(0x1337));
+/// assert_eq!(numf_parse_u128(&[0x13, 0x37]), Ok(0x1337));
+/// assert!(matches!(
+///     numf_parse_u128(b"0xfg"),
+///     Err(NumfError::InvalidNumber { format: Format::Hex, .. })
+/// ));
+/// ```
+pub fn numf_parse_u128(data: &[u8]) -> Result<u128, NumfError> {
+    numf_parse_u128_impl(data, Some(Format::Raw.prefix()[0]))
+}
+
+/// [numf_parser_impl], but octal numbers with the prefix `0` are accepted for [PrefixStyle::C]
+///
+/// A `0` followed only by octal digits is octal then, anything else is parsed as usual, so `0`
+/// itself, `09` and `0d17` are still decimal.
+pub(crate) fn numf_parser_styled(
+    data: &[u8],
+    raw_prefix: Option<u8>,
+    style: PrefixStyle,
+) -> anyhow::Result<NumberType> {
+    if style == PrefixStyle::C {
+        if let Some(digits) = strip_c_octal_prefix(data) {
+            let digits = String::from_utf8_lossy(digits);
+            return NumberType::from_str_radix(&digits, 8).map_err(|e| {
+                NumfError::InvalidNumber {
+                    format: Format::Octal,
+                    reason: e.to_string(),
+                }
+                .into()
+            });
+        }
+    }
+    numf_parser_impl(data, raw_prefix)
+}
+
+/// The octal digits after the `0` of [PrefixStyle::C], like in `0644`
+///
+/// Returns [None] if there is no leading `0`, nothing after it, or anything but octal digits
+/// after it, so that `0`, `09` and `0x1F` are not octal.
+fn strip_c_octal_prefix(data: &[u8]) -> Option<&[u8]> {
+    data.strip_prefix(b"0")
+        .filter(|digits| !digits.is_empty() && digits.iter().all(|b| (b'0'..=b'7').contains(b)))
+}
+
+/// The actual implementation of [numf_parser], with a configurable prefix byte for
+/// [Format::Raw]
+///
+/// If `raw_prefix` is [None], raw input is not accepted at all, see [numf_parser_strict].
+pub(crate) fn numf_parser_impl<T>(data: &[u8], raw_prefix: Option<u8>) -> anyhow::Result<T>
 where
-    T: std::str::FromStr + std::convert::TryFrom<u128>,
-    <T as std::str::FromStr>::Err: std::fmt::Display,
-    T: num::Num,
-    <T as num::Num>::FromStrRadixErr: std::fmt::Display,
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
-    u128: std::convert::From<T>,
-    <T as std::str::FromStr>::Err: std::error::Error,
+    T: std::convert::TryFrom<u128>,
     <T as std::convert::TryFrom<u128>>::Error: std::error::Error,
     <T as std::convert::TryFrom<u128>>::Error: std::marker::Send,
     <T as std::convert::TryFrom<u128>>::Error: std::marker::Sync,
     <T as std::convert::TryFrom<u128>>::Error: 'static,
 {
+    Ok(T::try_from(numf_parse_u128_impl(data, raw_prefix)?)?)
+}
+
+/// The actual implementation of [numf_parse_u128], see [numf_parser_impl]
+fn numf_parse_u128_impl(data: &[u8], raw_prefix: Option<u8>) -> Result<u128, NumfError> {
     let data_as_text = String::from_utf8_lossy(data).to_string();
+    let invalid = |format: Format, reason: &dyn Display| NumfError::InvalidNumber {
+        format,
+        reason: reason.to_string(),
+    };
 
-    if data_as_text.starts_with(&Format::Dec.prefix_str()) || data_as_text.parse::<T>().is_ok() {
-        let s = match data_as_text.strip_prefix(&Format::Dec.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
-        };
-        match s.parse() {
-            Ok(r) => Ok(r),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
-            }
+    // a leading '-' in front of something that would otherwise be a text number should not end
+    // up in the raw branch
+    if let Some(s) = data_as_text.strip_prefix('-') {
+        let looks_textual = (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            || Format::from_prefix(s.as_bytes()).is_some();
+        if looks_textual {
+            return Err(NumfError::NegativeNotSupported(data_as_text));
         }
-    } else if data_as_text.starts_with(&Format::Hex.prefix_str()) {
-        let s = match data_as_text.strip_prefix(&Format::Hex.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
+    }
+
+    // Prefixes come first, so that something like `032s...` (base32) or `016s...` (base16) is
+    // never taken for a decimal number. Without a prefix, anything that is only digits is decimal,
+    // even if it starts with `0`, like `032`.
+    if let Some((format, rest)) = Format::from_prefix(data) {
+        let s = String::from_utf8_lossy(rest);
+        return numf_parse_prefixed(format, &s);
+    }
+    if let Some(digits) = strip_hash_prefix(&data_as_text) {
+        return numf_parse_prefixed(Format::Hex, digits);
+    }
+
+    if let Ok(num) = data_as_text.parse::<u128>() {
+        Ok(num)
+    } else if let Ok(bytes) = decode_percent(&data_as_text) {
+        // the percent signs already say what it is, so the prefix is not needed
+        join::array_to_unsigned(&bytes).map_err(|e| invalid(Format::Percent, &e))
+    } else if let Ok(num) = decode_morse(&data_as_text) {
+        // nothing else is just dots and dashes
+        Ok(num)
+    } else {
+        let Some(raw_prefix) = raw_prefix else {
+            return Err(NumfError::UnknownFormat(data_as_text));
         };
-        match T::from_str_radix(s, 16) {
-            Ok(r) => Ok(r),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
-            }
+        // what could go wrong with interpreting everything else as raw number input
+        //
+        // A single byte is just the value. If there is more than that, a leading prefix byte
+        // (0x00 by default) is the prefix of Format::Raw and is skipped, which is also what
+        // allows 17 bytes for a prefixed u128.
+        numf_parse_raw_u128(data, data.len() > 1 && data[0] == raw_prefix)
+    }
+}
+
+/// Parse a whole number in scientific notation, like `1e6` or `1.5e3`
+///
+/// The mantissa is a decimal number that may have a fractional part, the exponent after the `e`
+/// (or `E`) may have a sign. Returns [None] if `s` does not look like scientific notation at all,
+/// so that it can be tried as something else.
+///
+/// The parsers only use this after the decimal prefix `0d` (or with [Format::Dec] as the input
+/// format), so that bare input like `1e6` is still raw data.
+///
+/// # Errors
+///
+/// Returns [NumfError::InvalidNumber] if the result is not a whole number or does not fit into a
+/// [NumberType].
+///
+/// # Example
+///
+/// ```
+/// use numf::format::parse_scientific;
+///
+/// assert_eq!(parse_scientific("1e6").unwrap().unwrap(), 1_000_000);
+/// assert_eq!(parse_scientific("1.5e1").unwrap().unwrap(), 15);
+/// assert_eq!(parse_scientific("1500E-2").unwrap().unwrap(), 15);
+/// assert!(parse_scientific("1.5e0").unwrap().is_err());
+/// assert!(parse_scientific("1e40").unwrap().is_err());
+/// assert!(parse_scientific("1337").is_none());
+/// ```
+pub fn parse_scientific(s: &str) -> Option<Result<NumberType, NumfError>> {
+    let (mantissa, exponent) = s.split_once(['e', 'E'])?;
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let exponent_digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty()
+        || exponent_digits.is_empty()
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || !is_digits(exponent_digits)
+    {
+        return None;
+    }
+    let invalid = |reason: &str| NumfError::InvalidNumber {
+        format: Format::Dec,
+        reason: format!("{s} {reason}"),
+    };
+
+    // the mantissa without its dot, and how many places it has to be moved to the left
+    let fraction = fraction.trim_end_matches('0');
+    let digits = format!("{whole}{fraction}");
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Some(Ok(0));
+    }
+    let Ok(value) = digits.parse::<NumberType>() else {
+        return Some(Err(invalid("has too many digits")));
+    };
+    let Ok(exponent) = exponent.parse::<i64>() else {
+        return Some(Err(invalid("does not fit into 128 bits")));
+    };
+    // this only overflows for huge negative exponents, which leave a fraction
+    let Some(shift) = exponent.checked_sub(fraction.len() as i64) else {
+        return Some(Err(invalid("is not a whole number")));
+    };
+    let power = |places: u64| {
+        u32::try_from(places)
+            .ok()
+            .and_then(|places| NumberType::checked_pow(10, places))
+    };
+    Some(if shift >= 0 {
+        power(shift.unsigned_abs())
+            .and_then(|power| value.checked_mul(power))
+            .ok_or_else(|| invalid("does not fit into 128 bits"))
+    } else {
+        match power(shift.unsigned_abs()) {
+            Some(power) if value % power == 0 => Ok(value / power),
+            _ => Err(invalid("is not a whole number")),
         }
-    } else if data_as_text.starts_with(&Format::Octal.prefix_str()) {
-        let s = match data_as_text.strip_prefix(&Format::Octal.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
-        };
-        match T::from_str_radix(s, 8) {
-            Ok(r) => Ok(r),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
-            }
+    })
+}
+
+/// The zeros of the decimal digits that [normalize_digits] knows, the other digits follow them
+const UNICODE_ZEROS: [char; 26] = [
+    '\u{0660}',  // Arabic-Indic
+    '\u{06F0}',  // Extended Arabic-Indic
+    '\u{07C0}',  // NKo
+    '\u{0966}',  // Devanagari
+    '\u{09E6}',  // Bengali
+    '\u{0A66}',  // Gurmukhi
+    '\u{0AE6}',  // Gujarati
+    '\u{0B66}',  // Oriya
+    '\u{0BE6}',  // Tamil
+    '\u{0C66}',  // Telugu
+    '\u{0CE6}',  // Kannada
+    '\u{0D66}',  // Malayalam
+    '\u{0DE6}',  // Sinhala Lith
+    '\u{0E50}',  // Thai
+    '\u{0ED0}',  // Lao
+    '\u{0F20}',  // Tibetan
+    '\u{1040}',  // Myanmar
+    '\u{17E0}',  // Khmer
+    '\u{1810}',  // Mongolian
+    '\u{FF10}',  // Fullwidth
+    '\u{104A0}', // Osmanya
+    '\u{1D7CE}', // Mathematical Bold
+    '\u{1D7D8}', // Mathematical Double-Struck
+    '\u{1D7E2}', // Mathematical Sans-Serif
+    '\u{1D7EC}', // Mathematical Sans-Serif Bold
+    '\u{1D7F6}', // Mathematical Monospace
+];
+
+/// Replace the decimal digits of other scripts with the ASCII digits `0` to `9`
+///
+/// Everything else is kept as it is, and nothing is allocated if there are no such digits.
+/// These digits are supported, each as the ten code points from the zero on:
+///
+/// | Digits | Zero |
+/// |--------|------|
+/// | Arabic-Indic | `U+0660` |
+/// | Extended Arabic-Indic (Persian, Urdu) | `U+06F0` |
+/// | NKo | `U+07C0` |
+/// | Devanagari | `U+0966` |
+/// | Bengali | `U+09E6` |
+/// | Gurmukhi | `U+0A66` |
+/// | Gujarati | `U+0AE6` |
+/// | Oriya | `U+0B66` |
+/// | Tamil | `U+0BE6` |
+/// | Telugu | `U+0C66` |
+/// | Kannada | `U+0CE6` |
+/// | Malayalam | `U+0D66` |
+/// | Sinhala Lith | `U+0DE6` |
+/// | Thai | `U+0E50` |
+/// | Lao | `U+0ED0` |
+/// | Tibetan | `U+0F20` |
+/// | Myanmar | `U+1040` |
+/// | Khmer | `U+17E0` |
+/// | Mongolian | `U+1810` |
+/// | Fullwidth | `U+FF10` |
+/// | Osmanya | `U+104A0` |
+/// | Mathematical Bold | `U+1D7CE` |
+/// | Mathematical Double-Struck | `U+1D7D8` |
+/// | Mathematical Sans-Serif | `U+1D7E2` |
+/// | Mathematical Sans-Serif Bold | `U+1D7EC` |
+/// | Mathematical Monospace | `U+1D7F6` |
+///
+/// # Example
+///
+/// ```
+/// use numf::format::normalize_digits;
+///
+/// assert_eq!(normalize_digits("１２３"), "123");
+/// assert_eq!(normalize_digits("٤٢"), "42");
+/// assert_eq!(normalize_digits("0x１F"), "0x1F");
+/// assert_eq!(normalize_digits("1337"), "1337");
+/// ```
+pub fn normalize_digits(s: &str) -> Cow<'_, str> {
+    let ascii = |c: char| {
+        UNICODE_ZEROS.iter().find_map(|zero| {
+            let digit = (c as u32).checked_sub(*zero as u32)?;
+            // unwrap is fine, this is an ASCII digit
+            (digit < 10).then(|| char::from_digit(digit, 10).unwrap())
+        })
+    };
+    if !s.chars().any(|c| ascii(c).is_some()) {
+        return Cow::Borrowed(s);
+    }
+    Cow::Owned(s.chars().map(|c| ascii(c).unwrap_or(c)).collect())
+}
+
+/// The hexadecimal digits after a `#`, like in the colors of HTML and CSS (`#1a2b3c`)
+///
+/// Returns [None] if there is no `#`, or if anything after it is not a hexadecimal digit, so
+/// that other data with a `#` is still read as raw bytes.
+fn strip_hash_prefix(s: &str) -> Option<&str> {
+    s.strip_prefix('#')
+        .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// Parse the rest of a number after the prefix of `format`, see [Format::from_prefix]
+///
+/// This is also used by [Format::parse], after the prefix was stripped if there was one.
+fn numf_parse_prefixed(format: Format, s: &str) -> Result<u128, NumfError> {
+    let invalid = |e: &dyn Display| NumfError::InvalidNumber {
+        format,
+        reason: e.to_string(),
+    };
+    match format {
+        Format::Dec => match parse_scientific(s) {
+            Some(result) => result,
+            None => s.parse().map_err(|e| invalid(&e)),
+        },
+        Format::Hex => u128::from_str_radix(s, 16).map_err(|e| invalid(&e)),
+        Format::Octal => u128::from_str_radix(s, 8).map_err(|e| invalid(&e)),
+        Format::Bin => u128::from_str_radix(s, 2).map_err(|e| invalid(&e)),
+        Format::Duration => parse_duration(s).map_err(|e| invalid(&e)),
+        Format::Negabinary => decode_negabinary(s).map_err(|e| invalid(&e)),
+        Format::Morse => decode_morse(s).map_err(|e| invalid(&e)),
+        Format::Zeckendorf => decode_zeckendorf(s).map_err(|e| invalid(&e)),
+        _ => {
+            let bytes = match format {
+                Format::Base64 => {
+                    let s = unwrap_lines(s);
+                    check_base_alphabet(&s, |b| b.is_ascii_alphanumeric() || b"+/".contains(&b))
+                        .map_err(|e| invalid(&e))?;
+                    fast32::base64::RFC4648
+                        .decode_str(restore_base_padding(&s, 4))
+                        .map_err(|e| invalid(&e))?
+                }
+                // base32 has no lowercase letters, but they are written with --lowercase
+                Format::Base32 => {
+                    let s = unwrap_lines(s);
+                    check_base_alphabet(&s, |b| {
+                        b.is_ascii_alphabetic() || (b'2'..=b'7').contains(&b)
+                    })
+                    .map_err(|e| invalid(&e))?;
+                    fast32::base32::RFC4648
+                        .decode_str(restore_base_padding(&s, 8).to_ascii_uppercase())
+                        .map_err(|e| invalid(&e))?
+                }
+                Format::Base32Crockford => fast32::base32::CROCKFORD
+                    .decode_str(normalize_crockford(s))
+                    .map_err(|e| invalid(&e))?,
+                Format::Base85 => decode_base85(s).map_err(|e| invalid(&e))?,
+                Format::Base16 => decode_base16(s).map_err(|e| invalid(&e))?,
+                Format::Percent => decode_percent(s).map_err(|e| invalid(&e))?,
+                // from_prefix never matches raw data
+                _ => unreachable!("{format} has no text prefix"),
+            };
+            join::array_to_unsigned(&bytes).map_err(|e| invalid(&e))
         }
-    } else if data_as_text.starts_with(&Format::Bin.prefix_str()) {
-        let s = match data_as_text.strip_prefix(&Format::Bin.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
-        };
-        match T::from_str_radix(s, 2) {
-            Ok(r) => Ok(r),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
-            }
+    }
+}
+
+/// The Morse code of the decimal digits, `MORSE_DIGITS[3]` is the code for `3`
+pub(crate) const MORSE_DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+];
+
+/// Write the decimal digits of `num` in Morse code, separated by spaces
+fn encode_morse(num: NumberType) -> String {
+    num.to_string()
+        .bytes()
+        .map(|digit| MORSE_DIGITS[(digit - b'0') as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read decimal digits in Morse code, see [encode_morse]
+///
+/// The digits may be separated by any amount of spaces. Letters and the word separator `/` are
+/// errors.
+fn decode_morse(s: &str) -> anyhow::Result<NumberType> {
+    if s.contains('/') {
+        return Err(anyhow!(
+            "a number is a single word, but '/' separates words"
+        ));
+    }
+    let mut digits = String::new();
+    for code in s.split(' ').filter(|code| !code.is_empty()) {
+        match MORSE_DIGITS.iter().position(|digit| *digit == code) {
+            Some(digit) => digits.push((b'0' + digit as u8) as char),
+            None => return Err(anyhow!("{code:?} is not a digit in Morse code")),
+        }
+    }
+    if digits.is_empty() {
+        return Err(anyhow!("no digits"));
+    }
+    digits.parse().map_err(|e| anyhow!("{e}"))
+}
+
+/// The Fibonacci numbers that fit into [NumberType], starting with 1 and 2
+///
+/// These are the places of [Format::Zeckendorf], the smallest first.
+pub(crate) fn fibonacci_places() -> Vec<NumberType> {
+    let mut places: Vec<NumberType> = vec![1, 2];
+    while let Some(next) = places[places.len() - 2].checked_add(places[places.len() - 1]) {
+        places.push(next);
+    }
+    places
+}
+
+/// Write `num` as the sum of Fibonacci numbers of its Zeckendorf representation
+///
+/// The largest Fibonacci number that still fits is taken first, which never takes two that are
+/// next to each other.
+pub(crate) fn encode_zeckendorf(num: NumberType) -> String {
+    if num == 0 {
+        return "0".to_string();
+    }
+    let mut digits = String::new();
+    let mut rest = num;
+    for place in fibonacci_places().into_iter().rev() {
+        if place <= rest {
+            digits.push('1');
+            rest -= place;
+        } else if !digits.is_empty() {
+            digits.push('0');
+        }
+    }
+    digits
+}
+
+/// Read a number in the Zeckendorf representation, see [encode_zeckendorf]
+///
+/// Fails if a digit is not `0` or `1`, if two `1` are next to each other, or if the number is
+/// too large for [NumberType].
+fn decode_zeckendorf(s: &str) -> anyhow::Result<NumberType> {
+    if s.is_empty() {
+        return Err(anyhow!("no digits"));
+    }
+    if let Some(c) = s.chars().find(|c| !matches!(c, '0' | '1')) {
+        return Err(anyhow!(
+            "{c:?} is not a digit of the Zeckendorf representation"
+        ));
+    }
+    if s.contains("11") {
+        return Err(anyhow!(
+            "two Fibonacci numbers next to each other are not allowed"
+        ));
+    }
+    let places = fibonacci_places();
+    let mut num: NumberType = 0;
+    for (i, digit) in s.bytes().rev().enumerate() {
+        if digit == b'0' {
+            continue;
         }
-    } else if data_as_text.starts_with(&Format::Base64.prefix_str()) {
-        let s = match data_as_text.strip_prefix(&Format::Base64.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
+        num = places
+            .get(i)
+            .and_then(|place| num.checked_add(*place))
+            .ok_or_else(|| anyhow!("too large for {} bits", NumberType::BITS))?;
+    }
+    Ok(num)
+}
+
+/// Write `num` in base -2
+///
+/// The digits are found like for a positive base: the remainder is the next digit, and the number
+/// is divided by the base. Because the base is negative, the rest changes its sign in every step,
+/// so only its magnitude and sign are kept, which also works for [u128::MAX].
+pub(crate) fn encode_negabinary(num: NumberType) -> String {
+    let mut digits = Vec::new();
+    let mut magnitude = num;
+    let mut negative = false;
+    while magnitude != 0 {
+        let digit = magnitude % 2;
+        digits.push(b'0' + digit as u8);
+        // (n - d) / -2 for a positive rest, (-n - d) / -2 = (n + d) / 2 for a negative one
+        magnitude = if negative {
+            magnitude / 2 + digit
+        } else {
+            magnitude / 2
+        };
+        negative = !negative && magnitude != 0;
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    digits.reverse();
+    String::from_utf8_lossy(&digits).to_string()
+}
+
+/// Read a number in base -2, see [encode_negabinary]
+///
+/// Fails if a digit is not `0` or `1`, or if the number is negative or too large for
+/// [NumberType].
+fn decode_negabinary(s: &str) -> anyhow::Result<NumberType> {
+    if s.is_empty() {
+        return Err(anyhow!("no digits"));
+    }
+    let mut magnitude: NumberType = 0;
+    let mut negative = false;
+    for c in s.chars() {
+        let digit = match c {
+            '0' => 0,
+            '1' => 1,
+            _ => return Err(anyhow!("{c:?} is not a digit in base -2")),
         };
-        match fast32::base64::RFC4648.decode_str(s) {
-            Ok(r) => Ok(join::array_to_unsigned::<T>(&r)?),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
+        // multiply by -2, then add the digit
+        magnitude = magnitude
+            .checked_mul(2)
+            .ok_or_else(|| anyhow!("too large for {} bits", NumberType::BITS))?;
+        negative = !negative && magnitude != 0;
+        if negative {
+            // the magnitude is at least 2 here, so this can not go below 0
+            magnitude -= digit;
+        } else {
+            magnitude = magnitude
+                .checked_add(digit)
+                .ok_or_else(|| anyhow!("too large for {} bits", NumberType::BITS))?;
+        }
+    }
+    if negative {
+        return Err(NumfError::NegativeNotSupported(s.to_string()).into());
+    }
+    Ok(magnitude)
+}
+
+/// Insert a newline after every `width` characters of `s`, but not at the end
+///
+/// # Panics
+///
+/// Panics if `width` is 0.
+fn wrap_lines(s: &str, width: usize) -> String {
+    group_chars(s, width, "\n")
+}
+
+/// Put `separator` after every `size` characters of `s`, but not at the end
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+fn group_chars(s: &str, size: usize, separator: &str) -> String {
+    s.as_bytes()
+        .chunks(size)
+        // base64 and base32 are ASCII, so this does not split characters
+        .map(String::from_utf8_lossy)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Remove the line breaks of [wrap_lines] (and `\r\n` ones) again
+fn unwrap_lines(s: &str) -> Cow<'_, str> {
+    if s.contains(['\n', '\r']) {
+        Cow::Owned(s.replace(['\n', '\r'], ""))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Check that base64 or base32 data only has characters for which `valid` is true, and `=` only as
+/// padding at the end
+///
+/// The decoder of fast32 does not catch all invalid characters by itself.
+fn check_base_alphabet(s: &str, valid: impl Fn(u8) -> bool) -> Result<(), String> {
+    let data = s.trim_end_matches('=');
+    match data.bytes().position(|b| !valid(b)) {
+        Some(index) => Err(format!(
+            "invalid character {:?} at index {index}",
+            data[index..].chars().next().unwrap_or_default()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Add the `=` padding back to base64 or base32 data, if it was left out
+///
+/// `block` is the amount of characters that the length must be a multiple of, 4 for base64 and 8
+/// for base32.
+fn restore_base_padding(s: &str, block: usize) -> String {
+    let missing = (block - s.len() % block) % block;
+    format!("{s}{}", "=".repeat(missing))
+}
+
+/// Bring Crockford base32 into its canonical form
+///
+/// Crockford base32 is case insensitive, reads `I` and `L` as `1` and `O` as `0`, and allows
+/// hyphens for readability.
+fn normalize_crockford(s: &str) -> String {
+    s.chars()
+        .filter(|c| *c != '-')
+        .map(|c| match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            other => other,
+        })
+        .collect()
+}
+
+/// Encode data as Adobe Ascii85, without the `<~` and `~>` delimiters
+///
+/// Full groups of 4 zero bytes are written as `z`. A partial group at the end of `n` bytes is
+/// padded with zero bytes, and only the first `n + 1` characters are written.
+pub(crate) fn encode_base85(data: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in data.chunks(4) {
+        if chunk == [0, 0, 0, 0] {
+            out.push('z');
+            continue;
+        }
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        let mut chars = [0u8; 5];
+        for c in chars.iter_mut().rev() {
+            *c = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+        out.push_str(&String::from_utf8_lossy(&chars[..chunk.len() + 1]));
+    }
+    out
+}
+
+/// Decode Adobe Ascii85 data, without the `<~` and `~>` delimiters
+///
+/// Whitespace is ignored, and `z` is read as 4 zero bytes, but only between groups.
+pub(crate) fn decode_base85(s: &str) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut group: Vec<u8> = Vec::with_capacity(5);
+    for c in s.chars().filter(|c| !c.is_whitespace()) {
+        match c {
+            'z' if group.is_empty() => out.extend_from_slice(&[0; 4]),
+            'z' => return Err(anyhow!("'z' is only allowed between groups in base85")),
+            '!'..='u' => group.push(c as u8 - b'!'),
+            _ => return Err(anyhow!("'{c}' is not a valid base85 character")),
+        }
+        if group.len() == 5 {
+            out.extend_from_slice(&decode_base85_group(&group)?);
+            group.clear();
+        }
+    }
+    match group.len() {
+        0 => (),
+        1 => return Err(anyhow!("a base85 group needs at least 2 characters")),
+        n => {
+            // pad with the highest digit, so that the truncated bytes are right
+            group.resize(5, b'u' - b'!');
+            out.extend_from_slice(&decode_base85_group(&group)?[..n - 1]);
+        }
+    }
+    Ok(out)
+}
+
+/// Decode one group of 5 base85 digits (already without the `!` offset) into 4 bytes
+fn decode_base85_group(group: &[u8]) -> anyhow::Result<[u8; 4]> {
+    let value = group
+        .iter()
+        .fold(0u64, |acc, digit| acc * 85 + *digit as u64);
+    match u32::try_from(value) {
+        Ok(v) => Ok(v.to_be_bytes()),
+        Err(_) => Err(anyhow!("base85 group is too large: {value}")),
+    }
+}
+
+/// Encode data as RFC 4648 base16, two uppercase digits for every byte
+pub(crate) fn encode_base16(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Decode RFC 4648 base16, lowercase digits are accepted too
+pub(crate) fn decode_base16(s: &str) -> anyhow::Result<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return Err(anyhow!("base16 needs two digits for every byte"));
+    }
+    if let Some(digit) = s.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(NumfError::InvalidDigit { digit, radix: 16 }.into());
+    }
+    // unwrap is fine, we checked that these are pairs of hex digits
+    Ok((0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect())
+}
+
+/// Encode data with URL percent-encoding, every byte is encoded
+pub(crate) fn encode_percent(data: &[u8]) -> String {
+    data.iter().map(|b| format!("%{b:02X}")).collect()
+}
+
+/// Decode URL percent-encoding, where every byte must be encoded
+pub(crate) fn decode_percent(s: &str) -> anyhow::Result<Vec<u8>> {
+    if s.is_empty() {
+        return Err(anyhow!("percent-encoding can not be empty"));
+    }
+    let Some(encoded) = s.strip_prefix('%') else {
+        return Err(anyhow!("percent-encoding must start with '%'"));
+    };
+    encoded
+        .split('%')
+        .map(|byte| {
+            if byte.len() != 2 {
+                return Err(anyhow!("'%{byte}' is not a percent-encoded byte"));
             }
+            decode_base16(byte).map(|b| b[0])
+        })
+        .collect()
+}
+
+/// The units of [Format::Duration] and how many seconds they are, largest first
+pub(crate) const DURATION_UNITS: [(char, NumberType); 5] = [
+    ('w', 7 * 24 * 60 * 60),
+    ('d', 24 * 60 * 60),
+    ('h', 60 * 60),
+    ('m', 60),
+    ('s', 1),
+];
+
+/// Write an amount of seconds as a duration, like `1h2m3s` or `01:02:03` for the clock style
+pub(crate) fn format_duration(seconds: NumberType, clock: bool) -> String {
+    if clock {
+        return format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+    }
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+    let mut out = String::new();
+    let mut rest = seconds;
+    for (unit, length) in DURATION_UNITS {
+        if rest >= length {
+            out.push_str(&format!("{}{unit}", rest / length));
+            rest %= length;
         }
-    } else if data_as_text.starts_with(&Format::Base32.prefix_str()) {
-        let s = match data_as_text.strip_prefix(&Format::Base32.prefix_str()) {
-            Some(sr) => sr,
-            None => &data_as_text,
-        };
-        match fast32::base32::RFC4648.decode_str(s) {
-            Ok(r) => Ok(join::array_to_unsigned::<T>(&r)?),
-            Err(e) => {
-                let e = format!("{e}");
-                Err(anyhow!(e))
+    }
+    out
+}
+
+/// Parse a duration like `1h2m3s` or `01:02:03` into the amount of seconds
+///
+/// The units may be in any order and may repeat, they are added up. The clock style needs hours,
+/// minutes and seconds, the minutes and seconds must be less than 60.
+///
+/// The parsers only use this after the prefix `0t` (or with [Format::Duration] as the input
+/// format), so that input like `5s` is still raw data.
+pub(crate) fn parse_duration(s: &str) -> anyhow::Result<NumberType> {
+    let overflow = || anyhow!("duration is too long: {s}");
+    if s.contains(':') {
+        let parts: Vec<&str> = s.split(':').collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.is_empty()) {
+            return Err(anyhow!("a duration needs to be hours:minutes:seconds: {s}"));
+        }
+        let mut total: NumberType = 0;
+        for (i, part) in parts.into_iter().enumerate() {
+            let value: NumberType = part
+                .parse()
+                .map_err(|_| anyhow!("not a valid duration: {s}"))?;
+            // only the hours may be as many as they want
+            if i > 0 && value >= 60 {
+                return Err(anyhow!("minutes and seconds must be less than 60: {s}"));
             }
+            total = total
+                .checked_mul(60)
+                .and_then(|t| t.checked_add(value))
+                .ok_or_else(overflow)?;
         }
-    } else {
-        // what could go wrong with interpreting everything else as raw number input
-        let s: Vec<u8> = if data.len() > 2 && data[0] == 0x00 {
-            data.iter().skip(1).map(ToOwned::to_owned).collect()
-        } else {
-            data.as_ref().to_vec()
+        return Ok(total);
+    }
+    if s.is_empty() {
+        return Err(anyhow!("a duration can not be empty"));
+    }
+    let mut total: NumberType = 0;
+    let mut digits = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let Some((_, length)) = DURATION_UNITS.iter().find(|(unit, _)| *unit == c) else {
+            return Err(anyhow!("'{c}' is not a unit of a duration"));
         };
-        Ok(join::array_to_unsigned(&s)?)
+        if digits.is_empty() {
+            return Err(anyhow!("the unit '{c}' needs a number in front of it"));
+        }
+        let value: NumberType = digits.parse().map_err(|_| overflow())?;
+        total = value
+            .checked_mul(*length)
+            .and_then(|v| total.checked_add(v))
+            .ok_or_else(overflow)?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(anyhow!("the number {digits} in a duration needs a unit"));
     }
+    Ok(total)
 }
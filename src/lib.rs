@@ -9,9 +9,13 @@
 //! Note that this crate is primarily intended to be used as a executable.
 //!
 //! Highlights:
+//! * [format::convert]
 //! * [format::numf_parser]
 //! * [format::numf_parser_str]
 //! * [format::Format::format]
 //! * [format::Format::format_str]
 
+#[cfg(feature = "csv")]
+pub mod csv_io;
+pub mod explain;
 pub mod format;
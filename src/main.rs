@@ -1,96 +1,966 @@
+use std::fmt::Display;
 use std::io::{IsTerminal, Read, Write};
 use std::process::exit;
 
 use clap::{CommandFactory, Parser};
-use numf::format::numf_parser_str;
 
+#[cfg(feature = "csv")]
+mod csv_io;
+mod explain;
 mod format;
-use crate::format::{numf_parser, Format};
+mod repl;
+use crate::format::Format;
 use format::*;
-use libpt::log::{debug, error};
+use libpt::log::{debug, error, info, warn};
+use unicode_segmentation::UnicodeSegmentation;
 
 fn main() -> anyhow::Result<()> {
     // try to read from stdin first, appending the numbers we read to the FormatOptions
     let mut options = FormatOptions::parse();
-    let _logger = libpt::log::Logger::builder()
-        .set_level(options.verbosity.level())
-        .display_time(false)
-        .build()
-        .map_err(|e| {
-            error!("could not initialize logger: {e}");
-        });
+    // no logging at all in porcelain mode, errors are printed by fail()
+    let _logger = if options.porcelain() {
+        None
+    } else {
+        Some(
+            libpt::log::Logger::builder()
+                .set_level(options.verbosity.level())
+                .display_time(false)
+                .build()
+                .map_err(|e| {
+                    error!("could not initialize logger: {e}");
+                }),
+        )
+    };
     debug!("logger active");
+    debug!("options: {options}");
+
+    // only list the formats, no numbers are needed for that
+    if options.format_list() {
+        print_format_list(&options);
+        return Ok(());
+    }
+
+    // the prompt replaces everything else, numbers are read from it
+    if options.interactive() {
+        if !std::io::stdin().is_terminal() {
+            fail(
+                &options,
+                1,
+                "--interactive needs the stdin to be a terminal",
+            );
+        }
+        repl::run(&mut options, std::io::stdin().lock(), std::io::stdout())?;
+        return Ok(());
+    }
+
+    // the detected formats of the inputs, for --stats
+    let mut detected: Vec<Format> = Vec::new();
+
+    // how many inputs could not be parsed, for --keep-going
+    let mut failed: usize = 0;
+
+    // parse the numbers from the arguments, now that we know how they should be parsed
+    for s in options.inputs() {
+        let what = match options.text_in() {
+            Some(_) => "read text",
+            None => "parse number",
+        };
+        match options.parse_input(&s) {
+            Ok(numbers) => {
+                // text is not in any format
+                let format = match options.text_in() {
+                    Some(_) => Format::Raw,
+                    None => {
+                        warn_ambiguous(&options, s.as_bytes());
+                        options.detect_format(s.as_bytes())
+                    }
+                };
+                for n in numbers {
+                    options.push_number(n);
+                    detected.push(format);
+                }
+            }
+            Err(e) if options.keep_going() => {
+                report(&options, format!("could not {what} {s:?}: {e:#}"));
+                failed += 1;
+            }
+            Err(e) => fail(&options, 2, format!("could not {what} {s:?}: {e:#}")),
+        }
+    }
+
+    // how many numbers came from the stdin, for --report
+    let mut from_stdin: usize = 0;
+
+    // the table from --csv-in, with the index of the number of every row
+    #[cfg(feature = "csv")]
+    let mut csv_table: Option<(csv_io::Table, Vec<Option<usize>>)> = None;
 
     let mut stdin_nums = Vec::new();
     let stdin = std::io::stdin();
     // only accept numbers from stdin if the stdin is not an interactive terminal
     if !stdin.is_terminal() {
         match stdin.lock().read_to_end(&mut stdin_nums) {
-            Ok(_) => {
-                let whole: String = match String::from_utf8(stdin_nums.clone()) {
-                    Ok(r) => r,
-                    Err(_) => {
-                        let number = match numf_parser(&stdin_nums) {
-                            Ok(n) => n,
-                            Err(e) => {
-                                eprintln!("{}", FormatOptions::command().render_usage());
-                                error!("could raw inputs from stdin as numbers: {e:#?}");
-                                exit(2);
-                            }
-                        };
-                        options.push_number(number);
-                        String::new()
+            // nothing at all is no number, not zero
+            Ok(_) if options.bytes_in() && !stdin_nums.is_empty() => {
+                let zeros = stdin_nums.iter().take_while(|b| **b == 0).count();
+                let bytes = &stdin_nums[zeros..];
+                let max = NumberType::BITS as usize / 8;
+                if bytes.len() > max {
+                    let len = bytes.len();
+                    let too_many = format!("the stdin has {len} bytes without the leading zeros");
+                    fail(
+                        &options,
+                        2,
+                        format!("{too_many}, but at most {max} fit into a number"),
+                    );
+                }
+                let number = if bytes.is_empty() {
+                    0
+                } else {
+                    match numf_parser_raw::<NumberType>(bytes, false) {
+                        Ok(n) => n,
+                        Err(e) => fail(&options, 2, format!("could not read the stdin: {e:#}")),
+                    }
+                };
+                options.push_number(number);
+                detected.push(Format::Raw);
+                from_stdin += 1;
+            }
+            Ok(_) if options.bytes_in() => (),
+            Ok(_) if options.text_in().is_some() => {
+                // unwrap is fine, checked by the guard
+                let mode = options.text_in().unwrap();
+                // the line break that ends the input is not part of the text
+                let text = stdin_nums
+                    .strip_suffix(b"\r\n")
+                    .or_else(|| stdin_nums.strip_suffix(b"\n"))
+                    .unwrap_or(&stdin_nums);
+                match text_to_numbers(text, mode) {
+                    Ok(numbers) => {
+                        for n in numbers {
+                            options.push_number(n);
+                            detected.push(Format::Raw);
+                            from_stdin += 1;
+                        }
                     }
+                    Err(e) => fail(&options, 2, format!("could not read the stdin: {e:#}")),
+                }
+            }
+            #[cfg(feature = "csv")]
+            Ok(_) if options.csv_in().is_some() => {
+                // unwrap is fine, checked by the guard
+                let column = options.csv_in().unwrap();
+                let table = match csv_io::Table::read(&stdin_nums, column) {
+                    Ok(table) => table,
+                    Err(e) => fail(&options, 2, format!("could not read CSV from stdin: {e:#}")),
                 };
-                let split = whole.split_whitespace();
-                for s in split {
-                    let number = match numf_parser_str(s) {
+                let mut rows = Vec::new();
+                for cell in table.cells() {
+                    let cell = cell.trim();
+                    // empty and missing cells have no number, the row is kept as it is
+                    if cell.is_empty() {
+                        rows.push(None);
+                        continue;
+                    }
+                    match options.parse_number(cell) {
+                        Ok(n) => {
+                            rows.push(Some(options.numbers().len()));
+                            options.push_number(n);
+                            detected.push(options.detect_format(cell.as_bytes()));
+                            from_stdin += 1;
+                        }
+                        Err(e) if options.keep_going() => {
+                            report(
+                                &options,
+                                format!("could not parse number {cell:?} from CSV: {e:#}"),
+                            );
+                            rows.push(None);
+                            failed += 1;
+                        }
+                        Err(e) => fail(
+                            &options,
+                            2,
+                            format!("could not parse number {cell:?} from CSV: {e:#}"),
+                        ),
+                    }
+                }
+                csv_table = Some((table, rows));
+            }
+            Ok(_) => {
+                let parts = match options.delimiter_in() {
+                    Some(delimiter) => split_input_by(&stdin_nums, delimiter),
+                    None => split_input(&stdin_nums),
+                };
+                for part in parts {
+                    let number = match options.parse_bytes(part) {
                         Ok(n) => n,
-                        Err(e) => {
-                            eprintln!("{}", FormatOptions::command().render_usage());
-                            error!("could not parse number from stdin: {e:#?}");
-                            exit(2);
+                        Err(e) if options.keep_going() => {
+                            report(
+                                &options,
+                                format!(
+                                    "could not parse number {:?} from stdin: {e:#}",
+                                    String::from_utf8_lossy(part)
+                                ),
+                            );
+                            failed += 1;
+                            continue;
                         }
+                        Err(e) => fail(
+                            &options,
+                            2,
+                            format!("could not parse number from stdin: {e:#}"),
+                        ),
                     };
-                    options.push_number(number)
+                    warn_ambiguous(&options, part);
+                    options.push_number(number);
+                    detected.push(options.detect_format(part));
+                    from_stdin += 1;
                 }
             }
-            Err(e) => {
-                eprintln!("{}", FormatOptions::command().render_usage());
-                error!("could not read from stdin: {e:#?}");
-                exit(2);
-            }
+            Err(e) => fail(&options, 2, format!("could not read from stdin: {e:#}")),
         };
     }
 
+    // with --keep-going, only give up here if nothing could be parsed
+    if failed > 0 && options.numbers().is_empty() {
+        fail(&options, 2, "none of the input numbers could be parsed");
+    }
+
     // add random numbers to the number list, according to how many are requested
-    if options.rand() > 0 {
-        use rand::prelude::*;
-        let mut rand = rand::rngs::OsRng;
-        for _i in 0..options.rand() {
-            options.push_number(rand.gen_range(0..options.rand_max()));
-        }
+    let mut rng = options.rng();
+    let random = options.generate_random_with(&mut rng);
+    let from_random = random.len();
+    for num in random {
+        options.push_number(num);
+    }
+
+    // repeat the whole list of numbers if requested
+    if let Some(count) = options.count() {
+        let repeated = options.numbers().repeat(count);
+        options.set_numbers(repeated);
     }
 
-    // exit with error if no numbers are to be formatted
+    // shuffle the whole list of numbers if requested, with the same generator as --rand
+    if options.shuffle() {
+        options.shuffle_numbers_with(&mut rng);
+    }
+
+    // exit with error if no numbers are to be formatted, unless that is fine
     if options.numbers().is_empty() {
-        eprintln!("{}", FormatOptions::command().render_usage());
-        error!("no numbers have been provided");
-        exit(1);
+        if options.allow_empty() {
+            debug!("no numbers, nothing to do");
+            return Ok(());
+        }
+        fail(&options, 1, "no numbers have been provided");
+    }
+
+    // compare the two numbers by their XOR, which has the bits set that differ
+    let diff = options.diff().then(|| match options.numbers() {
+        [a, b] => a ^ b,
+        numbers => fail(
+            &options,
+            1,
+            format!(
+                "--diff needs exactly two numbers, but got {}",
+                numbers.len()
+            ),
+        ),
+    });
+    if let Some(xor) = diff {
+        options.set_numbers(vec![xor]);
     }
 
-    let mut out: Vec<Vec<u8>> = Vec::new();
+    // keep only the smallest or largest number if requested, the first one if there are more
+    if options.min() || options.max() {
+        let (kind, extremum) = if options.min() {
+            ("smallest", options.numbers().iter().min())
+        } else {
+            ("largest", options.numbers().iter().max())
+        };
+        // unwrap is fine, there are numbers
+        let extremum = *extremum.unwrap();
+        // unwrap is fine, the extremum is one of the numbers
+        let index = options
+            .numbers()
+            .iter()
+            .position(|num| *num == extremum)
+            .unwrap();
+        info!("the {kind} number is {extremum}, at index {index}");
+        options.set_numbers(vec![extremum]);
+    }
 
+    // combine all numbers into one if requested
+    if let Some(reduce) = options.reduce() {
+        if let Some(num) = reduce.reduce(options.numbers()) {
+            options.set_numbers(vec![num]);
+        }
+    }
+
+    // transform the numbers before they are formatted, like --bit-reverse
+    let mut transformed = Vec::with_capacity(options.numbers().len());
     for num in options.numbers() {
-        out.push(options.format().format(*num, &options));
+        match options.transform(*num) {
+            Ok(n) => transformed.push(n),
+            Err(e) => fail(&options, 1, format!("could not transform {num}: {e:#}")),
+        }
+    }
+    options.set_numbers(transformed);
+
+    // format the amount of set bits instead of the numbers if requested
+    if options.popcount() {
+        let counts = options.numbers().iter().map(|num| popcount(*num)).collect();
+        options.set_numbers(counts);
+    }
+
+    // the numbers must fit into the width to be shown little-endian
+    if let Some(width) = options.le_display() {
+        for num in options.numbers() {
+            if let Err(e) = byte_swap(*num, width) {
+                fail(
+                    &options,
+                    1,
+                    format!("cannot show {num} little-endian: {e:#}"),
+                );
+            }
+        }
+    }
+
+    // the numbers must fit into the C type
+    if let Some(ctype) = options.ctype() {
+        for num in options.numbers() {
+            if let Err(e) = ctype.check(*num) {
+                fail(&options, 1, format!("cannot write {num} as {ctype}: {e}"));
+            }
+        }
     }
-    for o in out {
-        let mut stdout = std::io::stdout();
-        stdout.write_all(&o)?;
-        if options.format() != Format::Raw {
-            stdout.write_all(b"\n")?;
+
+    // the numbers must be possible as literals, the language gives the prefix then
+    if let Some(lang) = options.lang() {
+        for num in options.numbers() {
+            for format in options.formats() {
+                if let Err(e) = lang_literal(&options, lang, format, *num) {
+                    fail(&options, 1, format!("cannot write {num} as {lang}: {e:#}"));
+                }
+            }
+        }
+        options.set_prefix(false);
+    }
+
+    // write to the output file if one was given, the stdout otherwise
+    let mut out: Box<dyn Write> = match options.output_file() {
+        Some(path) => match std::fs::File::create(path) {
+            Ok(file) => Box::new(std::io::BufWriter::new(file)),
+            Err(e) => fail(
+                &options,
+                2,
+                format!("could not open output file {}: {e}", path.display()),
+            ),
+        },
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    // explain the formatting instead of just doing it, if requested
+    if options.explain() {
+        for (i, num) in options.numbers().iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            let lines = match options.to_base() {
+                Some(radix) => explain::explain_radix(*num, radix),
+                None => explain::explain(*num, options.format()),
+            };
+            for line in lines {
+                writeln!(out, "{line}")?;
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // only report the digit counts if requested
+    if options.digits() {
+        let alphabet_radix = options.alphabet().map(|a| a.graphemes(true).count() as u32);
+        let radix = match alphabet_radix
+            .or(options.to_base())
+            .or(options.format().radix())
+        {
+            Some(radix) => radix,
+            None => fail(
+                &options,
+                1,
+                format!(
+                    "cannot count digits for {}, it has no base",
+                    options.format()
+                ),
+            ),
+        };
+        for num in options.numbers() {
+            writeln!(out, "{}", digit_count(*num, radix))?;
         }
-        stdout.flush()?;
+        out.flush()?;
+        return Ok(());
+    }
+
+    // count the leading and trailing zeros if requested
+    if options.clz().is_some() || options.ctz().is_some() {
+        for num in options.numbers() {
+            let mut counts = Vec::new();
+            if let Some(width) = options.clz() {
+                counts.push(clz(*num, width));
+            }
+            if let Some(width) = options.ctz() {
+                counts.push(ctz(*num, width));
+            }
+            match counts.into_iter().collect::<anyhow::Result<Vec<u32>>>() {
+                Ok(counts) => writeln!(
+                    out,
+                    "{}",
+                    counts
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )?,
+                Err(e) => fail(&options, 1, format!("{e:#}")),
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // show the numbers as signed decimal numbers if requested
+    if let Some(width) = options.as_signed() {
+        for num in options.numbers() {
+            match as_signed(*num, width) {
+                Ok(signed) => writeln!(out, "{signed}")?,
+                Err(e) => fail(&options, 1, format!("{e:#}")),
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // show the numbers as dotted groups if requested
+    if let Some(group) = options.dotted() {
+        let min_bytes = options
+            .width()
+            .map_or(0, |width| (width as usize).div_ceil(8));
+        for num in options.numbers() {
+            writeln!(out, "{}", format_dotted(*num, group, min_bytes))?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // show the numbers as floats if requested
+    if let Some(float) = options.as_float() {
+        for num in options.numbers() {
+            match as_float(*num, float) {
+                Ok(f) => writeln!(out, "{f}")?,
+                Err(e) => fail(&options, 1, format!("{e:#}")),
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // make sure that the output can be parsed again before writing it
+    if options.verify() {
+        for num in options.numbers() {
+            let mut checks = Vec::new();
+            match options.to_base() {
+                _ if options.alphabet().is_some() => {
+                    let alphabet = options.alphabet().unwrap_or_default();
+                    let formatted = format_alphabet(*num, alphabet)?;
+                    let parsed = numf_parser_alphabet(&formatted, alphabet);
+                    checks.push((formatted.into_bytes(), parsed));
+                }
+                Some(radix) => {
+                    let formatted = format_radix(*num, radix)?;
+                    let parsed = numf_parser_radix::<NumberType>(&formatted, radix);
+                    checks.push((formatted.into_bytes(), parsed));
+                }
+                None => {
+                    for format in options.formats() {
+                        if let Err(e) = options.verify_number(format, *num) {
+                            fail(&options, 1, format!("verification failed: {e:#}"));
+                        }
+                    }
+                }
+            };
+            for (formatted, parsed) in checks {
+                let formatted = String::from_utf8_lossy(&formatted);
+                let failed = format!("verification failed: {num} was formatted as {formatted:?}");
+                match parsed {
+                    Ok(parsed) if parsed == *num => debug!("verified {num} as {formatted:?}"),
+                    Ok(parsed) => fail(
+                        &options,
+                        1,
+                        format!("{failed}, but parsed back as {parsed}"),
+                    ),
+                    Err(e) => fail(
+                        &options,
+                        1,
+                        format!("{failed}, but could not be parsed back: {e:#}"),
+                    ),
+                }
+            }
+        }
+    }
+
+    // make sure that nothing is too wide before writing any of the output
+    if options.max_width().is_some() {
+        for num in options.numbers() {
+            match format_custom(&options, *num)? {
+                Some(o) => check_max_width(&options, *num, o.chars().count()),
+                None => {
+                    for format in options.formats() {
+                        let o = format.format(*num, &options);
+                        let mut width = match format {
+                            Format::Raw => o.len(),
+                            _ => String::from_utf8_lossy(&o).chars().count(),
+                        };
+                        if options.prefix() && !options.max_width_with_prefix() {
+                            width -= match format {
+                                Format::Raw => {
+                                    usize::from(!options.no_raw_prefix() && !options.raw_minimal())
+                                }
+                                _ => format.prefix_for(options.prefix_style()).len(),
+                            };
+                        }
+                        check_max_width(&options, *num, width);
+                    }
+                }
+            }
+        }
+    }
+
+    // with --csv-out, the CSV replaces the usual output
+    #[cfg(feature = "csv")]
+    let csv_out = options.csv_out();
+    #[cfg(not(feature = "csv"))]
+    let csv_out = false;
+    #[cfg(feature = "csv")]
+    if csv_out {
+        let written = match &csv_table {
+            Some((table, rows)) => {
+                let numbers: Vec<Option<NumberType>> = rows
+                    .iter()
+                    .map(|row| row.map(|i| options.numbers()[i]))
+                    .collect();
+                table.write(&mut out, &options, &numbers)
+            }
+            None => csv_io::write_numbers(&mut out, &options, options.numbers()),
+        };
+        if let Err(e) = written {
+            fail(&options, 2, format!("could not write CSV: {e:#}"));
+        }
+    }
+    let lines: &[NumberType] = if csv_out { &[] } else { options.numbers() };
+
+    // the widths of the columns for --tabular, they are the same for all rows
+    let limit = table_width_limit(&options);
+    let columns = options.tabular().then(|| tabular_widths(&options, limit));
+    if let Some(widths) = &columns {
+        let header: Vec<String> = options.formats().iter().map(Format::to_string).collect();
+        write_row(&mut out, &options, &header, widths, limit.is_some())?;
+    }
+
+    // with --prefix-once, the numbers after the first one have no prefix, if there is one format
+    let unprefixed = (options.prefix_once() && options.formats().len() == 1).then(|| {
+        let mut unprefixed = options.clone();
+        unprefixed.set_prefix(false);
+        unprefixed
+    });
+
+    for (i, num) in lines.iter().enumerate() {
+        if let Some(widths) = &columns {
+            let row: Vec<String> = options
+                .formats()
+                .iter()
+                .map(|format| format.format_str(*num, &options))
+                .collect();
+            write_row(&mut out, &options, &row, widths, limit.is_some())?;
+            continue;
+        }
+        if options.ndjson() {
+            match format_custom(&options, *num)? {
+                Some(value) => {
+                    let label = match options.to_base() {
+                        Some(radix) if options.alphabet().is_none() => format!("Base{radix}"),
+                        _ => "Alphabet".to_string(),
+                    };
+                    write_ndjson(&mut out, &value, &label)?;
+                }
+                None => {
+                    for format in options.formats() {
+                        let value = match format {
+                            // raw bytes are not valid JSON
+                            Format::Raw => {
+                                fast32::base64::RFC4648.encode(&format.format(*num, &options))
+                            }
+                            _ => format.format_str(*num, &options),
+                        };
+                        write_ndjson(&mut out, &value, &format.to_string())?;
+                    }
+                }
+            }
+            continue;
+        }
+        match format_custom(&options, *num)? {
+            Some(o) => {
+                if !options.tag().is_empty() {
+                    write!(out, "{} ", options.tag())?;
+                }
+                writeln!(out, "{o}")?;
+            }
+            // every format with its name, so that the lines can be told apart
+            None if options.all() => {
+                for (format, formatted) in format_all_formats(*num, &options) {
+                    if !options.tag().is_empty() {
+                        write!(out, "{} ", options.tag())?;
+                    }
+                    writeln!(out, "{format}: {formatted}")?;
+                }
+            }
+            None => {
+                // every requested format for this number, before the next number
+                for format in options.formats() {
+                    // only label text, not raw data
+                    if format != Format::Raw && !options.tag().is_empty() {
+                        write!(out, "{} ", options.tag())?;
+                    }
+                    match options.lang() {
+                        Some(lang) => {
+                            out.write_all(lang_literal(&options, lang, format, *num)?.as_bytes())?
+                        }
+                        None => {
+                            let line_options = match &unprefixed {
+                                Some(unprefixed) if i > 0 => unprefixed,
+                                _ => &options,
+                            };
+                            format.format_to_writer(*num, line_options, &mut out)?
+                        }
+                    }
+                    if format != Format::Raw {
+                        out.write_all(b"\n")?;
+                    }
+                }
+            }
+        }
+    }
+    out.flush()?;
+
+    // list the bits that differ after their XOR
+    if let Some(xor) = diff {
+        match popcount(xor) {
+            0 => writeln!(out, "no bits differ")?,
+            1 => writeln!(out, "1 bit differs: {}", bit_ranges(xor))?,
+            n => writeln!(out, "{n} bits differ: {}", bit_ranges(xor))?,
+        }
+        out.flush()?;
+    }
+
+    // report how many inputs had which format, after the actual output
+    if options.stats() {
+        for format in Format::ALL {
+            let count = detected.iter().filter(|f| **f == format).count();
+            if count > 0 {
+                eprintln!("{format}: {count}");
+            }
+        }
+    }
+
+    // report how many numbers were formatted, after the actual output
+    if options.report() {
+        let total = options.numbers().len();
+        let from_args = detected.len() - from_stdin;
+        eprintln!(
+            "# {total} numbers formatted \
+             ({from_args} from arguments, {from_stdin} from stdin, {from_random} random)"
+        );
+    }
+
+    // the output is done, but some of the inputs were skipped
+    if failed > 0 {
+        report(
+            &options,
+            format!("{failed} of the input numbers could not be parsed and were skipped"),
+        );
+        exit(3);
     }
     Ok(())
 }
+
+/// Write `num` as a literal of `lang`, see [Lang] for what that looks like
+///
+/// The digits are formatted with `options`, which must not add a prefix, since the prefix of the
+/// language is added here.
+fn lang_literal(
+    options: &FormatOptions,
+    lang: Lang,
+    format: Format,
+    num: NumberType,
+) -> anyhow::Result<String> {
+    let prefix = match (format, lang) {
+        (Format::Dec, _) => "",
+        (Format::Hex, _) => "0x",
+        (Format::Bin, _) => "0b",
+        (Format::Octal, Lang::C) => "0",
+        (Format::Octal, _) => "0o",
+        _ => anyhow::bail!("only dec, hex, bin and octal can be written as literals, not {format}"),
+    };
+    let bits = options.ctype().map(|ctype| ctype.bits()).unwrap_or(
+        match NumberType::BITS - num.leading_zeros() {
+            0..=32 => 32,
+            33..=64 => 64,
+            _ => 128,
+        },
+    );
+    let suffix = match lang {
+        Lang::Rust => format!("u{bits}"),
+        Lang::C if bits <= 32 => "U".to_string(),
+        Lang::C if bits <= 64 => "ULL".to_string(),
+        Lang::C => anyhow::bail!("C has no literals with more than 64 bits"),
+        Lang::Python => String::new(),
+    };
+    let mut digits = format.format_str(num, options);
+    if lang == Lang::Rust {
+        digits = match format {
+            Format::Dec => group_digits(&digits, 3, '_'),
+            Format::Hex | Format::Bin => group_digits(&digits, 4, '_'),
+            _ => digits,
+        };
+    }
+    Ok(format!("{prefix}{digits}{suffix}"))
+}
+
+/// columns of `--tabular` are not made wider than this, so that base64 does not stretch them
+const TABULAR_MAX_WIDTH: usize = 24;
+
+/// columns are not made narrower than this to fit the table into the terminal
+const TABULAR_MIN_WIDTH: usize = 4;
+
+/// how wide the table of `--tabular` may be, from `--term-width` or the terminal
+///
+/// There is no limit if the output does not go to a terminal, unless `--term-width` is given.
+fn table_width_limit(options: &FormatOptions) -> Option<usize> {
+    if options.term_width().is_some() {
+        return options.term_width();
+    }
+    if options.output_file().is_some() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| width as usize)
+}
+
+/// the width of every column for `--tabular`, the widest value or name of that format
+///
+/// With a `limit`, the widest columns are made narrower until the table fits into it.
+fn tabular_widths(options: &FormatOptions, limit: Option<usize>) -> Vec<usize> {
+    let mut widths: Vec<usize> = options
+        .formats()
+        .iter()
+        .map(|format| {
+            options
+                .numbers()
+                .iter()
+                .map(|num| format.format_str(*num, options).chars().count())
+                .chain([format.to_string().len()])
+                .max()
+                .unwrap_or(0)
+                .min(TABULAR_MAX_WIDTH)
+        })
+        .collect();
+    let Some(limit) = limit else {
+        return widths;
+    };
+    let tag = match options.tag() {
+        "" => 0,
+        tag => tag.chars().count() + 1,
+    };
+    while tag + widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1) > limit {
+        // unwrap is fine, there is always at least one format
+        let widest = widths.iter_mut().max().unwrap();
+        if *widest <= TABULAR_MIN_WIDTH {
+            break;
+        }
+        *widest -= 1;
+    }
+    widths
+}
+
+/// write a row of the table for `--tabular`, with the cells padded to the widths of the columns
+///
+/// With `cut`, cells that are wider than their column are cut and end with `…`.
+fn write_row(
+    out: &mut impl Write,
+    options: &FormatOptions,
+    cells: &[String],
+    widths: &[usize],
+    cut: bool,
+) -> std::io::Result<()> {
+    let mut line = String::new();
+    for (cell, width) in cells.iter().zip(widths) {
+        if cut && cell.chars().count() > *width {
+            let cell: String = cell.chars().take(width - 1).collect();
+            line.push_str(&format!("{cell}…  "));
+        } else {
+            line.push_str(&format!("{cell:<width$}  "));
+        }
+    }
+    if options.tag().is_empty() {
+        writeln!(out, "{}", line.trim_end())
+    } else {
+        writeln!(out, "{} {}", options.tag(), line.trim_end())
+    }
+}
+
+/// write a line with a JSON object for `--ndjson`
+fn write_ndjson(out: &mut impl Write, value: &str, format: &str) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{{\"value\":\"{}\",\"format\":\"{}\"}}",
+        json_escape(value),
+        json_escape(format)
+    )
+}
+
+/// escape a string for a JSON string literal, base85 has `"` and `\` for example
+fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// print a table of all formats, the flags that select them and their prefixes
+fn print_format_list(options: &FormatOptions) {
+    let command = FormatOptions::command();
+    let rows: Vec<[String; 3]> = Format::ALL
+        .iter()
+        .map(|format| {
+            let arg = command
+                .get_arguments()
+                .find(|arg| arg.get_id() == FormatOptions::format_arg_id(*format));
+            let mut flags = Vec::new();
+            if let Some(short) = arg.and_then(|arg| arg.get_short()) {
+                flags.push(format!("-{short}"));
+            }
+            if let Some(long) = arg.and_then(|arg| arg.get_long()) {
+                flags.push(format!("--{long}"));
+            }
+            let prefix = match format {
+                // the prefix of raw is a byte, not text
+                Format::Raw => format!("byte 0x{:02X}", options.raw_prefix()),
+                _ => format.prefix_str(),
+            };
+            [format.to_string(), flags.join(", "), prefix]
+        })
+        .collect();
+    let header = ["format", "flags", "prefix"].map(String::from);
+    let widths: Vec<usize> = (0..2)
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[i].len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for [format, flags, prefix] in std::iter::once(&header).chain(&rows) {
+        println!(
+            "{format:w0$}  {flags:w1$}  {prefix}",
+            w0 = widths[0],
+            w1 = widths[1]
+        );
+    }
+}
+
+/// format a number with `--alphabet` or `--to-base`, if one of them is set
+fn format_custom(options: &FormatOptions, num: NumberType) -> anyhow::Result<Option<String>> {
+    if let Some(alphabet) = options.alphabet() {
+        return format_alphabet(num, alphabet).map(Some);
+    }
+    options
+        .to_base()
+        .map(|radix| format_radix(num, radix))
+        .transpose()
+}
+
+/// exit with an error if the output for `num` is wider than `--max-width`
+fn check_max_width(options: &FormatOptions, num: NumberType, width: usize) {
+    if let Some(max) = options.max_width() {
+        if width > max {
+            fail(
+                options,
+                1,
+                format!("{num} is {width} wide when formatted, but the maximum is {max}"),
+            );
+        }
+    }
+}
+
+/// warn about an input number that could be meant in another base, with `--warn-ambiguous`
+///
+/// With `--porcelain`, the warning is written to stderr as is, otherwise it is logged.
+fn warn_ambiguous(options: &FormatOptions, data: &[u8]) {
+    if !options.warn_ambiguous() {
+        return;
+    }
+    let formats = options.ambiguous_formats(data);
+    if formats.is_empty() {
+        return;
+    }
+    let text = String::from_utf8_lossy(data);
+    let readings = formats
+        .iter()
+        .map(|format| {
+            let prefix = format.prefix_str();
+            // unwrap is fine, only formats with a base are ambiguous
+            let num = numf_parser_radix::<NumberType>(&text, format.radix().unwrap()).unwrap();
+            format!("{prefix}{text} = {num}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let msg = format!(
+        "{text:?} is read as decimal, but could be any of {readings}, add a prefix to be sure"
+    );
+    if options.porcelain() {
+        eprintln!("{msg}");
+    } else {
+        warn!("{msg}");
+    }
+}
+
+/// print an error, but keep going
+///
+/// With `--porcelain`, the message is written to stderr as is, otherwise it is logged.
+fn report(options: &FormatOptions, msg: impl Display) {
+    if options.porcelain() {
+        eprintln!("{msg}");
+    } else {
+        error!("{msg}");
+    }
+}
+
+/// print an error and exit with `code`
+///
+/// Normally, the usage is printed and the error is logged. With `--porcelain`, only a single line
+/// with the message is written to stderr.
+fn fail(options: &FormatOptions, code: i32, msg: impl Display) -> ! {
+    if options.porcelain() {
+        eprintln!("{msg}");
+    } else {
+        eprintln!("{}", FormatOptions::command().render_usage());
+        error!("{msg}");
+    }
+    exit(code)
+}
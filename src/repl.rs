@@ -0,0 +1,127 @@
+//! The interactive prompt of the executable, see `--interactive`
+//!
+//! Every line that is read is parsed as a number and formatted with the current formats, until
+//! the input ends or `:q` is entered. Lines starting with `:` are commands.
+
+use std::io::{BufRead, Write};
+
+use crate::format::{Format, FormatOptions};
+
+const PROMPT: &str = "numf> ";
+
+const HELP: &str = "enter a number to format it
+commands:
+  :<format>   switch to a format, like :hex, :bin or :base64
+  :formats    show the current formats
+  :help       show this help
+  :q          quit";
+
+/// Run the prompt, reading lines from `input` and writing the prompt and results to `out`
+///
+/// Changing the format with a command changes it in `options`. Errors for single lines are
+/// written to `out` as well, they do not end the prompt.
+pub fn run(
+    options: &mut FormatOptions,
+    mut input: impl BufRead,
+    mut out: impl Write,
+) -> std::io::Result<()> {
+    let mut line = String::new();
+    loop {
+        write!(out, "{PROMPT}")?;
+        out.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            // EOF, end the prompt line
+            writeln!(out)?;
+            return Ok(());
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(command) = line.strip_prefix(':') {
+            match command {
+                "q" | "quit" | "exit" => return Ok(()),
+                "help" | "h" | "?" => writeln!(out, "{HELP}")?,
+                "formats" => writeln!(out, "{}", formats_list(options))?,
+                name => match name.parse::<Format>() {
+                    Ok(format) => {
+                        options.set_format(format);
+                        writeln!(out, "format: {format}")?;
+                    }
+                    Err(e) => writeln!(out, "error: {e}, see :help")?,
+                },
+            }
+            continue;
+        }
+
+        match options.parse_number(line) {
+            Ok(num) => {
+                for format in options.formats() {
+                    writeln!(out, "{}", format.format_str(num, options))?;
+                }
+            }
+            Err(e) => writeln!(out, "error: {e:#}")?,
+        }
+    }
+}
+
+fn formats_list(options: &FormatOptions) -> String {
+    options
+        .formats()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// run the prompt with `input` and return what was written
+    fn session(options: &mut FormatOptions, input: &str) -> String {
+        let mut out = Vec::new();
+        run(options, Cursor::new(input), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn switch_format() {
+        let mut options = FormatOptions::default();
+        let out = session(&mut options, "255\n:hex\n255\n:base64\n255\n");
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[1], "numf> format: Hex");
+        assert_eq!(lines[2], "numf> FF");
+        assert_eq!(lines[3], "numf> format: Base64");
+        assert_eq!(lines[4], "numf> /w==");
+        assert_eq!(options.formats(), [Format::Base64]);
+    }
+
+    #[test]
+    fn quit_and_eof() {
+        let mut options = FormatOptions::default();
+        options.set_format(Format::Hex);
+        // nothing after :q is read
+        assert_eq!(session(&mut options, "16\n:q\n17\n"), "numf> 10\nnumf> ");
+        // the prompt line is ended at EOF
+        assert_eq!(session(&mut options, "16\n"), "numf> 10\nnumf> \n");
+        assert_eq!(session(&mut options, ""), "numf> \n");
+    }
+
+    #[test]
+    fn error_continues() {
+        let mut options = FormatOptions::default();
+        options.set_format(Format::Hex);
+        let out = session(&mut options, "0xZZ\n16\n:nope\n17\n");
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].starts_with("numf> error: "));
+        assert_eq!(lines[1], "numf> 10");
+        assert!(lines[2].starts_with("numf> error: "));
+        assert!(lines[2].ends_with(", see :help"));
+        assert_eq!(lines[3], "numf> 11");
+    }
+}
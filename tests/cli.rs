@@ -0,0 +1,524 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use numf::format::Format;
+
+/// run the numf executable with `args`, with `stdin` as its stdin
+///
+/// `--porcelain` is always given, so that nothing is logged.
+fn numf(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_numf"))
+        .arg("--porcelain")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// the lines of the stdout of a successful run
+fn lines(output: &Output) -> Vec<String> {
+    assert!(
+        output.status.success(),
+        "numf failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone())
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn wrapped_base64_stdin() {
+    let wrapped = numf(&["--base64", "-p", "--wrap", "4", "0x13371337133713"], b"");
+    assert_eq!(lines(&wrapped), vec!["0sEzcT", "NxM3", "Ew=="]);
+    // every line is a number of its own on the stdin
+    let read = numf(&["-x"], &wrapped.stdout);
+    assert_eq!(lines(&read).len(), 3);
+    assert_ne!(lines(&read)[0], "13371337133713");
+
+    // without wrapping, the number can be read again
+    let plain = numf(&["--base64", "-p", "0x13371337133713"], b"");
+    assert_eq!(lines(&numf(&["-x"], &plain.stdout)), vec!["13371337133713"]);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_empty_cells() {
+    let table = b"name,value\nfoo,16\nbar,\nbaz\n\"qux\",  \nquux,0x20\n";
+    let output = numf(&["-x", "--csv-in", "2", "--csv-out"], table);
+    assert_eq!(
+        lines(&output),
+        vec!["name,value", "foo,10", "bar,", "baz", "qux,  ", "quux,20"]
+    );
+    let output = numf(&["-x", "--csv-in", "2"], table);
+    assert_eq!(lines(&output), vec!["10", "20"]);
+}
+
+#[test]
+fn text_in_stdin() {
+    assert_eq!(
+        lines(&numf(&["--text-in", "-d"], b"AB\n")),
+        vec!["65", "66"]
+    );
+    assert_eq!(
+        lines(&numf(&["--text-in", "-d"], b"AB\r\n")),
+        vec!["65", "66"]
+    );
+    assert_eq!(
+        lines(&numf(&["--text-in=bytes", "-d"], b"AB")),
+        vec!["65", "66"]
+    );
+    // only one line break is removed
+    assert_eq!(
+        lines(&numf(&["--text-in", "-d"], b"A\n\n")),
+        vec!["65", "10"]
+    );
+    assert_eq!(
+        lines(&numf(&["--text-in=combined", "-x", "-p"], b"AB\n")),
+        vec!["0x4142"]
+    );
+    // a character that is not ASCII is more than one byte
+    assert_eq!(
+        lines(&numf(&["--text-in", "-x"], "ä\n".as_bytes())),
+        vec!["C3", "A4"]
+    );
+}
+
+#[test]
+fn tag() {
+    assert_eq!(
+        lines(&numf(&["--tag", "foo", "-x", "-d", "255"], b"")),
+        vec!["foo 255", "foo FF"]
+    );
+    assert_eq!(
+        lines(&numf(&["--tag", "T", "--to-base", "7", "10"], b"")),
+        vec!["T 13"]
+    );
+    assert_eq!(
+        lines(&numf(&["--tag", "T", "--tabular", "-x", "-d", "255"], b"")),
+        vec!["T Dec  Hex", "T 255  FF"]
+    );
+    // raw data is not labelled
+    let output = numf(&["--tag", "foo", "-x", "--raw", "65"], b"");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"foo 41\nA");
+    // an empty tag is no tag
+    assert_eq!(lines(&numf(&["--tag", "", "-x", "65"], b"")), vec!["41"]);
+}
+
+#[test]
+fn keep_going_exit_codes() {
+    // without --keep-going, the first broken number stops everything
+    let output = numf(&["-d", "1", "0xfg", "2"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+
+    // the other numbers are written, but the exit code tells that some were skipped
+    let output = numf(&["-d", "--keep-going", "1", "0xfg", "2"], b"");
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stdout, b"1\n2\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("\"0xfg\""), "{stderr}");
+    assert!(stderr.contains("1 of the input numbers"), "{stderr}");
+
+    let output = numf(&["-d", "--keep-going"], b"1\n0xfg\n3\n");
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stdout, b"1\n3\n");
+
+    // nothing left to format is an error like without --keep-going
+    let output = numf(&["-d", "--keep-going", "0xfg"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+
+    let output = numf(&["-d", "--keep-going", "1", "2"], b"");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(lines(&output), vec!["1", "2"]);
+}
+
+#[test]
+fn max_width() {
+    assert_eq!(
+        lines(&numf(&["-x", "--max-width", "4", "0x1337"], b"")),
+        vec!["1337"]
+    );
+    // the prefix is not counted, unless it is asked for
+    assert_eq!(
+        lines(&numf(&["-x", "-p", "--max-width", "4", "0x1337"], b"")),
+        vec!["0x1337"]
+    );
+    let output = numf(
+        &[
+            "-x",
+            "-p",
+            "--max-width",
+            "4",
+            "--max-width-with-prefix",
+            "0x1337",
+        ],
+        b"",
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim(),
+        "4919 is 6 wide when formatted, but the maximum is 4"
+    );
+
+    // nothing is written if any of the numbers is too wide in any of the formats
+    let output = numf(&["-x", "-d", "--max-width", "3", "0xFF", "0x1337"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let output = numf(&["--to-base", "2", "--max-width", "3", "8"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn format_list() {
+    let list = lines(&numf(&["--format-list"], b""));
+    assert_eq!(list[0], "format           flags               prefix");
+    assert_eq!(list.len(), Format::ALL.len() + 1);
+    // a row for every format, with its name first and its prefix last
+    for (format, row) in Format::ALL.iter().zip(&list[1..]) {
+        let cells: Vec<&str> = row.split("  ").filter(|c| !c.is_empty()).collect();
+        assert_eq!(cells[0].trim(), format.to_string(), "{row}");
+        if *format != Format::Raw {
+            assert_eq!(cells.last().unwrap().trim(), format.prefix_str(), "{row}");
+        }
+    }
+    assert!(list.contains(&"Hex              -x, --hex           0x".to_string()));
+    assert!(list.contains(&"Base85           --base85            085s".to_string()));
+    assert!(list.contains(&"Raw              -a, --raw           byte 0x00".to_string()));
+
+    // the raw prefix is a byte that can be changed
+    let list = lines(&numf(&["--format-list", "--raw-prefix", "0xFF"], b""));
+    assert!(list.contains(&"Raw              -a, --raw           byte 0xFF".to_string()));
+}
+
+#[test]
+fn ndjson() {
+    assert_eq!(
+        lines(&numf(&["--ndjson", "-x", "-d", "255"], b"")),
+        vec![
+            r#"{"value":"255","format":"Dec"}"#,
+            r#"{"value":"FF","format":"Hex"}"#
+        ]
+    );
+    // raw bytes are not valid JSON, they are written as base64
+    assert_eq!(
+        lines(&numf(&["--ndjson", "--raw", "65"], b"")),
+        vec![r#"{"value":"QQ==","format":"Raw"}"#]
+    );
+    assert_eq!(
+        lines(&numf(&["--ndjson", "--to-base", "7", "10"], b"")),
+        vec![r#"{"value":"13","format":"Base7"}"#]
+    );
+    assert_eq!(
+        lines(&numf(&["--ndjson", "--alphabet", "ab", "5"], b"")),
+        vec![r#"{"value":"bab","format":"Alphabet"}"#]
+    );
+    // base85 has characters that need to be escaped, and wrapped lines are escaped too
+    assert_eq!(
+        lines(&numf(&["--ndjson", "--base85", "4", "184"], b"")),
+        vec![
+            r#"{"value":"\"9","format":"Base85"}"#,
+            r#"{"value":"\\,","format":"Base85"}"#
+        ]
+    );
+    assert_eq!(
+        lines(&numf(
+            &[
+                "--ndjson",
+                "--base64",
+                "-p",
+                "--wrap",
+                "4",
+                "0x13371337133713"
+            ],
+            b""
+        )),
+        vec![r#"{"value":"0sEzcT\nNxM3\nEw==","format":"Base64"}"#]
+    );
+}
+
+#[test]
+fn count() {
+    // the whole list is repeated, not every number on its own
+    assert_eq!(
+        lines(&numf(&["-d", "--count", "3", "1", "2"], b"")),
+        vec!["1", "2", "1", "2", "1", "2"]
+    );
+    // the numbers from the stdin are part of the list
+    assert_eq!(
+        lines(&numf(&["-d", "--count", "2", "1"], b"5\n")),
+        vec!["1", "5", "1", "5"]
+    );
+    // the repeated numbers are combined like the others
+    assert_eq!(
+        lines(&numf(
+            &["-d", "--count", "2", "--reduce", "sum", "1", "2"],
+            b""
+        )),
+        vec!["6"]
+    );
+    assert_eq!(
+        numf(&["-d", "--count", "0", "1"], b"").status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn tabular() {
+    // every column is as wide as its widest value or name, with two spaces between them
+    assert_eq!(
+        lines(&numf(&["--tabular", "-x", "-d", "-b", "255", "4096"], b"")),
+        vec![
+            "Dec   Hex   Bin",
+            "255   FF    11111111",
+            "4096  1000  1000000000000"
+        ]
+    );
+    // but not wider than 24, longer values stick out without a terminal to fit into
+    assert_eq!(
+        lines(&numf(
+            &[
+                "--tabular",
+                "-d",
+                "--base64",
+                "340282366920938463463374607431768211455"
+            ],
+            b""
+        )),
+        vec![
+            "Dec                       Base64",
+            "340282366920938463463374607431768211455  /////////////////////w=="
+        ]
+    );
+}
+
+#[test]
+fn tabular_term_width() {
+    // the widest columns are made narrower until the table fits, cut cells end with an ellipsis
+    let max = "340282366920938463463374607431768211455";
+    assert_eq!(
+        lines(&numf(
+            &[
+                "--tabular",
+                "-d",
+                "--base64",
+                "--term-width",
+                "20",
+                max,
+                "1"
+            ],
+            b""
+        )),
+        vec![
+            "Dec        Base64",
+            "34028236…  ////////…",
+            "1          AQ=="
+        ]
+    );
+    // but not narrower than 4
+    assert_eq!(
+        lines(&numf(
+            &["--tabular", "-d", "-x", "--term-width", "5", max],
+            b""
+        )),
+        vec!["Dec   Hex", "340…  FFF…"]
+    );
+    // the tag counts too
+    assert_eq!(
+        lines(&numf(
+            &[
+                "--tabular",
+                "-x",
+                "--tag",
+                "T",
+                "--term-width",
+                "6",
+                "0x1337"
+            ],
+            b""
+        )),
+        vec!["T Hex", "T 1337"]
+    );
+    assert_eq!(
+        lines(&numf(
+            &[
+                "--tabular",
+                "-x",
+                "--tag",
+                "T",
+                "--term-width",
+                "6",
+                "0x13371"
+            ],
+            b""
+        )),
+        vec!["T Hex", "T 133…"]
+    );
+}
+
+#[test]
+fn lang_presets() {
+    // exactly the table in the documentation of Lang
+    let literals = |lang: &str| {
+        lines(&numf(
+            &["--lang", lang, "-d", "-x", "-b", "-o", "0x1337"],
+            b"",
+        ))
+    };
+    assert_eq!(
+        literals("rust"),
+        vec![
+            "4_919u32",
+            "0x1337u32",
+            "0b1_0011_0011_0111u32",
+            "0o11467u32"
+        ]
+    );
+    assert_eq!(
+        literals("c"),
+        vec!["4919U", "0x1337U", "0b1001100110111U", "011467U"]
+    );
+    assert_eq!(
+        literals("python"),
+        vec!["4919", "0x1337", "0b1001100110111", "0o11467"]
+    );
+
+    // the suffix is the smallest type that fits, or the C type
+    let literal = |args: &[&str]| lines(&numf(args, b""));
+    assert_eq!(
+        literal(&["--lang", "rust", "-x", "0x100000000"]),
+        vec!["0x1_0000_0000u64"]
+    );
+    assert_eq!(
+        literal(&["--lang", "rust", "-x", "0x10000000000000000"]),
+        vec!["0x1_0000_0000_0000_0000u128"]
+    );
+    assert_eq!(
+        literal(&["--lang", "rust", "--ctype", "u8", "-x", "0x13"]),
+        vec!["0x13u8"]
+    );
+    assert_eq!(
+        literal(&["--lang", "c", "-x", "0x100000000"]),
+        vec!["0x100000000ULL"]
+    );
+    // the case of the digits can still be chosen
+    assert_eq!(
+        literal(&["--lang", "c", "-x", "--lowercase", "0xAB"]),
+        vec!["0xabU"]
+    );
+
+    // nothing is written for numbers or formats that have no literals
+    let output = numf(&["--lang", "c", "-x", "0x10000000000000000"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let output = numf(&["--lang", "rust", "--base64", "0x13"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn min_max() {
+    assert_eq!(
+        lines(&numf(&["-d", "--min", "5", "3", "9", "3"], b"")),
+        vec!["3"]
+    );
+    assert_eq!(
+        lines(&numf(&["-d", "--max", "5", "3", "9", "9"], b"")),
+        vec!["9"]
+    );
+    // the numbers from the stdin count too, in any format
+    assert_eq!(
+        lines(&numf(&["-d", "--max", "0x10"], b"1\n0b11111\n")),
+        vec!["31"]
+    );
+    assert_eq!(
+        lines(&numf(&["-d", "--min", "--count", "2", "4", "2"], b"")),
+        vec!["2"]
+    );
+    // the one number is formatted like any other
+    assert_eq!(
+        lines(&numf(&["-x", "-p", "--max", "255", "16"], b"")),
+        vec!["0xFF"]
+    );
+
+    assert_eq!(
+        numf(&["-d", "--min", "--max", "1", "2"], b"").status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        numf(&["-d", "--max", "--reduce", "sum", "1", "2"], b"")
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn prefix_once() {
+    assert_eq!(
+        lines(&numf(&["-x", "-p", "--prefix-once", "1", "2", "3"], b"")),
+        vec!["0x1", "2", "3"]
+    );
+    assert_eq!(
+        lines(&numf(&["-x", "-p", "--prefix-once", "1"], b"0x2\n3\n")),
+        vec!["0x1", "2", "3"]
+    );
+    assert_eq!(
+        lines(&numf(
+            &[
+                "-d",
+                "-p",
+                "--prefix-once",
+                "--signed",
+                "--width",
+                "8",
+                "--",
+                "-1",
+                "-2"
+            ],
+            b""
+        )),
+        vec!["-0d1", "-2"]
+    );
+    // with more than one format, the prefix tells them apart, so it stays
+    assert_eq!(
+        lines(&numf(&["-x", "-d", "-p", "--prefix-once", "1", "2"], b"")),
+        vec!["0d1", "0x1", "0d2", "0x2"]
+    );
+    // there has to be a prefix to write once
+    assert_eq!(
+        numf(&["-x", "--prefix-once", "1"], b"").status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn negative_numbers() {
+    assert_eq!(
+        lines(&numf(&["-x", "--float", "f64", "-2", "1.5"], b"")),
+        vec!["C000000000000000", "3FF8000000000000"]
+    );
+    assert_eq!(
+        lines(&numf(&["-x", "--float", "f32", "-1.5e3"], b"")),
+        vec!["C4BB8000"]
+    );
+    assert_eq!(
+        lines(&numf(&["-x", "--float", "f32", "--", "-inf"], b"")),
+        vec!["FF800000"]
+    );
+    assert_eq!(
+        lines(&numf(&["-x", "--signed", "--width", "16", "-1"], b"")),
+        vec!["FFFF"]
+    );
+    // flags are still flags
+    assert_eq!(lines(&numf(&["-d", "-x", "16"], b"")), vec!["16", "10"]);
+}
@@ -0,0 +1,52 @@
+use numf::explain::*;
+use numf::format::*;
+
+#[test]
+fn explain_radix_formats() {
+    assert_eq!(
+        explain(255, Format::Hex),
+        vec![
+            "255 / 16 = 15, remainder 15 -> 'F'",
+            "15 / 16 = 0, remainder 15 -> 'F'",
+            "the remainders in reverse order are the digits",
+            "result: FF",
+        ]
+    );
+    assert_eq!(
+        explain(0, Format::Bin),
+        vec![
+            "0 / 2 = 0, remainder 0 -> '0'",
+            "the remainders in reverse order are the digits",
+            "result: 0",
+        ]
+    );
+    assert_eq!(explain(8, Format::Octal).last().unwrap(), "result: 10");
+    assert_eq!(explain(1337, Format::Dec).last().unwrap(), "result: 1337");
+    assert_eq!(explain_radix(342, 12).last().unwrap(), "result: 246");
+}
+
+#[test]
+fn explain_bit_groups() {
+    assert_eq!(
+        explain(256, Format::Base64),
+        vec![
+            "bytes: 0x01 0x00",
+            "bits: 00000001 00000000",
+            "groups of 6 bits:",
+            "  000000 -> 0 -> 'A'",
+            "  010000 -> 16 -> 'Q'",
+            "  0000 + 00 -> 0 -> 'A'",
+            "padding: = to a multiple of 4 characters",
+            "result: AQA=",
+        ]
+    );
+    let options = FormatOptions::default();
+    for num in [0, 1, 0x41414242, 0x4141414141414141, u128::MAX] {
+        for format in [Format::Base64, Format::Base32] {
+            assert_eq!(
+                explain(num, format).last().unwrap(),
+                &format!("result: {}", format.format_str(num, &options))
+            );
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use numf::format::*;
 
 #[test]
@@ -270,3 +272,2447 @@ fn parser_generics() {
     assert_eq!(numf_parser_str::<u64>("55").unwrap(), 55);
     assert_eq!(numf_parser_str::<u128>("55").unwrap(), 55);
 }
+
+#[test]
+fn parser_negative() {
+    for input in [
+        "-0x10",
+        "-0b101",
+        "-0o17",
+        "-0d16",
+        "-16",
+        "-0sQUFCQg==",
+        "-032sIFAUEQQ=",
+    ] {
+        let err = numf_parser_str::<u128>(input).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<NumfError>(),
+            Some(&NumfError::NegativeNotSupported(input.to_string()))
+        );
+    }
+    // a lone '-' is not a number, it's just a raw byte
+    assert_eq!(numf_parser_str::<u128>("-").unwrap(), b'-' as u128);
+}
+
+#[test]
+fn bytes() {
+    assert_eq!(to_bytes(0), vec![0x00]);
+    assert_eq!(to_bytes(0x1), vec![0x01]);
+    assert_eq!(to_bytes(0x100), vec![0x01, 0x00]);
+    assert_eq!(to_bytes(0xC0FFEE), vec![0xC0, 0xFF, 0xEE]);
+    assert_eq!(to_bytes(u128::MAX), vec![0xFF; 16]);
+
+    let options = FormatOptions::default();
+    for num in [0, 0x1337, 0xC0FFEE, u128::MAX] {
+        assert_eq!(Format::Raw.format(num, &options), to_bytes(num));
+    }
+}
+
+#[test]
+fn radix() {
+    assert_eq!(format_radix(0, 2).unwrap(), "0");
+    assert_eq!(format_radix(0b1101, 2).unwrap(), "1101");
+    assert_eq!(format_radix(0xC0FFEE, 16).unwrap(), "C0FFEE");
+    assert_eq!(
+        format_radix(u128::MAX, 36).unwrap(),
+        "F5LXX1ZZ5PNORYNQGLHZMSP33"
+    );
+    assert_eq!(format_radix(144, 12).unwrap(), "100");
+    assert!(format_radix(1, 1).is_err());
+    assert!(format_radix(1, 37).is_err());
+
+    assert_eq!(numf_parser_radix::<u128>("1101", 2).unwrap(), 0b1101);
+    assert_eq!(numf_parser_radix::<u128>("c0ffee", 16).unwrap(), 0xC0FFEE);
+    assert_eq!(
+        numf_parser_radix::<u128>("F5LXX1ZZ5PNORYNQGLHZMSP33", 36).unwrap(),
+        u128::MAX
+    );
+    assert_eq!(
+        numf_parser_radix::<u128>("18", 7)
+            .unwrap_err()
+            .downcast_ref::<NumfError>(),
+        Some(&NumfError::InvalidDigit {
+            digit: '8',
+            radix: 7
+        })
+    );
+    assert_eq!(
+        numf_parser_radix::<u128>("1", 40)
+            .unwrap_err()
+            .downcast_ref::<NumfError>(),
+        Some(&NumfError::InvalidRadix(40))
+    );
+
+    // base 7 to base 12
+    let mut options = FormatOptions::default();
+    options.set_from_base(Some(7));
+    let num = options.parse_number("666").unwrap();
+    assert_eq!(num, 342);
+    assert_eq!(format_radix(num, 12).unwrap(), "246");
+}
+
+#[test]
+fn options_display() {
+    let mut options = FormatOptions::default();
+    assert_eq!(
+        options.to_string(),
+        format!(
+            "format=Hex prefix=false padding=false rand=0 rand_max={}",
+            u128::MAX
+        )
+    );
+
+    options.set_format(Format::Base64);
+    options.set_padding(true);
+    options.set_rand(5);
+    options.set_rand_max(100);
+    options.set_from_base(Some(7));
+    options.set_to_base(Some(12));
+    assert_eq!(
+        options.to_string(),
+        "format=Base64 prefix=false padding=true rand=5 rand_max=100 from_base=7 to_base=12"
+    );
+}
+
+#[test]
+fn digits() {
+    assert_eq!(digit_count(0, 2), 1);
+    assert_eq!(digit_count(0, 10), 1);
+    assert_eq!(digit_count(1, 2), 1);
+    assert_eq!(digit_count(0b1000_0000, 2), 8);
+    assert_eq!(digit_count(0o777, 8), 3);
+    assert_eq!(digit_count(0o1000, 8), 4);
+    assert_eq!(digit_count(u128::MAX, 2), 128);
+    assert_eq!(digit_count(u128::MAX, 16), 32);
+    assert_eq!(digit_count(u128::MAX, 10), 39);
+    for num in [0, 1, 7, 0x1337, 123456789, u128::MAX] {
+        for radix in [2, 7, 10, 16, 36] {
+            assert_eq!(
+                digit_count(num, radix) as usize,
+                format_radix(num, radix).unwrap().len()
+            );
+        }
+    }
+}
+
+#[test]
+fn raw_roundtrip() {
+    let mut options = FormatOptions::default();
+    assert_eq!(Format::Raw.format(0x0, &options), vec![0x00]);
+    assert_eq!(Format::Raw.format(0xFF, &options), vec![0xFF]);
+    assert_eq!(Format::Raw.format(0x1337, &options), vec![0x13, 0x37]);
+    for num in [0x0, 0xFF, 0x1337, u128::MAX] {
+        let data = Format::Raw.format(num, &options);
+        assert_eq!(numf_parser::<u128>(&data).unwrap(), num);
+    }
+
+    options.set_prefix(true);
+    assert_eq!(Format::Raw.format(0x0, &options), vec![0x00, 0x00]);
+    assert_eq!(Format::Raw.format(0xFF, &options), vec![0x00, 0xFF]);
+    assert_eq!(Format::Raw.format(0x1337, &options), vec![0x00, 0x13, 0x37]);
+    for num in [0x0, 0xFF, 0x1337, 0x3132, u128::MAX] {
+        let data = Format::Raw.format(num, &options);
+        assert_eq!(numf_parser::<u128>(&data).unwrap(), num);
+    }
+
+    // the prefix is only skipped once
+    assert_eq!(numf_parser::<u128>(&[0x00, 0x00, 0x01]).unwrap(), 1);
+    assert!(numf_parser::<u128>(&[0x00; 18]).is_err());
+}
+
+#[test]
+fn raw_prefix_override() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_raw_prefix(0xAA);
+    assert_eq!(Format::Raw.format(0x0, &options), vec![0xAA, 0x00]);
+    assert_eq!(Format::Raw.format(0x1337, &options), vec![0xAA, 0x13, 0x37]);
+    // other formats keep their prefixes
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "0x1337");
+
+    // data that really starts with zero bytes now round trips
+    assert_eq!(options.parse_bytes(&[0xAA, 0x00, 0x13]).unwrap(), 0x13);
+    assert_eq!(options.parse_bytes(&[0x00, 0x00, 0x13]).unwrap(), 0x13);
+    assert_eq!(options.parse_bytes(&[0x00, 0x13, 0x37]).unwrap(), 0x1337);
+    for num in [0x0, 0xFF, 0x1337, u128::MAX] {
+        let data = Format::Raw.format(num, &options);
+        assert_eq!(options.parse_bytes(&data).unwrap(), num);
+    }
+    // a single byte is never a prefix
+    assert_eq!(options.parse_bytes(&[0xAA]).unwrap(), 0xAA);
+}
+
+#[test]
+fn base32_crockford() {
+    let mut options = FormatOptions::default();
+    for num in [0, 1, 0x41414242, 0x4141414141414141, u128::MAX] {
+        let formatted = Format::Base32Crockford.format_str(num, &options);
+        assert!(!formatted.contains(['I', 'L', 'O', 'U', '=']));
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("032c{formatted}")).unwrap(),
+            num
+        );
+        // case insensitive, and with the ambiguous letters
+        let sloppy = formatted.to_lowercase().replace('1', "l").replace('0', "O");
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("032c{sloppy}")).unwrap(),
+            num
+        );
+        let sloppy = formatted.replace('1', "I").replace('0', "o");
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("032c{sloppy}")).unwrap(),
+            num
+        );
+    }
+    assert_eq!(
+        Format::Base32Crockford.format_str(0x41414242, &options),
+        "850M4GG"
+    );
+    assert_eq!(
+        numf_parser_str::<u128>("032c85-0M-4GG").unwrap(),
+        0x41414242
+    );
+    assert!(numf_parser_str::<u128>("032cU").is_err());
+
+    options.set_prefix(true);
+    assert_eq!(
+        Format::Base32Crockford.format_str(0x41414242, &options),
+        "032c850M4GG"
+    );
+    options.set_format(Format::Base32Crockford);
+    assert_eq!(options.format(), Format::Base32Crockford);
+}
+
+#[test]
+fn format_names() {
+    for (i, format) in Format::ALL.iter().enumerate() {
+        assert_eq!(Format::try_from(i as u8).unwrap(), *format);
+        // the display name can be parsed back
+        assert_eq!(format.to_string().parse::<Format>().unwrap(), *format);
+        assert_eq!(
+            Format::try_from(format.to_string().as_str()).unwrap(),
+            *format
+        );
+    }
+    assert_eq!(
+        Format::try_from(Format::ALL.len() as u8),
+        Err(NumfError::InvalidFormatIndex(Format::ALL.len() as u8))
+    );
+
+    assert_eq!(Format::try_from("HEX").unwrap(), Format::Hex);
+    assert_eq!(Format::try_from("x").unwrap(), Format::Hex);
+    assert_eq!(Format::try_from("decimal").unwrap(), Format::Dec);
+    assert_eq!(Format::try_from("b32").unwrap(), Format::Base32);
+    assert_eq!(
+        Format::try_from("crockford").unwrap(),
+        Format::Base32Crockford
+    );
+    assert_eq!(Format::try_from("a").unwrap(), Format::Raw);
+    assert_eq!(
+        Format::try_from("hexx"),
+        Err(NumfError::UnknownFormatName("hexx".to_string()))
+    );
+}
+
+#[test]
+fn signed_view() {
+    assert_eq!(as_signed(0, 1).unwrap(), 0);
+    assert_eq!(as_signed(1, 1).unwrap(), -1);
+    assert_eq!(as_signed(0x7F, 8).unwrap(), 127);
+    assert_eq!(as_signed(0xFF, 8).unwrap(), -1);
+    assert_eq!(as_signed(0xFF, 16).unwrap(), 255);
+    assert_eq!(as_signed(0x8000, 16).unwrap(), i16::MIN as i128);
+    assert_eq!(as_signed(0xFFFFFFFF, 32).unwrap(), -1);
+    assert_eq!(as_signed(u64::MAX as u128, 64).unwrap(), -1);
+    assert_eq!(as_signed(u128::MAX, 128).unwrap(), -1);
+    assert_eq!(as_signed(1 << 127, 128).unwrap(), i128::MIN);
+    assert_eq!(as_signed(1, 128).unwrap(), 1);
+
+    assert_eq!(
+        as_signed(0x100, 8).unwrap_err().downcast_ref::<NumfError>(),
+        Some(&NumfError::ValueTooWide {
+            value: 0x100,
+            width: 8
+        })
+    );
+    assert_eq!(
+        as_signed(1, 0).unwrap_err().downcast_ref::<NumfError>(),
+        Some(&NumfError::InvalidWidth(0))
+    );
+    assert_eq!(
+        as_signed(1, 129).unwrap_err().downcast_ref::<NumfError>(),
+        Some(&NumfError::InvalidWidth(129))
+    );
+}
+
+#[test]
+fn base85() {
+    let mut options = FormatOptions::default();
+    // "Man " from the wikipedia example
+    assert_eq!(Format::Base85.format_str(0x4d616e20, &options), "9jqo^");
+    assert_eq!(Format::Base85.format_str(0, &options), "!!");
+    assert_eq!(Format::Base85.format_str(0x100000000, &options), "!<<*\"!!");
+    assert_eq!(
+        Format::Base85.format_str(0x0100000000000000, &options),
+        "!<<*\"z"
+    );
+    assert_eq!(Format::Base85.format_str(0x41414242, &options), "5s[h(");
+    assert_eq!(Format::Base85.format_str(0x414142, &options), "5s[g");
+    assert_eq!(numf_parser_str::<u128>("085s!!").unwrap(), 0);
+    assert_eq!(numf_parser_str::<u128>("085s5s[g").unwrap(), 0x414142);
+    assert_eq!(
+        numf_parser_str::<u128>("085s!<<*\"z").unwrap(),
+        0x0100000000000000
+    );
+    assert_eq!(numf_parser_str::<u128>("085s9jqo^").unwrap(), 0x4d616e20);
+    assert!(numf_parser_str::<u128>("085s5").is_err());
+    assert!(numf_parser_str::<u128>("085s5z").is_err());
+    assert!(numf_parser_str::<u128>("085s5s[~").is_err());
+    // more than u32::MAX in a group
+    assert!(numf_parser_str::<u128>("085suuuuu").is_err());
+
+    options.set_prefix(true);
+    for num in [
+        0,
+        1,
+        0xFF,
+        0x1337,
+        0x414142,
+        0x100000000,
+        0x0100000000000000,
+        0x4141414141414141,
+        u128::MAX,
+    ] {
+        let formatted = Format::Base85.format_str(num, &options);
+        assert_eq!(numf_parser_str::<u128>(&formatted).unwrap(), num);
+    }
+}
+
+#[test]
+fn parser_detect() {
+    let inputs: [(&[u8], Format); 11] = [
+        (b"1337", Format::Dec),
+        (b"0d1337", Format::Dec),
+        (b"0x1337", Format::Hex),
+        (b"0o1337", Format::Octal),
+        (b"0b1101", Format::Bin),
+        (b"0sQUFCQg==", Format::Base64),
+        (b"032sIFAUEQQ=", Format::Base32),
+        (b"032c850M4GG", Format::Base32Crockford),
+        (b"085s5s[h(", Format::Base85),
+        (&[0x13, 0x37], Format::Raw),
+        (&[0x00, 0x13, 0x37], Format::Raw),
+    ];
+    for (data, format) in inputs {
+        assert_eq!(numf_parser_detect(data), format);
+        // and it can really be parsed
+        assert!(numf_parser::<u128>(data).is_ok());
+    }
+}
+
+#[test]
+fn transforms() {
+    // reversing twice is the identity
+    for (num, width) in [(0, 1), (1, 1), (0b101, 3), (0xC0FFEE, 24), (u128::MAX, 128)] {
+        assert_eq!(
+            bit_reverse(bit_reverse(num, width).unwrap(), width).unwrap(),
+            num
+        );
+    }
+    for (num, width) in [
+        (0, 8),
+        (0x13, 8),
+        (0x1337, 16),
+        (0xC0FFEE, 24),
+        (u128::MAX, 128),
+    ] {
+        assert_eq!(
+            byte_swap(byte_swap(num, width).unwrap(), width).unwrap(),
+            num
+        );
+    }
+    // palindromes do not change
+    assert_eq!(bit_reverse(0b10000001, 8).unwrap(), 0b10000001);
+    assert_eq!(byte_swap(0x13, 8).unwrap(), 0x13);
+
+    assert_eq!(bit_reverse(0b00000001, 8).unwrap(), 0b10000000);
+    assert_eq!(bit_reverse(0b1, 7).unwrap(), 0b1000000);
+    assert_eq!(bit_reverse(0b1101, 5).unwrap(), 0b10110);
+    assert_eq!(bit_reverse(1, 128).unwrap(), 1 << 127);
+    assert_eq!(byte_swap(0x1337, 16).unwrap(), 0x3713);
+    assert_eq!(byte_swap(0xC0FFEE, 24).unwrap(), 0xEEFFC0);
+    assert_eq!(byte_swap(0xC0FFEE, 32).unwrap(), 0xEEFFC000);
+    assert_eq!(byte_swap(1, 128).unwrap(), 1 << 120);
+
+    assert!(bit_reverse(0b1000, 3).is_err());
+    assert!(byte_swap(0x1337, 8).is_err());
+    assert_eq!(
+        byte_swap(0x13, 12).unwrap_err().downcast_ref::<NumfError>(),
+        Some(&NumfError::NotByteAligned(12))
+    );
+
+    let mut options = FormatOptions::default();
+    assert_eq!(options.transform(0x1337).unwrap(), 0x1337);
+    options.set_bit_reverse(Some(16));
+    options.set_byte_swap(Some(16));
+    assert_eq!(options.transform(0x0001).unwrap(), 0x0080);
+}
+
+#[test]
+fn options_builder() {
+    assert_eq!(FormatOptions::builder().build(), FormatOptions::default());
+
+    let options = FormatOptions::builder()
+        .format(Format::Bin)
+        .prefix(true)
+        .padding(true)
+        .numbers(vec![1, 2])
+        .rand(3)
+        .rand_max(10)
+        .raw_prefix(0xAA)
+        .build();
+    let mut manual = FormatOptions::default();
+    manual.set_format(Format::Bin);
+    manual.set_prefix(true);
+    manual.set_padding(true);
+    manual.set_numbers(vec![1, 2]);
+    manual.set_rand(3);
+    manual.set_rand_max(10);
+    manual.set_raw_prefix(0xAA);
+    assert_eq!(options, manual);
+    assert_eq!(options.format().format_str(5, &options), "0b00000101");
+}
+
+#[test]
+fn mixed_input() {
+    let options = FormatOptions::default();
+    let data = b"1 0x2\n0b11\t\xff 5\n";
+    let parts = split_input(data);
+    assert_eq!(parts, vec![&b"1"[..], b"0x2", b"0b11", b"\xff", b"5"]);
+    let numbers: Vec<u128> = parts
+        .iter()
+        .map(|p| options.parse_bytes(p).unwrap())
+        .collect();
+    assert_eq!(numbers, vec![1, 2, 3, 0xFF, 5]);
+
+    // pure text works like before
+    assert_eq!(split_input(b"  1\n\n2  "), vec![&b"1"[..], b"2"]);
+    assert!(split_input(b"").is_empty());
+    // raw data with bytes that look like whitespace stays in one piece
+    assert_eq!(split_input(b"\x20\xff\xb4"), vec![&b"\x20\xff\xb4"[..]]);
+    assert_eq!(options.parse_bytes(b"\x20\xff\xb4").unwrap(), 0x20FFB4);
+}
+
+#[test]
+fn output_file() {
+    let mut options = FormatOptions::default();
+    assert_eq!(options.output_file(), None);
+    options.set_output_file(Some("out.bin".into()));
+    assert_eq!(options.output_file(), Some(std::path::Path::new("out.bin")));
+    assert!(options.to_string().ends_with(" output_file=out.bin"));
+}
+
+#[test]
+fn duration() {
+    let mut options = FormatOptions::default();
+    assert_eq!(Format::Duration.format_str(0, &options), "0s");
+    assert_eq!(Format::Duration.format_str(59, &options), "59s");
+    assert_eq!(Format::Duration.format_str(3723, &options), "1h2m3s");
+    assert_eq!(Format::Duration.format_str(3600, &options), "1h");
+    assert_eq!(Format::Duration.format_str(691261, &options), "1w1d1m1s");
+    assert_eq!(
+        Format::Duration.format_str(u128::MAX, &options),
+        "562636188692027882710606163081627w2d8h4m15s"
+    );
+
+    options.set_clock(true);
+    assert_eq!(Format::Duration.format_str(0, &options), "00:00:00");
+    assert_eq!(Format::Duration.format_str(3723, &options), "01:02:03");
+    assert_eq!(Format::Duration.format_str(90000, &options), "25:00:00");
+
+    options.set_prefix(true);
+    assert_eq!(Format::Duration.format_str(3723, &options), "0t01:02:03");
+
+    assert_eq!(numf_parser_str::<u128>("0t1h2m3s").unwrap(), 3723);
+    assert_eq!(numf_parser_str::<u128>("0t0s").unwrap(), 0);
+    assert_eq!(numf_parser_str::<u128>("0t01:02:03").unwrap(), 3723);
+    assert_eq!(numf_parser_str::<u128>("0t100:59:59").unwrap(), 363599);
+    assert_eq!(numf_parser_str::<u128>("0t1w1d1m1s").unwrap(), 691261);
+    assert_eq!(numf_parser_str::<u128>("0t90m").unwrap(), 5400);
+    assert_eq!(Format::Duration.parse(b"1h2m3s").unwrap(), 3723);
+    assert!(numf_parser_str::<u128>("0t1h2").is_err());
+    assert!(numf_parser_str::<u128>("0th").is_err());
+    assert!(numf_parser_str::<u128>("0t1:2").is_err());
+    assert!(numf_parser_str::<u8>("0t1h").is_err());
+    // a clock has less than 60 minutes and seconds
+    assert!(numf_parser_str::<u128>("0t1:99:99").is_err());
+    assert!(numf_parser_str::<u128>("0t1:60:00").is_err());
+    assert!(numf_parser_str::<u128>("0t1:00:60").is_err());
+
+    assert_eq!(numf_parser_detect(b"0t1h2m3s"), Format::Duration);
+    assert_eq!(numf_parser_detect(b"0t01:02:03"), Format::Duration);
+    // without the prefix, it's raw data like before
+    for raw in ["5s", "1d", "1h2m3s", "01:02:03"] {
+        assert_eq!(numf_parser_detect(raw.as_bytes()), Format::Raw);
+        assert_eq!(
+            numf_parser_str::<u128>(raw).unwrap(),
+            numf_parser_raw::<u128>(raw.as_bytes(), false).unwrap()
+        );
+    }
+    assert_eq!("duration".parse::<Format>().unwrap(), Format::Duration);
+}
+
+#[test]
+fn rand_limit() {
+    let mut options = FormatOptions::default();
+    assert_eq!(options.rand_limit(), u128::MAX);
+    options.set_width(Some(16));
+    assert_eq!(options.rand_limit(), 0x10000);
+    options.set_width(Some(128));
+    assert_eq!(options.rand_limit(), u128::MAX);
+    options.set_width(Some(127));
+    assert_eq!(options.rand_limit(), 1 << 127);
+
+    // a lower rand_max still wins
+    let options = FormatOptions::builder()
+        .width(Some(16))
+        .rand_max(100)
+        .build();
+    assert_eq!(options.rand_limit(), 100);
+    let options = FormatOptions::builder()
+        .width(Some(8))
+        .rand_max(1000)
+        .build();
+    assert_eq!(options.rand_limit(), 256);
+}
+
+#[test]
+fn bin_group() {
+    let mut options = FormatOptions::default();
+    options.set_bin_group(NonZeroUsize::new(4));
+    assert_eq!(Format::Bin.format_str(0x1337, &options), "1 0011 0011 0111");
+    assert_eq!(Format::Bin.format_str(0, &options), "0");
+    assert_eq!(Format::Bin.format_str(0xF, &options), "1111");
+    assert_eq!(Format::Bin.format_str(0x1F, &options), "1 1111");
+    // only binary is grouped
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "1337");
+
+    options.set_padding(true);
+    options.set_prefix(true);
+    assert_eq!(
+        Format::Bin.format_str(0x1337, &options),
+        "0b0001 0011 0011 0111"
+    );
+    options.set_bin_group(NonZeroUsize::new(8));
+    assert_eq!(
+        Format::Bin.format_str(0x1337, &options),
+        "0b00010011 00110111"
+    );
+    options.set_bin_group(NonZeroUsize::new(3));
+    assert_eq!(Format::Bin.format_str(0xFF, &options), "0b11 111 111");
+    // a group of 0 is no group
+    options.set_bin_group(NonZeroUsize::new(0));
+    assert_eq!(options.bin_group(), None);
+    assert_eq!(Format::Bin.format_str(0xFF, &options), "0b11111111");
+}
+
+#[test]
+fn floats() {
+    assert_eq!(
+        float_bits("2.5", FloatType::F64).unwrap(),
+        2.5f64.to_bits() as u128
+    );
+    assert_eq!(float_bits("0", FloatType::F32).unwrap(), 0);
+    assert_eq!(float_bits("-0", FloatType::F32).unwrap(), 0x80000000);
+    assert_eq!(
+        float_bits("inf", FloatType::F64).unwrap(),
+        0x7FF0000000000000
+    );
+    assert_eq!(
+        float_bits("NaN", FloatType::F64).unwrap(),
+        f64::NAN.to_bits() as u128
+    );
+    assert!(float_bits("", FloatType::F64).is_err());
+    assert!(float_bits("1.5.3", FloatType::F64).is_err());
+
+    assert_eq!(as_float(0, FloatType::F64).unwrap(), "0");
+    assert_eq!(as_float(0x80000000, FloatType::F32).unwrap(), "-0");
+    assert_eq!(as_float(0x7FF0000000000000, FloatType::F64).unwrap(), "inf");
+    assert_eq!(
+        as_float(0xFFF0000000000000, FloatType::F64).unwrap(),
+        "-inf"
+    );
+    assert_eq!(as_float(0xFFFFFFFF, FloatType::F32).unwrap(), "NaN");
+    assert_eq!(
+        as_float(2.75f32.to_bits() as u128, FloatType::F32).unwrap(),
+        "2.75"
+    );
+    assert!(as_float(1 << 64, FloatType::F64).is_err());
+
+    let mut options = FormatOptions::default();
+    options.set_float(Some(FloatType::F32));
+    assert_eq!(options.parse_number("1.5").unwrap(), 0x3FC00000);
+    assert!(options.parse_number("0x3FC00000").is_err());
+}
+
+#[test]
+fn strip_base_padding() {
+    let mut options = FormatOptions::default();
+    options.set_strip_base_padding(true);
+    assert_eq!(Format::Base64.format_str(0x41, &options), "QQ");
+    assert_eq!(Format::Base64.format_str(0x4141, &options), "QUE");
+    assert_eq!(Format::Base64.format_str(0x414141, &options), "QUFB");
+    assert_eq!(Format::Base64.format_str(0x41414141, &options), "QUFBQQ");
+    assert_eq!(Format::Base32.format_str(0x41, &options), "IE");
+    assert_eq!(Format::Base32.format_str(0x41414242, &options), "IFAUEQQ");
+    // nothing to strip in the other formats
+    assert_eq!(Format::Hex.format_str(0x41, &options), "41");
+
+    options.set_prefix(true);
+    for num in [
+        0x01,
+        0xFFFF,
+        0x123456,
+        0xDEADBEEF,
+        0x0102030405,
+        0x010203040506,
+        0x01020304050607,
+        u128::MAX,
+    ] {
+        for format in [Format::Base64, Format::Base32] {
+            let formatted = format.format_str(num, &options);
+            assert!(!formatted.ends_with('='), "{formatted}");
+            assert_eq!(
+                numf_parser_str::<u128>(&formatted).unwrap(),
+                num,
+                "{format} {formatted}"
+            );
+        }
+    }
+}
+
+#[test]
+fn verify_roundtrip() {
+    // this is what --verify checks
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_verify(true);
+    assert!(options.to_string().ends_with(" verify=true"));
+    for format in Format::ALL {
+        for num in [
+            0,
+            1,
+            0x20,
+            0xFF,
+            0x1337,
+            0xDEADBEEF,
+            u64::MAX as u128,
+            u128::MAX,
+        ] {
+            let formatted = format.format(num, &options);
+            assert_eq!(
+                numf_parser::<u128>(&formatted).unwrap(),
+                num,
+                "{format} {formatted:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn verify_output_options() {
+    // every option that changes the output has to be read back by --verify
+    type Change = fn(&mut FormatOptions);
+    let changes: Vec<(&str, Change)> = vec![
+        ("bin_group", |o| o.set_bin_group(NonZeroUsize::new(4))),
+        ("signed", |o| {
+            o.set_signed(true);
+            o.set_width(Some(32));
+        }),
+        ("base_group", |o| {
+            o.set_base_group(Some("4:-".parse().unwrap()));
+        }),
+        ("min_digits", |o| o.set_min_digits(Some(12))),
+        ("pad_char", |o| {
+            o.set_min_digits(Some(12));
+            o.set_pad_char('_');
+        }),
+        ("padding", |o| o.set_padding(true)),
+        ("le_display", |o| o.set_le_display(Some(32))),
+        ("lowercase", |o| o.set_case(Some(Case::Lower))),
+        ("prefix_style", |o| {
+            o.set_prefix_style(PrefixStyle::C);
+            o.set_min_digits(Some(12));
+        }),
+        ("strip_base_padding", |o| o.set_strip_base_padding(true)),
+        ("base64_wrap", |o| o.set_base64_wrap(NonZeroUsize::new(4))),
+        ("clock", |o| o.set_clock(true)),
+        ("ctype", |o| o.set_ctype(Some(CType::U32))),
+        ("raw_full_width", |o| o.set_raw_full_width(true)),
+    ];
+    for (name, change) in changes {
+        let mut options = FormatOptions::default();
+        change(&mut options);
+        for format in Format::ALL {
+            for num in [0, 1, 0xFF, 0x1337, 0xDEADBEEF] {
+                if let Err(e) = options.verify_number(format, num) {
+                    panic!("{name}: {format}: {e:#}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn parser_strict() {
+    for garbage in ["hello", "", "12a", "0y15", "\u{ff}"] {
+        let e = numf_parser_strict::<u128>(garbage.as_bytes()).unwrap_err();
+        assert_eq!(
+            e.downcast_ref::<NumfError>(),
+            Some(&NumfError::UnknownFormat(garbage.to_string())),
+            "{garbage:?}"
+        );
+        // the lenient parser still accepts these as raw
+        if !garbage.is_empty() {
+            assert!(
+                numf_parser::<u128>(garbage.as_bytes()).is_ok(),
+                "{garbage:?}"
+            );
+        }
+    }
+    assert_eq!(numf_parser_strict::<u128>(b"0b101").unwrap(), 5);
+    assert_eq!(numf_parser_strict::<u128>(b"0d17").unwrap(), 17);
+    assert_eq!(numf_parser_strict::<u128>(b"0t1h").unwrap(), 3600);
+    // a broken number with a known prefix is still the error of that format
+    assert!(numf_parser_strict::<u128>(b"0xfg").is_err());
+
+    let mut options = FormatOptions::default();
+    assert_eq!(options.parse_number("AB").unwrap(), 0x4142);
+    options.set_strict(true);
+    assert!(options.parse_number("AB").is_err());
+    assert!(options.parse_bytes(&[0x00, 0x13, 0x37]).is_err());
+    assert_eq!(options.parse_number("0x13").unwrap(), 0x13);
+}
+
+#[test]
+fn octal_padding_min_digits() {
+    let mut options = FormatOptions::default();
+    options.set_padding(true);
+    assert_eq!(Format::Octal.format_str(0o7, &options), "007");
+    assert_eq!(Format::Octal.format_str(0o377, &options), "377");
+    assert_eq!(Format::Octal.format_str(0o644, &options), "000644");
+    assert_eq!(Format::Octal.format_str(0, &options), "000");
+    assert_eq!(
+        Format::Octal.format_str(u128::MAX, &options),
+        format!("{:o}", u128::MAX)
+    );
+    // decimal has no whole bytes
+    assert_eq!(Format::Dec.format_str(7, &options), "7");
+
+    let mut options = FormatOptions::default();
+    options.set_min_digits(Some(4));
+    assert_eq!(Format::Octal.format_str(0o644, &options), "0644");
+    assert_eq!(Format::Dec.format_str(7, &options), "0007");
+    assert_eq!(Format::Hex.format_str(0xF, &options), "000F");
+    assert_eq!(Format::Bin.format_str(1, &options), "0001");
+    assert_eq!(Format::Dec.format_str(123456, &options), "123456");
+    // not for the other formats
+    assert_eq!(Format::Base64.format_str(0x41, &options), "QQ==");
+
+    options.set_prefix(true);
+    options.set_padding(true);
+    assert_eq!(Format::Octal.format_str(0o644, &options), "0o000644");
+    assert_eq!(Format::Hex.format_str(0xF, &options), "0x000F");
+    options.set_bin_group(NonZeroUsize::new(4));
+    options.set_min_digits(Some(12));
+    assert_eq!(Format::Bin.format_str(0x1F, &options), "0b0000 0001 1111");
+}
+
+#[test]
+fn parse_u128() {
+    assert_eq!(numf_parse_u128(b"1337"), Ok(1337));
+    assert_eq!(numf_parse_u128(b"0b101"), Ok(5));
+    assert_eq!(numf_parse_u128(b"0sQUFCQg=="), Ok(0x41414242));
+    assert_eq!(numf_parse_u128(&[0x00, 0x13, 0x37]), Ok(0x1337));
+    assert_eq!(
+        numf_parse_u128(b"-5"),
+        Err(NumfError::NegativeNotSupported("-5".to_string()))
+    );
+    assert!(matches!(
+        numf_parse_u128(b"0o78"),
+        Err(NumfError::InvalidNumber {
+            format: Format::Octal,
+            ..
+        })
+    ));
+    assert!(matches!(
+        numf_parse_u128(&[0xFF; 20]),
+        Err(NumfError::InvalidNumber {
+            format: Format::Raw,
+            ..
+        })
+    ));
+
+    // the generic parsers still work for smaller types
+    assert_eq!(numf_parser_str::<u8>("0xFF").unwrap(), 0xFF);
+    assert!(numf_parser_str::<u8>("0x100").is_err());
+    assert!(numf_parser_str::<u8>("256").is_err());
+    assert_eq!(numf_parser::<u16>(&[0x13, 0x37]).unwrap(), 0x1337);
+    assert!(numf_parser::<u16>(&[0x13, 0x37, 0x00]).is_err());
+}
+
+#[test]
+fn options_from_format() {
+    for format in Format::ALL {
+        let options = FormatOptions::from_format(format);
+        assert_eq!(options.format(), format);
+        let mut expected = FormatOptions::default();
+        expected.set_format(format);
+        assert_eq!(options, expected);
+    }
+}
+
+#[test]
+fn le_display() {
+    let mut options = FormatOptions::default();
+    options.set_le_display(Some(16));
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "3713");
+    assert_eq!(Format::Hex.format_str(0x37, &options), "3700");
+    assert_eq!(Format::Bin.format_str(0x0102, &options), "0000001000000001");
+    // the value stays the same for the other formats
+    assert_eq!(Format::Dec.format_str(0x1337, &options), "4919");
+    // does not fit, shown as is
+    assert_eq!(Format::Hex.format_str(0x123456, &options), "123456");
+
+    options.set_le_display(Some(32));
+    options.set_prefix(true);
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "0x37130000");
+    assert_eq!(Format::Hex.format_str(0xDEADBEEF, &options), "0xEFBEADDE");
+}
+
+#[test]
+fn multiple_formats() {
+    let mut options = FormatOptions::default();
+    assert_eq!(options.formats(), vec![Format::Hex]);
+    options.set_format(Format::Bin);
+    assert_eq!(options.formats(), vec![Format::Bin]);
+
+    options.set_formats(&[Format::Hex, Format::Bin, Format::Dec]);
+    assert_eq!(
+        options.formats(),
+        vec![Format::Dec, Format::Hex, Format::Bin]
+    );
+    assert!(options.to_string().starts_with("format=Dec,Hex,Bin "));
+
+    // set_format replaces all of them
+    options.set_format(Format::Octal);
+    assert_eq!(options.formats(), vec![Format::Octal]);
+    assert_eq!(options.format(), Format::Octal);
+
+    options.set_formats(&[]);
+    assert_eq!(options.formats(), vec![Format::Hex]);
+
+    let options = FormatOptions::builder()
+        .formats(&[Format::Base64, Format::Raw])
+        .build();
+    assert_eq!(options.formats(), vec![Format::Base64, Format::Raw]);
+
+    let options = <FormatOptions as clap::Parser>::parse_from(["numf", "-x", "-b", "-d", "1"]);
+    assert_eq!(
+        options.formats(),
+        vec![Format::Dec, Format::Hex, Format::Bin]
+    );
+}
+
+#[test]
+fn parse_raw_numbers() {
+    let mut options = <FormatOptions as clap::Parser>::parse_from([
+        "numf",
+        "0x10",
+        "17",
+        "--numbers-blob",
+        "0b11 0o7",
+    ]);
+    assert!(options.numbers().is_empty());
+    assert_eq!(options.inputs(), vec!["0x10", "17", "0b11", "0o7"]);
+    options.parse_raw_numbers().unwrap();
+    assert_eq!(options.numbers(), [16, 17, 3, 7]);
+
+    let mut options = <FormatOptions as clap::Parser>::parse_from([
+        "numf",
+        "--delimiter-in",
+        ",",
+        "--numbers-blob",
+        "1, 2",
+    ]);
+    assert_eq!(options.inputs(), vec!["1", "2"]);
+    options.parse_raw_numbers().unwrap();
+    assert_eq!(options.numbers(), [1, 2]);
+
+    let mut options = <FormatOptions as clap::Parser>::parse_from(["numf", "--text-in", "AB"]);
+    assert_eq!(options.parse_input("AB").unwrap(), vec![65, 66]);
+    options.parse_raw_numbers().unwrap();
+    assert_eq!(options.numbers(), [65, 66]);
+
+    // nothing is added if one of the numbers is broken
+    let mut options = <FormatOptions as clap::Parser>::parse_from(["numf", "1", "0xfg"]);
+    let e = options.parse_raw_numbers().unwrap_err();
+    assert!(format!("{e:#}").contains("\"0xfg\""), "{e:#}");
+    assert!(options.numbers().is_empty());
+}
+
+#[test]
+fn digit_separators() {
+    let mut options = FormatOptions::default();
+    assert!(options.parse_number("1,000,000").unwrap() != 1_000_000);
+
+    options.set_digit_separators(", '");
+    assert_eq!(options.parse_number("1,000,000").unwrap(), 1_000_000);
+    assert_eq!(options.parse_number("1 000 000").unwrap(), 1_000_000);
+    assert_eq!(options.parse_number("1'000'000").unwrap(), 1_000_000);
+    assert_eq!(options.parse_number("0xFF,FF").unwrap(), 0xFFFF);
+    assert_eq!(options.parse_number("42").unwrap(), 42);
+    // raw data is not changed, even if it contains a separator
+    assert_eq!(options.parse_bytes(&[0xFF, b',']).unwrap(), 0xFF2C);
+    assert_eq!(options.parse_bytes(b"a,b").unwrap(), 0x612C62);
+    assert_eq!(
+        options.parse_bytes(b"a,b").unwrap(),
+        FormatOptions::default().parse_bytes(b"a,b").unwrap()
+    );
+    assert!(options.to_string().ends_with(" digit_separators=\", '\""));
+    // digits of other scripts are numbers too
+    options.set_unicode_digits(true);
+    assert_eq!(options.parse_number("١,٠٠٠").unwrap(), 1000);
+}
+
+#[test]
+fn base16() {
+    let mut options = FormatOptions::default();
+    assert_eq!(Format::Base16.format_str(0xF, &options), "0F");
+    assert_eq!(Format::Base16.format_str(0, &options), "00");
+    assert_eq!(Format::Base16.format_str(0x1337, &options), "1337");
+    assert_eq!(Format::Base16.format_str(0xABCDE, &options), "0ABCDE");
+    // padding is a hex thing
+    options.set_padding(true);
+    options.set_min_digits(Some(8));
+    assert_eq!(Format::Base16.format_str(0xF, &options), "0F");
+    options.set_prefix(true);
+    assert_eq!(Format::Base16.format_str(0xF, &options), "016s0F");
+
+    assert_eq!(numf_parser_str::<u128>("016s0F").unwrap(), 0xF);
+    assert_eq!(numf_parser_str::<u128>("016sdeadbeef").unwrap(), 0xDEADBEEF);
+    assert!(numf_parser_str::<u128>("016sF").is_err());
+    assert!(numf_parser_str::<u128>("016sGG").is_err());
+    assert_eq!(numf_parser_detect(b"016s0F"), Format::Base16);
+    assert_eq!("b16".parse::<Format>().unwrap(), Format::Base16);
+}
+
+#[test]
+fn convert_formats() {
+    let mut options = FormatOptions::default();
+    assert_eq!(convert("255", Format::Hex, &options).unwrap(), "FF");
+    assert_eq!(convert("0b1010", Format::Octal, &options).unwrap(), "12");
+    assert_eq!(convert("0t1h", Format::Dec, &options).unwrap(), "3600");
+    options.set_prefix(true);
+    assert_eq!(convert("255", Format::Hex, &options).unwrap(), "0xFF");
+    options.set_from_base(Some(7));
+    assert_eq!(convert("10", Format::Dec, &options).unwrap(), "0d7");
+    assert!(convert("0x10", Format::Dec, &options).is_err());
+}
+
+#[test]
+fn percent() {
+    let mut options = FormatOptions::default();
+    assert_eq!(Format::Percent.format_str(0x1337, &options), "%13%37");
+    assert_eq!(Format::Percent.format_str(0, &options), "%00");
+    assert_eq!(Format::Percent.format_str(0xABCDEF, &options), "%AB%CD%EF");
+    options.set_prefix(true);
+    assert_eq!(Format::Percent.format_str(0x1337, &options), "0p%13%37");
+
+    assert_eq!(numf_parser_str::<u128>("%13%37").unwrap(), 0x1337);
+    assert_eq!(numf_parser_str::<u128>("0p%ab%cd").unwrap(), 0xABCD);
+    assert!(numf_parser_str::<u128>("0p%1").is_err());
+    assert!(numf_parser_str::<u128>("0p%GG").is_err());
+    assert!(numf_parser_str::<u128>("0p13").is_err());
+    assert_eq!(numf_parser_detect(b"%13"), Format::Percent);
+
+    for num in [0, 1, 0xFF, 0x100, 0xDEADBEEF, u128::MAX] {
+        let formatted = Format::Percent.format_str(num, &options);
+        assert_eq!(numf_parser_str::<u128>(&formatted).unwrap(), num);
+    }
+}
+
+#[test]
+fn pad_char() {
+    let mut options = FormatOptions::default();
+    options.set_pad_char(' ');
+    options.set_min_digits(Some(4));
+    assert_eq!(Format::Hex.format_str(0xF, &options), "   F");
+    assert_eq!(Format::Dec.format_str(42, &options), "  42");
+    assert_eq!(Format::Dec.format_str(123456, &options), "123456");
+    options.set_prefix(true);
+    // the alignment goes in front of the prefix
+    assert_eq!(Format::Hex.format_str(0xF, &options), "   0xF");
+    assert_eq!(Format::Octal.format_str(0o7, &options), "   0o7");
+
+    options.set_min_digits(None);
+    options.set_padding(true);
+    assert_eq!(Format::Hex.format_str(0xFFF, &options), " 0xFFF");
+    assert_eq!(Format::Bin.format_str(0b101, &options), "     0b101");
+    options.set_bin_group(NonZeroUsize::new(4));
+    assert_eq!(Format::Bin.format_str(0b10101, &options), "   0b1 0101");
+
+    // zeros work like before
+    options.set_pad_char('0');
+    assert_eq!(Format::Bin.format_str(0b10101, &options), "0b0001 0101");
+    assert_eq!(Format::Hex.format_str(0xFFF, &options), "0x0FFF");
+}
+
+#[test]
+fn format_radix_of() {
+    assert_eq!(Format::Bin.radix(), Some(2));
+    assert_eq!(Format::Octal.radix(), Some(8));
+    assert_eq!(Format::Dec.radix(), Some(10));
+    assert_eq!(Format::Hex.radix(), Some(16));
+    for format in Format::ALL {
+        match format.radix() {
+            // the formats with a base write the same digits as format_radix
+            Some(radix) => assert_eq!(
+                format.format_str(0x1337, &FormatOptions::default()),
+                format_radix(0x1337, radix).unwrap()
+            ),
+            None => assert!(!matches!(
+                format,
+                Format::Dec | Format::Hex | Format::Bin | Format::Octal
+            )),
+        }
+    }
+}
+
+#[test]
+fn raw_full_width() {
+    let mut options = FormatOptions::default();
+    options.set_raw_full_width(true);
+    let mut expected = vec![0; 15];
+    expected.push(0x01);
+    assert_eq!(Format::Raw.format(0x1, &options), expected);
+    assert_eq!(Format::Raw.format(0, &options), vec![0; 16]);
+    assert_eq!(Format::Raw.format(u128::MAX, &options), vec![0xFF; 16]);
+    // only raw changes
+    assert_eq!(Format::Base64.format_str(0x1, &options), "AQ==");
+
+    options.set_prefix(true);
+    for num in [0, 1, 0x1337, u128::MAX] {
+        let formatted = Format::Raw.format(num, &options);
+        assert_eq!(formatted.len(), 17);
+        assert_eq!(numf_parser::<u128>(&formatted).unwrap(), num);
+    }
+}
+
+#[test]
+fn format_from_prefix() {
+    for format in Format::ALL {
+        if format == Format::Raw {
+            continue;
+        }
+        let mut data = format.prefix().to_vec();
+        data.extend_from_slice(b"rest");
+        assert_eq!(
+            Format::from_prefix(&data),
+            Some((format, &b"rest"[..])),
+            "{format:?}"
+        );
+    }
+    // the longest prefix wins, even if a shorter one would match too
+    assert_eq!(
+        Format::from_prefix(b"032s"),
+        Some((Format::Base32, &b""[..]))
+    );
+    assert_eq!(
+        Format::from_prefix(b"016sFF"),
+        Some((Format::Base16, &b"FF"[..]))
+    );
+    assert_eq!(Format::from_prefix(b""), None);
+    assert_eq!(Format::from_prefix(b"0"), None);
+    assert_eq!(Format::from_prefix(b"032"), None);
+    assert_eq!(Format::from_prefix(&[0x00, 0x13, 0x37]), None);
+}
+
+#[test]
+fn parser_prefix_precedence() {
+    // numbers that start like a prefix, but are only digits, are decimal
+    for (data, num) in [
+        ("032", 32),
+        ("0320", 320),
+        ("016", 16),
+        ("085", 85),
+        ("00", 0),
+        ("0", 0),
+    ] {
+        assert_eq!(numf_parser_str::<u128>(data).unwrap(), num, "{data}");
+        assert_eq!(numf_parser_detect(data.as_bytes()), Format::Dec, "{data}");
+    }
+    // with the rest of the prefix, they are not decimal anymore
+    for (data, format, num) in [
+        ("032sGE======", Format::Base32, 49),
+        ("032sGE", Format::Base32, 49),
+        ("032cBM", Format::Base32Crockford, 0x5D),
+        ("016s20", Format::Base16, 32),
+        ("085s$3", Format::Base85, 10),
+        ("0d032", Format::Dec, 32),
+        ("0x032", Format::Hex, 0x32),
+        ("0o032", Format::Octal, 0o32),
+    ] {
+        assert_eq!(numf_parser_str::<u128>(data).unwrap(), num, "{data}");
+        assert_eq!(numf_parser_detect(data.as_bytes()), format, "{data}");
+    }
+    // a prefix with invalid digits is an error, not decimal or raw data
+    assert!(numf_parser_str::<u128>("032s1").is_err());
+    assert!(numf_parser_str::<u128>("0d32s").is_err());
+}
+
+#[test]
+fn format_to_writer() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_padding(true);
+    for format in Format::ALL {
+        for num in [0, 1, 0x1337, u64::MAX as u128, u128::MAX] {
+            let mut out = Vec::new();
+            format.format_to_writer(num, &options, &mut out).unwrap();
+            assert_eq!(out, format.format(num, &options), "{format:?} {num}");
+        }
+    }
+
+    // the numbers that are formatted on the stack are the same with other options, and so are
+    // the ones that need more than the buffer on the stack
+    let mut grouped = options.clone();
+    grouped.set_bin_group(NonZeroUsize::new(4));
+    grouped.set_case(Some(Case::Lower));
+    let mut aligned = FormatOptions::default();
+    aligned.set_pad_char('_');
+    aligned.set_min_digits(Some(40));
+    let mut signed = FormatOptions::default();
+    signed.set_signed(true);
+    signed.set_width(Some(64));
+    let mut wide = FormatOptions::default();
+    wide.set_min_digits(Some(1000));
+    for options in [grouped, aligned, signed, wide] {
+        for format in [
+            Format::Dec,
+            Format::Hex,
+            Format::Bin,
+            Format::Octal,
+            Format::Raw,
+        ] {
+            for num in [0, 1, 0xBEEF, u64::MAX as u128] {
+                let mut out = Vec::new();
+                format.format_to_writer(num, &options, &mut out).unwrap();
+                assert_eq!(out, format.format(num, &options), "{format:?} {num}");
+            }
+        }
+    }
+
+    // it appends to what is already written
+    let mut out = b"x".to_vec();
+    Format::Dec
+        .format_to_writer(13, &options, &mut out)
+        .unwrap();
+    assert_eq!(out, b"x0d13");
+}
+
+#[test]
+fn base64_wrap() {
+    let mut options = FormatOptions::default();
+    options.set_base64_wrap(NonZeroUsize::new(4));
+    assert_eq!(Format::Base64.format_str(0x1337, &options), "Ezc=");
+    assert_eq!(
+        Format::Base64.format_str(0x1337_1337, &options),
+        "EzcT\nNw=="
+    );
+    assert_eq!(Format::Base32.format_str(0x1337, &options), "CM3Q\n====");
+    // the prefix does not count
+    options.set_prefix(true);
+    assert_eq!(
+        Format::Base64.format_str(0x1337_1337, &options),
+        "0sEzcT\nNw=="
+    );
+    // other formats are not wrapped
+    assert_eq!(Format::Hex.format_str(0x1337_1337, &options), "0x13371337");
+
+    // a u128 is at most 24 characters in base64, so 76 never wraps
+    options.set_base64_wrap(NonZeroUsize::new(76));
+    assert!(!Format::Base64
+        .format_str(u128::MAX, &options)
+        .contains('\n'));
+
+    // wrapped output can be parsed again
+    for width in [1, 4, 7, 76] {
+        options.set_base64_wrap(NonZeroUsize::new(width));
+        for format in [Format::Base64, Format::Base32] {
+            for num in [0, 0x1337, u64::MAX as u128, u128::MAX] {
+                let formatted = format.format(num, &options);
+                assert_eq!(numf_parser::<u128>(&formatted).unwrap(), num);
+            }
+        }
+    }
+    assert_eq!(numf_parser::<u128>(b"0sEzcT\r\nNw==").unwrap(), 0x1337_1337);
+}
+
+#[test]
+fn reduce_numbers() {
+    let numbers = [0x0F, 0xF3, 0x30];
+    assert_eq!(Reduce::Sum.reduce(&numbers), Some(0x132));
+    assert_eq!(Reduce::Product.reduce(&numbers), Some(0x0F * 0xF3 * 0x30));
+    assert_eq!(Reduce::Xor.reduce(&numbers), Some(0xCC));
+    assert_eq!(Reduce::And.reduce(&numbers), Some(0x00));
+    assert_eq!(Reduce::Or.reduce(&numbers), Some(0xFF));
+    assert_eq!(Reduce::Min.reduce(&numbers), Some(0x0F));
+    assert_eq!(Reduce::Max.reduce(&numbers), Some(0xF3));
+
+    // overflow wraps around
+    assert_eq!(
+        Reduce::Sum.reduce(&[u128::MAX, u128::MAX]),
+        Some(u128::MAX - 1)
+    );
+    assert_eq!(Reduce::Product.reduce(&[1 << 127, 2]), Some(0));
+
+    // a single number stays as it is
+    for reduce in [Reduce::Sum, Reduce::Product, Reduce::Xor, Reduce::Min] {
+        assert_eq!(reduce.reduce(&[1337]), Some(1337));
+        assert_eq!(reduce.reduce(&[]), None);
+    }
+
+    let options = <FormatOptions as clap::Parser>::parse_from(["numf", "--reduce", "xor"]);
+    assert_eq!(options.reduce(), Some(Reduce::Xor));
+}
+
+#[test]
+fn bitwise_transform() {
+    let mut options = FormatOptions::default();
+    options.set_xor(Some(0xFF));
+    assert_eq!(options.transform(0x00).unwrap(), 0xFF);
+    assert_eq!(options.transform(0x0F).unwrap(), 0xF0);
+
+    let mut options = FormatOptions::default();
+    options.set_and(Some(0xF0));
+    options.set_or(Some(0x01));
+    assert_eq!(options.transform(0x3C).unwrap(), 0x31);
+
+    let mut options = FormatOptions::default();
+    options.set_not(true);
+    assert_eq!(options.transform(0).unwrap(), u128::MAX);
+    assert_eq!(options.transform(u128::MAX).unwrap(), 0);
+
+    let mut options = FormatOptions::default();
+    options.set_shl(Some(4));
+    assert_eq!(options.transform(0x13).unwrap(), 0x130);
+    // bits are shifted out
+    assert_eq!(options.transform(u128::MAX).unwrap(), u128::MAX << 4);
+    options.set_shl(None);
+    options.set_shr(Some(4));
+    assert_eq!(options.transform(0x137).unwrap(), 0x13);
+
+    // shifting by the whole width or more leaves nothing
+    for bits in [128, 129, u32::MAX] {
+        options.set_shl(Some(bits));
+        options.set_shr(None);
+        assert_eq!(options.transform(u128::MAX).unwrap(), 0);
+        options.set_shl(None);
+        options.set_shr(Some(bits));
+        assert_eq!(options.transform(u128::MAX).unwrap(), 0);
+    }
+
+    // the masks are parsed like the numbers
+    let options =
+        <FormatOptions as clap::Parser>::parse_from(["numf", "--xor", "0xFF", "--shl", "1"]);
+    assert_eq!(options.xor(), Some(0xFF));
+    assert_eq!(options.transform(0x0F).unwrap(), 0x1E0);
+}
+
+#[test]
+fn format_str_into() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    for format in Format::ALL {
+        for num in [0, 0x1337, u128::MAX] {
+            let mut s = String::new();
+            format.format_str_into(num, &options, &mut s);
+            assert_eq!(s, format.format_str(num, &options), "{format:?} {num}");
+        }
+    }
+
+    // it appends, the buffer can be reused
+    let mut s = String::from("0x13 is ");
+    Format::Dec.format_str_into(0x13, &options, &mut s);
+    assert_eq!(s, "0x13 is 0d19");
+    s.clear();
+    Format::Bin.format_str_into(5, &options, &mut s);
+    assert_eq!(s, "0b101");
+
+    // raw is converted lossily
+    s.clear();
+    Format::Raw.format_str_into(0xFF, &options, &mut s);
+    assert_eq!(s, "\0\u{FFFD}");
+}
+
+#[test]
+fn generate_random() {
+    use rand::SeedableRng;
+
+    let mut options = FormatOptions::default();
+    assert!(options.generate_random().is_empty());
+
+    options.set_rand(50);
+    options.set_rand_max(1000);
+    let a = options.generate_random_with(&mut rand::rngs::StdRng::seed_from_u64(1337));
+    let b = options.generate_random_with(&mut rand::rngs::StdRng::seed_from_u64(1337));
+    assert_eq!(a.len(), 50);
+    assert_eq!(a, b);
+    assert!(a.iter().all(|n| *n < 1000));
+
+    // the width limits them too
+    options.set_width(Some(4));
+    let numbers = options.generate_random();
+    assert_eq!(numbers.len(), 50);
+    assert!(numbers.iter().all(|n| *n < 16));
+}
+
+#[test]
+fn negabinary() {
+    let options = FormatOptions::default();
+    // OEIS A039724, the numbers 0 to 16 in base -2
+    let sequence = [
+        "0", "1", "110", "111", "100", "101", "11010", "11011", "11000", "11001", "11110", "11111",
+        "11100", "11101", "10010", "10011", "10000",
+    ];
+    for (num, expected) in sequence.iter().enumerate() {
+        assert_eq!(
+            Format::Negabinary.format_str(num as u128, &options),
+            *expected
+        );
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("0n{expected}")).unwrap(),
+            num as u128
+        );
+    }
+
+    // the largest numbers still work
+    for num in [u64::MAX as u128, u128::MAX - 1, u128::MAX] {
+        let formatted = Format::Negabinary.format_str(num, &options);
+        assert!(formatted.len() <= 129);
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("0n{formatted}")).unwrap(),
+            num
+        );
+    }
+    assert_eq!(
+        Format::Negabinary.format_str(u128::MAX, &options).len(),
+        129
+    );
+
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_min_digits(Some(8));
+    assert_eq!(Format::Negabinary.format_str(6, &options), "0n00011010");
+    assert_eq!(numf_parser_str::<u128>("0n00011010").unwrap(), 6);
+
+    // 10 is -2, and things that are not base -2 at all
+    assert!(numf_parser_str::<u128>("0n10").is_err());
+    assert!(numf_parser_str::<u128>("0n12").is_err());
+    assert!(numf_parser_str::<u128>("0n").is_err());
+    // 2^130 does not fit
+    assert!(numf_parser_str::<u128>(&format!("0n1{}", "0".repeat(130))).is_err());
+    assert_eq!(numf_parser_detect(b"0n110"), Format::Negabinary);
+}
+
+#[test]
+fn prefix_style_c() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_prefix_style(PrefixStyle::C);
+    assert_eq!(Format::Dec.format_str(420, &options), "420");
+    assert_eq!(Format::Octal.format_str(420, &options), "0644");
+    assert_eq!(Format::Hex.format_str(420, &options), "0x1A4");
+    assert_eq!(Format::Bin.format_str(5, &options), "0b101");
+    // C has nothing for these
+    assert_eq!(Format::Base64.format_str(420, &options), "0sAaQ=");
+
+    // both styles are accepted when parsing
+    for data in ["0644", "0o644", "420", "0d420", "0x1A4"] {
+        assert_eq!(options.parse_number(data).unwrap(), 420, "{data}");
+    }
+    // but only octal digits make an octal number
+    assert_eq!(options.parse_number("0").unwrap(), 0);
+    assert_eq!(options.parse_number("00").unwrap(), 0);
+    assert_eq!(options.parse_number("09").unwrap(), 9);
+    assert_eq!(options.parse_number("0d017").unwrap(), 17);
+    assert_eq!(options.parse_number("017").unwrap(), 0o17);
+
+    // the numf style keeps a leading 0 decimal
+    options.set_prefix_style(PrefixStyle::Numf);
+    assert_eq!(options.parse_number("017").unwrap(), 17);
+    assert_eq!(Format::Octal.format_str(420, &options), "0o644");
+    assert_eq!(Format::Dec.format_str(420, &options), "0d420");
+
+    let options =
+        <FormatOptions as clap::Parser>::parse_from(["numf", "--prefix-style", "c", "-p"]);
+    assert_eq!(options.prefix_style(), PrefixStyle::C);
+    assert_eq!(
+        <FormatOptions as clap::Parser>::parse_from(["numf"]).prefix_style(),
+        PrefixStyle::Numf
+    );
+}
+
+#[test]
+fn dotted() {
+    assert_eq!(format_dotted(0x0A141E28, 1, 0), "10.20.30.40");
+    assert_eq!(format_dotted(0, 1, 0), "0");
+    assert_eq!(format_dotted(0, 1, 4), "0.0.0.0");
+    assert_eq!(format_dotted(0xC0A80001, 2, 0), "49320.1");
+    // the first group is the short one
+    assert_eq!(format_dotted(0x0102030405, 2, 0), "1.515.1029");
+    assert_eq!(format_dotted(u128::MAX, 16, 0), u128::MAX.to_string());
+
+    for (group, num) in [
+        (1, 0x0A141E28),
+        (2, 0x0102030405),
+        (3, u128::MAX),
+        (16, 1337),
+    ] {
+        assert_eq!(
+            parse_dotted(&format_dotted(num, group, 0), group).unwrap(),
+            num
+        );
+    }
+    assert_eq!(parse_dotted("0.0.0.1", 1).unwrap(), 1);
+    assert!(parse_dotted("1.256", 1).is_err());
+    assert!(parse_dotted("1..2", 1).is_err());
+    assert!(parse_dotted("1.a", 1).is_err());
+    // 17 bytes
+    assert!(parse_dotted(&["1"; 17].join("."), 1).is_err());
+    assert_eq!(parse_dotted(&["0"; 17].join("."), 1).unwrap(), 0);
+
+    let mut options = FormatOptions::default();
+    options.set_dotted(Some(1));
+    assert_eq!(options.parse_number("192.168.0.1").unwrap(), 0xC0A80001);
+    assert_eq!(options.parse_number("0x10").unwrap(), 0x10);
+}
+
+#[test]
+fn parser_raw_explicit_prefix() {
+    // the prefix byte is only skipped if asked to
+    assert_eq!(numf_parser_raw::<u128>(&[0x00, 0xFF], true).unwrap(), 0xFF);
+    assert_eq!(numf_parser_raw::<u128>(&[0x00, 0xFF], false).unwrap(), 0xFF);
+    assert_eq!(numf_parser_raw::<u128>(&[0x01, 0xFF], true).unwrap(), 0xFF);
+    assert_eq!(
+        numf_parser_raw::<u128>(&[0x01, 0xFF], false).unwrap(),
+        0x01FF
+    );
+    assert_eq!(numf_parser_raw::<u128>(&[0x30], false).unwrap(), 0x30);
+    // text is not detected
+    assert_eq!(numf_parser_raw::<u128>(b"0x1", false).unwrap(), 0x307831);
+    assert!(numf_parser_raw::<u128>(&[], true).is_err());
+
+    // 16 bytes fit, 17 only with a prefix
+    let mut data = vec![0x00];
+    data.extend_from_slice(&[0xFF; 16]);
+    assert_eq!(numf_parser_raw::<u128>(&data, true).unwrap(), u128::MAX);
+    assert!(numf_parser_raw::<u128>(&[0x01; 17], false).is_err());
+    // and the type has to be large enough
+    assert!(numf_parser_raw::<u8>(&[0x13, 0x37], false).is_err());
+
+    // the auto detection still guesses the prefix
+    assert_eq!(numf_parser::<u128>(&[0x00, 0x13, 0x37]).unwrap(), 0x1337);
+    assert_eq!(numf_parser::<u128>(&[0x00]).unwrap(), 0);
+}
+
+#[test]
+fn base_zero_pad() {
+    let mut options = FormatOptions::default();
+    options.set_base_zero_pad(Some(8));
+    for format in [Format::Base64, Format::Base32] {
+        let lengths: Vec<usize> = [0, 1, 0x1337, u32::MAX as u128, u64::MAX as u128]
+            .iter()
+            .map(|num| format.format(*num, &options).len())
+            .collect();
+        assert!(lengths.iter().all(|l| *l == lengths[0]), "{format:?}");
+    }
+    assert_eq!(Format::Base64.format_str(1, &options), "AAAAAAAAAAE=");
+    assert_eq!(Format::Base32.format_str(1, &options), "AAAAAAAAAAAAC===");
+    // larger numbers are not cut
+    assert_eq!(
+        Format::Base64.format_str(u128::MAX, &options),
+        Format::Base64.format_str(u128::MAX, &FormatOptions::default())
+    );
+    // other formats are not padded
+    assert_eq!(Format::Hex.format_str(1, &options), "1");
+
+    // the leading zero bytes are gone when parsing
+    options.set_prefix(true);
+    for format in [Format::Base64, Format::Base32] {
+        for num in [0, 1, 0x1337, u64::MAX as u128] {
+            let formatted = format.format(num, &options);
+            assert_eq!(numf_parser::<u128>(&formatted).unwrap(), num);
+        }
+    }
+}
+
+#[test]
+fn morse() {
+    let mut options = FormatOptions::default();
+    assert_eq!(Format::Morse.format_str(0, &options), "-----");
+    assert_eq!(Format::Morse.format_str(13, &options), ".---- ...--");
+    assert_eq!(
+        Format::Morse.format_str(1234567890, &options),
+        ".---- ..--- ...-- ....- ..... -.... --... ---.. ----. -----"
+    );
+    options.set_prefix(true);
+    assert_eq!(Format::Morse.format_str(42, &options), "0m....- ..---");
+
+    for num in [0, 7, 1337, u128::MAX] {
+        let formatted = Format::Morse.format(num, &options);
+        assert_eq!(numf_parser::<u128>(&formatted).unwrap(), num);
+    }
+    // the prefix is optional, and the spaces between the digits do not matter
+    assert_eq!(numf_parser_str::<u128>(".---- ...--").unwrap(), 13);
+    assert_eq!(numf_parser_str::<u128>("  .----   ...-- ").unwrap(), 13);
+    assert_eq!(numf_parser_detect(b".---- ...--"), Format::Morse);
+    // a number is one word, and only digits are known
+    assert!(numf_parser_str::<u128>("0m.---- / ...--").is_err());
+    assert!(numf_parser_str::<u128>("0m.-").is_err());
+    assert!(numf_parser_str::<u128>("0m").is_err());
+    assert!(numf_parser_strict::<u128>(b".---- / ...--").is_err());
+
+    // lines of morse code on the stdin are kept together
+    assert_eq!(
+        split_input(b"13 .---- ...--\n.---- ...--\n0x13"),
+        vec![&b"13"[..], b".----", b"...--", b".---- ...--", b"0x13"]
+    );
+
+    // also with the prefix, so that the output of -p can be read again
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    let data = format!(
+        "{}\n{}\n",
+        Format::Morse.format_str(1337, &options),
+        Format::Morse.format_str(42, &options)
+    );
+    let parts = split_input(data.as_bytes());
+    assert_eq!(
+        parts,
+        vec![&b"0m.---- ...-- ...-- --..."[..], b"0m....- ..---"]
+    );
+    let numbers: Vec<u128> = parts
+        .iter()
+        .map(|part| options.parse_bytes(part).unwrap())
+        .collect();
+    assert_eq!(numbers, vec![1337, 42]);
+}
+
+#[test]
+fn format_canonical_names() {
+    for format in Format::ALL {
+        let name = format.name();
+        assert_eq!(name, name.to_lowercase());
+        assert_eq!(name.parse::<Format>().unwrap(), format);
+        let from: &'static str = format.into();
+        assert_eq!(from, name);
+    }
+    assert_eq!(Format::Dec.name(), "dec");
+    assert_eq!(Format::Base64.name(), "base64");
+    assert_eq!(<&str>::from(Format::Hex), "hex");
+}
+
+#[test]
+fn no_raw_prefix() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_no_raw_prefix(true);
+    assert_eq!(Format::Raw.format(0x1337, &options), [0x13, 0x37]);
+    assert_eq!(Format::Raw.format(0, &options), [0x00]);
+    // the text formats keep their prefixes
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "0x1337");
+
+    options.set_no_raw_prefix(false);
+    assert_eq!(Format::Raw.format(0x1337, &options), [0x00, 0x13, 0x37]);
+
+    // without the prefix, the data can be read back exactly
+    options.set_no_raw_prefix(true);
+    for num in [0, 0xFF, 0x1337, u128::MAX] {
+        let formatted = Format::Raw.format(num, &options);
+        assert_eq!(numf_parser_raw::<u128>(&formatted, false).unwrap(), num);
+    }
+}
+
+#[test]
+fn count_zeros() {
+    for width in [1, 8, 64, 128] {
+        assert_eq!(clz(0, width).unwrap(), width);
+        assert_eq!(ctz(0, width).unwrap(), width);
+        assert_eq!(clz(1, width).unwrap(), width - 1);
+        assert_eq!(ctz(1, width).unwrap(), 0);
+    }
+    assert_eq!(clz(0x1337, 16).unwrap(), 3);
+    assert_eq!(ctz(0x1330, 16).unwrap(), 4);
+    assert_eq!(clz(u128::MAX, 128).unwrap(), 0);
+    assert_eq!(ctz(1 << 127, 128).unwrap(), 127);
+
+    assert!(clz(0x100, 8).is_err());
+    assert!(ctz(0x100, 8).is_err());
+    assert!(clz(1, 0).is_err());
+    assert!(ctz(1, 129).is_err());
+}
+
+#[test]
+fn popcount_of() {
+    assert_eq!(popcount(0x1337), 8);
+    assert_eq!(popcount(1 << 127), 1);
+    assert_eq!(popcount(u64::MAX as u128), 64);
+
+    // the count is formatted like a number
+    let options = FormatOptions::default();
+    assert_eq!(
+        Format::Bin.format_str(popcount(u128::MAX), &options),
+        "10000000"
+    );
+}
+
+#[test]
+fn custom_alphabet() {
+    assert_eq!(format_alphabet(0, "01").unwrap(), "0");
+    assert_eq!(format_alphabet(0x1337, "0123456789ABCDEF").unwrap(), "1337");
+    assert_eq!(format_alphabet(5, "ab").unwrap(), "bab");
+    assert_eq!(format_alphabet(u128::MAX, "01").unwrap(), "1".repeat(128));
+    assert_eq!(numf_parser_alphabet("bab", "ab").unwrap(), 5);
+    assert_eq!(numf_parser_alphabet("ÿé", "éÿ").unwrap(), 2);
+    assert!(numf_parser_alphabet("bac", "ab").is_err());
+    assert!(numf_parser_alphabet("", "ab").is_err());
+    assert!(numf_parser_alphabet(&"b".repeat(129), "ab").is_err());
+
+    // too short, or with duplicates
+    assert!(format_alphabet(1, "").is_err());
+    assert!(format_alphabet(1, "a").is_err());
+    assert!(format_alphabet(1, "aba").is_err());
+
+    let mut options = FormatOptions::default();
+    assert!(options.set_from_alphabet(Some("aa")).is_err());
+    options.set_from_alphabet(Some("xyz")).unwrap();
+    assert_eq!(options.parse_bytes(b"zyx").unwrap(), 21);
+}
+
+#[test]
+fn invert_bits() {
+    assert_eq!(invert(0x00FF, 16).unwrap(), 0xFF00);
+    assert_eq!(invert(0b101, 3).unwrap(), 0b010);
+    assert_eq!(invert(u128::MAX, 128).unwrap(), 0);
+    assert!(invert(0x10000, 16).is_err());
+    assert!(invert(1, 0).is_err());
+
+    let mut options = FormatOptions::default();
+    options.set_invert(Some(8));
+    assert_eq!(options.transform(0x0F).unwrap(), 0xF0);
+    assert!(options.transform(0x100).is_err());
+}
+
+#[test]
+fn numbers_blob() {
+    let mut options = FormatOptions::default();
+    options.set_numbers_blob(Some("0x10 0b11\n\t0o7\n\n1337".to_string()));
+    assert_eq!(options.inputs(), vec!["0x10", "0b11", "0o7", "1337"]);
+    options.parse_raw_numbers().unwrap();
+    assert_eq!(options.numbers(), [16, 3, 7, 1337]);
+
+    // the blob is split like the stdin, so morse code is kept together
+    options.set_numbers_blob(Some("0m.---- ...--\n.---- ..---\n2".to_string()));
+    assert_eq!(options.inputs(), vec!["0m.---- ...--", ".---- ..---", "2"]);
+    options.set_numbers(vec![]);
+    options.parse_raw_numbers().unwrap();
+    assert_eq!(options.numbers(), [13, 12, 2]);
+
+    for empty in ["", "  \n "] {
+        options.set_numbers_blob(Some(empty.to_string()));
+        assert!(options.inputs().is_empty());
+    }
+    options.set_numbers_blob(Some("1 0xfg".to_string()));
+    assert!(options.parse_raw_numbers().is_err());
+}
+
+#[test]
+fn ctype_width() {
+    assert!(CType::U8.check(0xFF).is_ok());
+    assert!(CType::U8.check(0x100).is_err());
+    assert!(CType::U128.check(u128::MAX).is_ok());
+
+    let mut options = FormatOptions::default();
+    options.set_ctype(Some(CType::U16));
+    assert_eq!(Format::Raw.format(0x1337, &options), [0x13, 0x37]);
+    assert_eq!(Format::Raw.format(0x37, &options), [0x00, 0x37]);
+    assert_eq!(Format::Hex.format(0x37, &options), b"0037");
+    assert_eq!(Format::Bin.format(1, &options), b"0000000000000001");
+    options.set_ctype(Some(CType::U32));
+    assert_eq!(Format::Raw.format(0x1337, &options), [0, 0, 0x13, 0x37]);
+}
+
+#[test]
+fn zeckendorf() {
+    let options = FormatOptions::default();
+    let known = [
+        (0, "0"),
+        (1, "1"),
+        (2, "10"),
+        (3, "100"),
+        (4, "101"),
+        (5, "1000"),
+        (6, "1001"),
+        (7, "1010"),
+        (8, "10000"),
+        (12, "10101"),
+        (100, "1000010100"),
+    ];
+    for (num, repr) in known {
+        assert_eq!(Format::Zeckendorf.format_str(num, &options), repr);
+        assert_eq!(
+            numf_parser_str::<u128>(&format!("0z{repr}")).unwrap(),
+            num,
+            "{repr}"
+        );
+    }
+    assert_eq!(numf_parser_str::<u128>("0z000101").unwrap(), 4);
+    let max = Format::Zeckendorf.format_str(u128::MAX, &options);
+    assert!(!max.contains("11"));
+    assert_eq!(
+        numf_parser_str::<u128>(&format!("0z{max}")).unwrap(),
+        u128::MAX
+    );
+
+    // the Zeckendorf representation is unique, so there are no consecutive ones
+    assert!(numf_parser_str::<u128>("0z11").is_err());
+    assert!(numf_parser_str::<u128>("0z1011").is_err());
+    assert!(numf_parser_str::<u128>("0z102").is_err());
+    assert!(numf_parser_str::<u128>("0z").is_err());
+    assert!(numf_parser_str::<u128>(&format!("0z1{}", "0".repeat(200))).is_err());
+}
+
+#[test]
+fn format_parse() {
+    let mut options = FormatOptions::default();
+    for format in Format::ALL {
+        for num in [0, 1, 0x1337, 0xDEADBEEF, u64::MAX as u128, u128::MAX] {
+            options.set_prefix(false);
+            let formatted = format.format(num, &options);
+            assert_eq!(format.parse(&formatted).unwrap(), num, "{format} {num}");
+            options.set_prefix(true);
+            let formatted = format.format(num, &options);
+            assert_eq!(format.parse(&formatted).unwrap(), num, "{format} {num}");
+        }
+    }
+
+    // the format is not detected, so this is hexadecimal and not decimal
+    assert_eq!(Format::Hex.parse(b"10").unwrap(), 16);
+    assert_eq!(Format::Dec.parse(b"10").unwrap(), 10);
+    assert_eq!(Format::Duration.parse(b"1m").unwrap(), 60);
+    assert_eq!(Format::Raw.parse(b"10").unwrap(), 0x3130);
+    assert!(Format::Bin.parse(b"0x10").is_err());
+    assert!(Format::Dec.parse(b"").is_err());
+    assert!(Format::Hex.parse(&[0xFF]).is_err());
+}
+
+#[test]
+fn parse_input_format() {
+    let mut options = FormatOptions::default();
+    options.set_from(Some(Format::Base64));
+    assert_eq!(options.parse_bytes(b"QUFCQg==").unwrap(), 0x41414242);
+    assert_eq!(options.parse_bytes(b"QUFCQg").unwrap(), 0x41414242);
+    assert_eq!(options.parse_bytes(b"0sQUFCQg==").unwrap(), 0x41414242);
+    // this would be decimal without the format
+    assert_eq!(options.parse_bytes(b"1234").unwrap(), 0xD76DF8);
+    assert!(options.parse_bytes(b"QUFCQg=!").is_err());
+
+    options.set_from(Some(Format::Base32));
+    assert_eq!(options.parse_bytes(b"IFAUEQQ=").unwrap(), 0x41414242);
+    assert_eq!(options.parse_bytes(b"IFAUEQQ").unwrap(), 0x41414242);
+
+    options.set_from(Some(Format::Hex));
+    assert_eq!(options.parse_bytes(b"10").unwrap(), 16);
+
+    options.set_from(None);
+    assert_eq!(options.parse_bytes(b"10").unwrap(), 10);
+}
+
+#[test]
+fn emoji_alphabet() {
+    // some of these are made of several chars, but they are still one digit each
+    let alphabet = "🙂👍🏽🏳️‍🌈é";
+    for num in [0, 1, 2, 3, 4, 0x1337, u128::MAX] {
+        let formatted = format_alphabet(num, alphabet).unwrap();
+        assert_eq!(numf_parser_alphabet(&formatted, alphabet).unwrap(), num);
+    }
+    assert_eq!(format_alphabet(4, alphabet).unwrap(), "👍🏽🙂");
+    assert_eq!(numf_parser_alphabet("🏳️‍🌈é", alphabet).unwrap(), 11);
+    // the thumb without the skin tone is a different symbol
+    assert!(numf_parser_alphabet("👍", alphabet).is_err());
+    assert!(format_alphabet(1, "👍🏽👍🏽").is_err());
+}
+
+#[test]
+fn base_group() {
+    let mut options = FormatOptions::default();
+    options.set_base_group(Some("4:-".parse().unwrap()));
+    assert_eq!(Format::Base64.format_str(0x41414242, &options), "QUFC-Qg==");
+    assert_eq!(Format::Base32.format_str(0x41414242, &options), "IFAU-EQQ=");
+    options.set_strip_base_padding(true);
+    assert_eq!(Format::Base64.format_str(0x41414242, &options), "QUFC-Qg");
+    options.set_strip_base_padding(false);
+
+    // grouped output can be read again
+    options.set_prefix(true);
+    for num in [0, 0x1337, 0x41414242, u128::MAX] {
+        for format in [Format::Base64, Format::Base32] {
+            let formatted = format.format_str(num, &options);
+            assert_eq!(
+                options.parse_number(&formatted).unwrap(),
+                num,
+                "{formatted}"
+            );
+        }
+    }
+    options.set_from(Some(Format::Base64));
+    assert_eq!(options.parse_number("QUFC-Qg==").unwrap(), 0x41414242);
+
+    // other formats are not changed
+    options.set_base_group(Some("2: ".parse().unwrap()));
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "0x1337");
+
+    assert!("4".parse::<BaseGroup>().is_err());
+    assert!("x:-".parse::<BaseGroup>().is_err());
+    assert!("4:=".parse::<BaseGroup>().is_err());
+    assert!("0:-".parse::<BaseGroup>().is_err());
+    let group = BaseGroup::new(NonZeroUsize::new(3).unwrap(), "_").unwrap();
+    assert_eq!(group.to_string(), "3:_");
+    assert!(BaseGroup::new(NonZeroUsize::MIN, "").is_err());
+
+    // --verify reads the groups like the parser does
+    options.set_base_group(Some(group));
+    options.set_from(None);
+    assert!(options.to_string().contains(" base_group=3:_"));
+    for format in [Format::Base64, Format::Base32] {
+        assert!(options.verify_number(format, 0x41414242).is_ok());
+    }
+}
+
+#[test]
+fn parser_hash_hex() {
+    assert_eq!(numf_parser_str::<u128>("#ffffff").unwrap(), 0xffffff);
+    assert_eq!(numf_parser_str::<u128>("#1a2b3c").unwrap(), 0x1a2b3c);
+    assert_eq!(numf_parser_str::<u32>("#FFF").unwrap(), 0xfff);
+    assert_eq!(numf_parser_detect(b"#ffffff"), Format::Hex);
+    // without valid digits, it is still raw data
+    assert_eq!(numf_parser_str::<u128>("#fg").unwrap(), 0x236667);
+    assert_eq!(numf_parser_detect(b"#"), Format::Raw);
+    assert!(numf_parser_strict::<u128>(b"#fg").is_err());
+}
+
+#[test]
+fn signed_parsing() {
+    let mut options = FormatOptions::default();
+    options.set_signed(true);
+    options.set_width(Some(16));
+    assert_eq!(options.parse_number("-1").unwrap(), 0xFFFF);
+    assert_eq!(options.parse_number("-32768").unwrap(), 0x8000);
+    assert_eq!(options.parse_number("-0x10").unwrap(), 0xFFF0);
+    assert_eq!(options.parse_number("0xFFFF").unwrap(), 0xFFFF);
+    assert!(options.parse_number("-32769").is_err());
+    assert!(options.parse_number("0x10000").is_err());
+    // decimal numbers are values, not bit patterns like hex
+    assert_eq!(options.parse_number("32767").unwrap(), 0x7FFF);
+    assert!(options.parse_number("32768").is_err());
+    assert!(options.parse_number("40000").is_err());
+    assert!(options.parse_number("0d40000").is_err());
+    assert_eq!(options.parse_number("0x8000").unwrap(), 0x8000);
+    assert_eq!(options.parse_number("0b1000000000000000").unwrap(), 0x8000);
+
+    // decimal output has the sign, the other formats show the bits
+    assert_eq!(Format::Dec.format_str(0xFFFF, &options), "-1");
+    assert_eq!(Format::Dec.format_str(0x7FFF, &options), "32767");
+    assert_eq!(Format::Hex.format_str(0xFFFF, &options), "FFFF");
+    options.set_prefix(true);
+    assert_eq!(Format::Dec.format_str(0x8000, &options), "-0d32768");
+    let mut buf = [0; 16];
+    let len = Format::Dec
+        .format_slice(0x8000, &options, &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..len], b"-0d32768");
+    assert_eq!(Format::Dec.predicted_len(0x8000, &options), len);
+    // the output can be read again
+    for num in [0x8000, 0xFFFF, 0, 1, 0x7FFF] {
+        let formatted = Format::Dec.format_str(num, &options);
+        assert_eq!(options.parse_number(&formatted).unwrap(), num);
+    }
+
+    // like hex from some other program
+    options.set_prefix(false);
+    options.set_from(Some(Format::Hex));
+    let num = options.parse_number("FFFE").unwrap();
+    assert_eq!(Format::Dec.format_str(num, &options), "-2");
+
+    // without a width, there is no sign
+    options.set_width(None);
+    assert!(options.parse_number("-1").is_err());
+    assert_eq!(Format::Dec.format_str(0xFFFF, &options), "65535");
+}
+
+#[test]
+fn split_by_delimiter() {
+    assert_eq!(
+        split_input_by(b"1,2, 0x3 ,\n4,5\r\n", ','),
+        vec![&b"1"[..], b"2", b"0x3", b"4", b"5"]
+    );
+    assert_eq!(split_input_by(b"1;;2;", ';'), vec![&b"1"[..], b"2"]);
+    assert_eq!(split_input_by(b"", ','), Vec::<&[u8]>::new());
+    // morse code is not split at its spaces
+    assert_eq!(
+        split_input_by(b".---- ..---,...--", ','),
+        vec![&b".---- ..---"[..], b"...--"]
+    );
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_table() {
+    use numf::csv_io::Table;
+
+    let table = Table::read(b"id,\"the, value\"\n1,\"0x10\"\n2\n3,0b11\n", 2).unwrap();
+    assert_eq!(table.cells(), vec!["0x10", "", "0b11"]);
+    assert!(Table::read(b"id,value\n1,2\n", 3).is_err());
+    assert!(Table::read(b"id,value\n1,2\n", 0).is_err());
+
+    let mut options = FormatOptions::default();
+    options.set_formats(&[Format::Dec, Format::Hex]);
+    options.set_prefix(true);
+    let mut out = Vec::new();
+    table
+        .write(&mut out, &options, &[Some(16), None, Some(3)])
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "id,Dec,Hex\n1,0d16,0x10\n2\n3,0d3,0x3\n"
+    );
+}
+
+#[test]
+fn digits_fast_path() {
+    let mut numbers: Vec<NumberType> = (0..300).collect();
+    numbers.extend([
+        0xDEAD_BEEF,
+        u64::MAX as NumberType - 1,
+        u64::MAX as NumberType,
+        u64::MAX as NumberType + 1,
+        u128::MAX,
+    ]);
+    for num in numbers {
+        assert_eq!(format_digits::<16>(num), format!("{num:X}"));
+        assert_eq!(format_digits::<10>(num), format!("{num}"));
+        assert_eq!(format_digits::<8>(num), format!("{num:o}"));
+        assert_eq!(format_digits::<2>(num), format!("{num:b}"));
+    }
+}
+
+#[test]
+fn differing_bits() {
+    assert_eq!(bit_ranges(0x12 ^ 0x13), "0");
+    assert_eq!(bit_ranges(0xF0F0), "4-7, 12-15");
+    assert_eq!(bit_ranges(u128::MAX), "0-127");
+    assert_eq!(bit_ranges(u128::MAX - 1), "1-127");
+    assert_eq!(bit_ranges((1 << 127) | 1), "0, 127");
+    assert_eq!(bit_ranges(0), "");
+}
+
+#[test]
+fn parser_scientific() {
+    assert_eq!(numf_parser_str::<u128>("0d1e6").unwrap(), 1_000_000);
+    assert_eq!(numf_parser_str::<u128>("0d15e3").unwrap(), 15_000);
+    assert_eq!(numf_parser_str::<u128>("0d1.5e1").unwrap(), 15);
+    assert_eq!(numf_parser_str::<u128>("0d2.50E+2").unwrap(), 250);
+    assert_eq!(numf_parser_str::<u128>("0d0.0e99").unwrap(), 0);
+    assert_eq!(numf_parser_str::<u128>("0d1e38").unwrap(), 10u128.pow(38));
+    assert_eq!(numf_parser_detect(b"0d1e6"), Format::Dec);
+    let mut options = FormatOptions::default();
+    options.set_from(Some(Format::Dec));
+    assert_eq!(options.parse_number("1e6").unwrap(), 1_000_000);
+    // not whole numbers
+    assert!(numf_parser_str::<u128>("0d1.5e0").is_err());
+    assert!(numf_parser_str::<u128>("0d1e-1").is_err());
+    assert!(numf_parser_str::<u128>("0d1e-9223372036854775808").is_err());
+    assert!(numf_parser_str::<u128>("0d1.5e-9223372036854775808").is_err());
+    // too large
+    assert!(numf_parser_str::<u128>("0d1e39").is_err());
+    assert!(numf_parser_str::<u128>("0d1e99999999999999999999").is_err());
+    // without the prefix, it's raw data like before
+    for raw in ["1e6", "1e40", "1.5e1"] {
+        assert_eq!(numf_parser_detect(raw.as_bytes()), Format::Raw);
+        assert_eq!(
+            numf_parser_str::<u128>(raw).unwrap(),
+            numf_parser_raw::<u128>(raw.as_bytes(), false).unwrap()
+        );
+    }
+    assert!(numf_parser_strict::<u128>(b"1e6").is_err());
+    // hexadecimal digits are still hexadecimal
+    assert_eq!(numf_parser_str::<u128>("0x1e6").unwrap(), 0x1E6);
+}
+
+#[test]
+fn raw_minimal() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_raw_full_width(true);
+    options.set_raw_minimal(true);
+    assert_eq!(Format::Raw.format(0x1337, &options), vec![0x13, 0x37]);
+    assert_eq!(Format::Raw.format(0, &options), vec![0x00]);
+    assert_eq!(Format::Raw.format(u128::MAX, &options), vec![0xFF; 16]);
+    // the other formats keep their prefix
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "0x1337");
+
+    // the minimal bytes of 0x1337 are read back as 0x1337
+    let bytes = Format::Raw.format(0x1337, &options);
+    assert_eq!(numf_parser::<u128>(&bytes).unwrap(), 0x1337);
+}
+
+#[test]
+fn predicted_len() {
+    let numbers: [NumberType; 12] = [
+        0,
+        1,
+        7,
+        0xFF,
+        0x100,
+        0x1337,
+        86_400 * 8 + 61,
+        0xFFFF_FFFF,
+        0x1_0000_0000,
+        u64::MAX as NumberType,
+        1 << 100,
+        u128::MAX,
+    ];
+    let mut variants: Vec<FormatOptions> = Vec::new();
+    for bits in 0..8u8 {
+        let mut options = FormatOptions::default();
+        options.set_prefix(bits & 1 != 0);
+        options.set_padding(bits & 2 != 0);
+        if bits & 4 != 0 {
+            options.set_min_digits(Some(20));
+        }
+        variants.push(options);
+    }
+    let mut options = FormatOptions::default();
+    options.set_pad_char('·');
+    options.set_min_digits(Some(12));
+    options.set_prefix(true);
+    options.set_bin_group(NonZeroUsize::new(4));
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_strip_base_padding(true);
+    options.set_base_zero_pad(Some(7));
+    options.set_base_group(Some("3:--".parse().unwrap()));
+    options.set_clock(true);
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_base64_wrap(NonZeroUsize::new(5));
+    options.set_raw_full_width(true);
+    options.set_prefix_style(PrefixStyle::C);
+    options.set_prefix(true);
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_ctype(Some(CType::U128));
+    options.set_le_display(Some(128));
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_signed(true);
+    options.set_width(Some(64));
+    options.set_raw_minimal(true);
+    options.set_prefix(true);
+    variants.push(options);
+
+    for options in &variants {
+        for format in Format::ALL {
+            for num in numbers {
+                assert_eq!(
+                    format.predicted_len(num, options),
+                    format.format(num, options).len(),
+                    "{format} of {num} with{options}"
+                );
+            }
+        }
+    }
+
+    let mut options = FormatOptions::default();
+    options.set_format(Format::Base32);
+    assert_eq!(options.predicted_len(0x1337), 8);
+}
+
+#[test]
+fn formattable_newtype() {
+    struct Address(u32);
+    impl Formattable for Address {
+        fn to_number(&self) -> NumberType {
+            self.0 as NumberType
+        }
+    }
+
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    let address = Address(0xC0A8_0001);
+    for format in Format::ALL {
+        assert_eq!(
+            format.format_value(&address, &options),
+            format.format(0xC0A8_0001, &options)
+        );
+    }
+    assert_eq!(
+        Format::Hex.format_value_str(&address, &options),
+        "0xC0A80001"
+    );
+    assert_eq!(address.to_be_bytes_minimal(), vec![0xC0, 0xA8, 0x00, 0x01]);
+    assert_eq!(Format::Dec.format_value_str(&7u8, &options), "0d7");
+    assert_eq!(usize::MAX.to_number(), usize::MAX as NumberType);
+}
+
+#[test]
+fn c_octal_precedence() {
+    let mut options = FormatOptions::default();
+    options.set_prefix_style(PrefixStyle::C);
+    // a single 0 and 0 with decimal digits stay decimal
+    assert_eq!(options.parse_number("0").unwrap(), 0);
+    assert_eq!(options.detect_format(b"0"), Format::Dec);
+    assert_eq!(options.parse_number("08").unwrap(), 8);
+    assert_eq!(options.detect_format(b"08"), Format::Dec);
+    // the prefixes of numf win over the 0 of C
+    assert_eq!(options.parse_number("0d10").unwrap(), 10);
+    assert_eq!(options.detect_format(b"0d10"), Format::Dec);
+    assert_eq!(options.parse_number("0o10").unwrap(), 8);
+    assert_eq!(options.parse_number("0b10").unwrap(), 2);
+    assert_eq!(options.parse_number("0x10").unwrap(), 16);
+    // only octal digits after the 0 make it octal
+    assert_eq!(options.parse_number("010").unwrap(), 8);
+    assert_eq!(options.parse_number("0000755").unwrap(), 0o755);
+    assert_eq!(options.detect_format(b"0755"), Format::Octal);
+    assert_eq!(options.detect_format(b"0e3"), Format::Raw);
+    // --from wins over everything
+    options.set_from(Some(Format::Hex));
+    assert_eq!(options.detect_format(b"0755"), Format::Hex);
+
+    // the output reads back as the same number
+    options.set_from(None);
+    options.set_prefix(true);
+    for num in [0, 7, 8, 0o644, u128::MAX] {
+        let octal = Format::Octal.format_str(num, &options);
+        assert_eq!(options.parse_number(&octal).unwrap(), num, "{octal}");
+    }
+
+    // zeros in front of a decimal number would make it octal, so it is padded with spaces
+    options.set_min_digits(Some(4));
+    assert_eq!(Format::Dec.format_str(17, &options), "  17");
+    assert_eq!(Format::Dec.predicted_len(17, &options), 4);
+    let mut buf = [0u8; 8];
+    let len = Format::Dec.format_slice(17, &options, &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"  17");
+    assert_eq!(Format::Octal.format_str(15, &options), "00017");
+    assert_eq!(Format::Hex.format_str(17, &options), "0x0011");
+    for num in [0, 7, 17, 0o644] {
+        options.verify_number(Format::Dec, num).unwrap();
+        let dec = Format::Dec.format_str(num, &options);
+        assert_eq!(options.parse_number(dec.trim()).unwrap(), num, "{dec}");
+    }
+    // other pad chars and the numf style are not changed
+    options.set_pad_char('_');
+    assert_eq!(Format::Dec.format_str(17, &options), "__17");
+    options.set_pad_char('0');
+    options.set_prefix_style(PrefixStyle::Numf);
+    assert_eq!(Format::Dec.format_str(17, &options), "0d0017");
+}
+
+#[test]
+fn shuffle_seeded() {
+    let mut options = FormatOptions::default();
+    options.set_seed(Some(42));
+    options.set_rand(20);
+    options.set_numbers((0..100).collect());
+
+    let mut a = options.clone();
+    let mut rng = a.rng();
+    let random = a.generate_random_with(&mut rng);
+    random.into_iter().for_each(|n| a.push_number(n));
+    a.shuffle_numbers_with(&mut rng);
+
+    let mut b = options.clone();
+    let mut rng = b.rng();
+    let random = b.generate_random_with(&mut rng);
+    random.into_iter().for_each(|n| b.push_number(n));
+    b.shuffle_numbers_with(&mut rng);
+
+    assert_eq!(a.numbers(), b.numbers());
+    assert_eq!(a.numbers().len(), 120);
+    assert_ne!(&a.numbers()[..100], (0..100).collect::<Vec<_>>().as_slice());
+
+    // all numbers are still there
+    let mut shuffled = a.numbers().to_vec();
+    shuffled.retain(|n| *n < 100);
+    shuffled.sort();
+    assert!((0..100).all(|n| shuffled.binary_search(&n).is_ok()));
+
+    // another seed gives another order
+    let mut c = options.clone();
+    c.set_seed(Some(43));
+    c.shuffle_numbers_with(&mut c.rng());
+    assert_ne!(c.numbers(), options.numbers());
+}
+
+#[test]
+fn unicode_digits() {
+    let mut options = FormatOptions::default();
+    options.set_strict(true);
+    assert!(options.parse_number("１２３").is_err());
+
+    options.set_unicode_digits(true);
+    assert_eq!(options.parse_number("１２３").unwrap(), 123);
+    assert_eq!(options.parse_number("०१२३४५६७८९").unwrap(), 123_456_789);
+    assert_eq!(options.parse_number("𝟏𝟎").unwrap(), 10);
+    // fullwidth letters are not digits
+    assert!(options.parse_number("0x１ｆ").is_err());
+    assert_eq!(options.parse_number("0x１F").unwrap(), 0x1F);
+    // other characters are kept, so this is still not a number
+    assert!(options.parse_number("１２a").is_err());
+    assert_eq!(normalize_digits("٣.١٤"), "3.14");
+}
+
+#[test]
+fn format_into_slice() {
+    let numbers: [NumberType; 7] = [0, 1, 0xFF, 0x1337, 0xFFFF_FFFF, 1 << 100, u128::MAX];
+    let mut variants = vec![FormatOptions::default()];
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_padding(true);
+    options.set_min_digits(Some(9));
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    options.set_pad_char('·');
+    options.set_min_digits(Some(40));
+    options.set_bin_group(NonZeroUsize::new(4));
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_signed(true);
+    options.set_width(Some(128));
+    options.set_prefix(true);
+    options.set_raw_full_width(true);
+    variants.push(options);
+    let mut options = FormatOptions::default();
+    options.set_ctype(Some(CType::U128));
+    options.set_le_display(Some(128));
+    options.set_prefix(true);
+    options.set_prefix_style(PrefixStyle::C);
+    variants.push(options);
+
+    let mut buf = [0u8; 512];
+    for options in &variants {
+        for format in [
+            Format::Hex,
+            Format::Bin,
+            Format::Octal,
+            Format::Dec,
+            Format::Raw,
+        ] {
+            for num in numbers {
+                let len = format.format_slice(num, options, &mut buf).unwrap();
+                assert_eq!(
+                    &buf[..len],
+                    format.format(num, options).as_slice(),
+                    "{format} of {num} with{options}"
+                );
+            }
+        }
+    }
+
+    // nothing is written if it does not fit
+    let options = FormatOptions::default();
+    let mut small = [b'?'; 3];
+    assert_eq!(
+        Format::Dec.format_slice(1337, &options, &mut small),
+        Err(NumfError::BufferTooSmall {
+            needed: 4,
+            capacity: 3
+        })
+    );
+    assert_eq!(small, [b'?'; 3]);
+    assert_eq!(Format::Dec.format_slice(133, &options, &mut small), Ok(3));
+    assert_eq!(&small, b"133");
+    assert_eq!(
+        Format::Morse.format_slice(1, &options, &mut buf),
+        Err(NumfError::NeedsAllocation(Format::Morse))
+    );
+}
+
+#[test]
+fn text_in() {
+    let mut options = FormatOptions::default();
+    let numbers = text_to_numbers(b"AB", TextIn::Bytes).unwrap();
+    assert_eq!(numbers, vec![65, 66]);
+    let dec: Vec<String> = numbers
+        .iter()
+        .map(|n| Format::Dec.format_str(*n, &options))
+        .collect();
+    assert_eq!(dec, vec!["65", "66"]);
+
+    options.set_prefix(true);
+    let combined = text_to_numbers(b"AB", TextIn::Combined).unwrap();
+    assert_eq!(combined, vec![0x4142]);
+    assert_eq!(Format::Hex.format_str(combined[0], &options), "0x4142");
+
+    assert_eq!(text_to_numbers(b"", TextIn::Combined).unwrap(), vec![]);
+    assert!(text_to_numbers(&[b'x'; 17], TextIn::Combined).is_err());
+}
+
+#[test]
+fn check_digits() {
+    let mut options = FormatOptions::default();
+    options.set_check(Some(Check::Luhn));
+    assert_eq!(Format::Dec.format_str(7992739871, &options), "79927398713");
+    // credit card style numbers
+    assert_eq!(
+        Format::Dec.format_str(411111111111111, &options),
+        "4111111111111111"
+    );
+    assert_eq!(
+        Format::Dec.format_str(555555555555444, &options),
+        "5555555555554444"
+    );
+    assert_eq!(Format::Dec.format_str(0, &options), "00");
+    // only decimal has check digits
+    assert_eq!(Format::Hex.format_str(0x1337, &options), "1337");
+    assert_eq!(
+        Format::Dec.predicted_len(7992739871, &options),
+        "79927398713".len()
+    );
+
+    options.set_check(Some(Check::Mod97));
+    assert_eq!(Format::Dec.format_str(123456, &options), "12345676");
+    assert_eq!(12345676 % 97, 1);
+    options.set_min_digits(Some(10));
+    assert_eq!(Format::Dec.format_str(123456, &options), "0012345676");
+}
+
+#[test]
+fn sign_magnitude_width_8() {
+    let mut options = FormatOptions::default();
+    options.set_signed(true);
+    options.set_width(Some(8));
+    options.set_sign_magnitude(Some(8));
+    options.set_prefix(true);
+
+    let num = options
+        .transform(options.parse_number("-5").unwrap())
+        .unwrap();
+    assert_eq!(num, 0x85);
+    assert_eq!(Format::Bin.format_str(num, &options), "0b10000101");
+    assert_eq!(Format::Hex.format_str(num, &options), "0x85");
+    // two's complement for comparison
+    assert_eq!(options.parse_number("-5").unwrap(), 0xFB);
+    // signed decimal reads the sign-magnitude number back
+    assert_eq!(Format::Dec.format_str(num, &options), "-0d5");
+    let mut buf = [0u8; 16];
+    let len = Format::Dec.format_slice(num, &options, &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"-0d5");
+    assert_eq!(Format::Dec.predicted_len(num, &options), 4);
+    for format in [Format::Dec, Format::Hex, Format::Bin] {
+        options.verify_number(format, num).unwrap();
+    }
+    assert_eq!(from_sign_magnitude(num, 8).unwrap(), -5);
+
+    assert_eq!(options.transform(5).unwrap(), 5);
+    assert_eq!(options.transform(0).unwrap(), 0);
+    assert_eq!(options.transform(0x81).unwrap(), 0xFF);
+    // -128 would need the sign bit for its magnitude
+    assert!(options.transform(0x80).is_err());
+    assert!(options.transform(0x100).is_err());
+}
+
+#[test]
+fn all_formats() {
+    let mut options = FormatOptions::default();
+    let all = format_all_formats(0x1337, &options);
+    assert_eq!(all.len(), Format::ALL.len() - 1);
+    assert!(!all.iter().any(|(format, _)| *format == Format::Raw));
+    for (format, formatted) in &all {
+        assert_eq!(*formatted, format.format_str(0x1337, &options));
+    }
+
+    options.set_prefix(true);
+    options.set_padding(true);
+    let all = format_all_formats(0x1337, &options);
+    assert!(all.contains(&(Format::Hex, "0x1337".to_string())));
+    assert!(all.contains(&(Format::Bin, "0b0001001100110111".to_string())));
+    assert!(all.contains(&(Format::Dec, "0d4919".to_string())));
+
+    // --all selects the same formats for the other outputs, like --tabular
+    options.set_all(true);
+    let formats: Vec<Format> = all.iter().map(|(format, _)| *format).collect();
+    assert_eq!(options.formats(), formats);
+}
+
+#[test]
+fn warn_ambiguous() {
+    let mut options = FormatOptions::default();
+    options.set_warn_ambiguous(true);
+    assert_eq!(
+        options.ambiguous_formats(b"11"),
+        vec![Format::Dec, Format::Hex, Format::Bin, Format::Octal]
+    );
+    assert!(options.ambiguous_formats(b"0x11").is_empty());
+    assert!(options.ambiguous_formats(b"0d11").is_empty());
+    // the same in every base
+    assert!(options.ambiguous_formats(b"1").is_empty());
+    // only a warning, the number is still decimal
+    assert_eq!(options.parse_number("11").unwrap(), 11);
+
+    options.set_from(Some(Format::Hex));
+    assert!(options.ambiguous_formats(b"11").is_empty());
+}
+
+#[test]
+fn case_per_format() {
+    let mut options = FormatOptions::default();
+    options.set_prefix(true);
+    assert_eq!(Format::Hex.format_str(0xBEEF, &options), "0xBEEF");
+    assert_eq!(Format::Base16.format_str(0xBEEF, &options), "016sBEEF");
+
+    options.set_case(Some(Case::Lower));
+    assert_eq!(Format::Hex.format_str(0xBEEF, &options), "0xbeef");
+    assert_eq!(Format::Base16.format_str(0xBEEF, &options), "016sbeef");
+    assert_eq!(Format::Percent.format_str(0xBEEF, &options), "0p%be%ef");
+    assert_eq!(Format::Base32.format_str(0xBEEF, &options), "032sx3xq====");
+    // the case is part of the number
+    let base64 = Format::Base64.format_str(0xBEEF, &FormatOptions::default());
+    assert_eq!(
+        Format::Base64.format_str(0xBEEF, &options),
+        format!("0s{base64}")
+    );
+
+    // the fill in front of the prefix keeps its case too
+    options.set_pad_char('X');
+    options.set_min_digits(Some(6));
+    assert_eq!(Format::Hex.format_str(0xBEEF, &options), "XX0xbeef");
+    let mut buf = [0; 16];
+    let len = Format::Hex
+        .format_slice(0xBEEF, &options, &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..len], b"XX0xbeef");
+
+    // lowercase output can be read again
+    for format in [Format::Hex, Format::Base32, Format::Base16, Format::Percent] {
+        let formatted = format.format_str(0xBEEF, &options);
+        assert_eq!(
+            numf_parser_str::<u128>(formatted.trim_start_matches('X')).unwrap(),
+            0xBEEF
+        );
+    }
+}